@@ -318,42 +318,50 @@ pub fn get_ir_definition_references<'a>(
             ExecutableDefinition::Operation(_) => OperationDefinitionName(name).into(),
             ExecutableDefinition::Fragment(_) => FragmentDefinitionName(name).into(),
         };
-        let mut selections: Vec<_> = match definition {
+        let selections = match definition {
             ExecutableDefinition::Operation(definition) => &definition.selections,
             ExecutableDefinition::Fragment(definition) => &definition.selections,
-        }
-        .iter()
-        .collect();
-        let mut references: ExecutableDefinitionNameSet = Default::default();
-        while let Some(selection) = selections.pop() {
-            match selection {
-                Selection::FragmentSpread(selection) => {
-                    references.insert(selection.fragment.item.into());
-                }
-                Selection::LinkedField(selection) => {
-                    if let Some(fragment_name) = get_resolver_fragment_dependency_name(
-                        schema.field(selection.definition.item),
-                    ) {
-                        references.insert(fragment_name.into());
-                    }
-                    selections.extend(&selection.selections);
-                }
-                Selection::InlineFragment(selection) => {
-                    selections.extend(&selection.selections);
-                }
-                Selection::Condition(selection) => {
-                    selections.extend(&selection.selections);
+        };
+        result.insert(name, get_ir_selection_references(schema, selections));
+    }
+    result
+}
+
+/// Get fragment references of the selections of a definition: the fragments
+/// spread, and the root fragments of the Relay Resolvers selected.
+pub fn get_ir_selection_references(
+    schema: &SDLSchema,
+    selections: &[Selection],
+) -> ExecutableDefinitionNameSet {
+    let mut selections: Vec<_> = selections.iter().collect();
+    let mut references: ExecutableDefinitionNameSet = Default::default();
+    while let Some(selection) = selections.pop() {
+        match selection {
+            Selection::FragmentSpread(selection) => {
+                references.insert(selection.fragment.item.into());
+            }
+            Selection::LinkedField(selection) => {
+                if let Some(fragment_name) =
+                    get_resolver_fragment_dependency_name(schema.field(selection.definition.item))
+                {
+                    references.insert(fragment_name.into());
                 }
-                Selection::ScalarField(selection) => {
-                    if let Some(fragment_name) = get_resolver_fragment_dependency_name(
-                        schema.field(selection.definition.item),
-                    ) {
-                        references.insert(fragment_name.into());
-                    }
+                selections.extend(&selection.selections);
+            }
+            Selection::InlineFragment(selection) => {
+                selections.extend(&selection.selections);
+            }
+            Selection::Condition(selection) => {
+                selections.extend(&selection.selections);
+            }
+            Selection::ScalarField(selection) => {
+                if let Some(fragment_name) =
+                    get_resolver_fragment_dependency_name(schema.field(selection.definition.item))
+                {
+                    references.insert(fragment_name.into());
                 }
             }
         }
-        result.insert(name, references);
     }
-    result
+    references
 }
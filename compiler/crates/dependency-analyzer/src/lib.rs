@@ -14,13 +14,19 @@ mod ast;
 mod ir;
 mod minimized_executable;
 mod schema_change_analyzer;
+mod schema_usage;
 
 pub use ast::get_definition_references;
 pub use ast::get_reachable_ast;
 pub use ast::ReachableAst;
 pub use ir::get_ir_definition_references;
+pub use ir::get_ir_selection_references;
 pub use ir::get_reachable_ir;
 pub use ir::ExecutableDefinitionNameMap;
 pub use ir::ExecutableDefinitionNameSet;
 pub use ir::ExecutableDefinitionNameVec;
 pub use minimized_executable::MinProgram;
+pub use schema_change_analyzer::get_affected_definitions;
+pub use schema_change_analyzer::get_affected_definitions_with_dependents;
+pub use schema_usage::SchemaUsage;
+pub use schema_usage::UnusedSchemaItems;
@@ -14,7 +14,10 @@ use schema::SDLSchema;
 use schema::Schema;
 use schema_diff::check::IncrementalBuildSchemaChange;
 
+use crate::get_ir_definition_references;
+use crate::ExecutableDefinitionNameMap;
 use crate::ExecutableDefinitionNameSet;
+use crate::ExecutableDefinitionNameVec;
 
 pub fn get_affected_definitions(
     schema: &SDLSchema,
@@ -24,6 +27,33 @@ pub fn get_affected_definitions(
     SchemaChangeDefinitionFinder::get_definitions(schema, definitions, schema_changes)
 }
 
+/// Find the definitions that select the changed types, and the definitions
+/// that depend on them, through fragment spreads or Relay Resolvers.
+pub fn get_affected_definitions_with_dependents(
+    schema: &SDLSchema,
+    definitions: &[ExecutableDefinition],
+    schema_changes: FxHashSet<IncrementalBuildSchemaChange>,
+) -> ExecutableDefinitionNameSet {
+    let mut affected_definitions = get_affected_definitions(schema, definitions, schema_changes);
+    let mut dependents: ExecutableDefinitionNameMap<ExecutableDefinitionNameVec> =
+        Default::default();
+    for (name, references) in get_ir_definition_references(schema, definitions) {
+        for reference in references {
+            dependents.entry(reference).or_default().push(name);
+        }
+    }
+
+    let mut stack = affected_definitions.iter().copied().collect::<Vec<_>>();
+    while let Some(name) = stack.pop() {
+        for dependent in dependents.get(&name).into_iter().flatten() {
+            if affected_definitions.insert(*dependent) {
+                stack.push(*dependent);
+            }
+        }
+    }
+    affected_definitions
+}
+
 struct SchemaChangeDefinitionFinder<'a, 'b> {
     changed_definitions: ExecutableDefinitionNameSet,
     current_executable: &'a ExecutableDefinition,
@@ -132,7 +162,38 @@ impl SchemaChangeDefinitionFinder<'_, '_> {
                         .insert(self.get_name_from_executable());
                 }
             }
-            Type::InputObject(_) | Type::Scalar(_) => (),
+            Type::InputObject(id) => {
+                let input_object_name = self.schema.input_object(id).name.item.0;
+                if self
+                    .schema_changes
+                    .contains(&IncrementalBuildSchemaChange::InputObject(
+                        input_object_name,
+                    ))
+                {
+                    self.changed_definitions
+                        .insert(self.get_name_from_executable());
+                }
+            }
+            Type::Scalar(id) => {
+                let scalar_name = self.schema.scalar(id).name.item.0;
+                if self
+                    .schema_changes
+                    .contains(&IncrementalBuildSchemaChange::Scalar(scalar_name))
+                {
+                    self.changed_definitions
+                        .insert(self.get_name_from_executable());
+                }
+            }
+        }
+    }
+
+    /// The variables might be unused, or only passed to fragments.
+    fn add_type_changes_from_variable_definitions(
+        &mut self,
+        variable_definitions: &[VariableDefinition],
+    ) {
+        for variable_definition in variable_definitions {
+            self.add_type_changes(variable_definition.type_.inner());
         }
     }
 }
@@ -149,8 +210,14 @@ impl Visitor for SchemaChangeDefinitionFinder<'_, '_> {
         self.default_visit_linked_field(field);
     }
 
+    fn visit_operation(&mut self, operation: &OperationDefinition) {
+        self.add_type_changes_from_variable_definitions(&operation.variable_definitions);
+        self.default_visit_operation(operation);
+    }
+
     fn visit_fragment(&mut self, fragment: &FragmentDefinition) {
         self.add_type_changes(fragment.type_condition);
+        self.add_type_changes_from_variable_definitions(&fragment.variable_definitions);
         self.default_visit_fragment(fragment);
     }
 
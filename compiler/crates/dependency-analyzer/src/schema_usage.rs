@@ -0,0 +1,394 @@
+/*
+ * Copyright (c) Meta Platforms, Inc. and affiliates.
+ *
+ * This source code is licensed under the MIT license found in the
+ * LICENSE file in the root directory of this source tree.
+ */
+
+use common::ArgumentName;
+use graphql_ir::*;
+use intern::string_key::StringKey;
+use intern::Lookup;
+use rustc_hash::FxHashSet;
+use schema::definitions::Type;
+use schema::FieldID;
+use schema::SDLSchema;
+use schema::Schema;
+use schema::TypeReference;
+use serde::Serialize;
+
+/// The types, fields, arguments and enum values of a schema that are used by
+/// the programs. Items are collected by name, so that the usages of programs
+/// built with different instances of a schema, e.g. of the projects sharing a
+/// schema, can be merged.
+#[derive(Default)]
+pub struct SchemaUsage {
+    types: FxHashSet<StringKey>,
+    /// Fields of objects and interfaces, and fields of input objects.
+    fields: FxHashSet<(StringKey, StringKey)>,
+    arguments: FxHashSet<(StringKey, StringKey, StringKey)>,
+    enum_values: FxHashSet<(StringKey, StringKey)>,
+    /// Input types used with all their fields or values, and enums returned
+    /// by fields.
+    fully_used_types: FxHashSet<StringKey>,
+}
+
+/// The server schema items that are not used by any document, as schema
+/// coordinates, e.g. `User.name(format:)`.
+#[derive(Debug, Default, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct UnusedSchemaItems {
+    pub types: Vec<String>,
+    pub fields: Vec<String>,
+    pub arguments: Vec<String>,
+    pub enum_values: Vec<String>,
+}
+
+impl SchemaUsage {
+    pub fn add_program(&mut self, program: &Program) {
+        let mut collector = SchemaUsageCollector {
+            schema: &program.schema,
+            usage: self,
+        };
+        collector.visit_program(program);
+    }
+
+    /// Returns the items of the schema that are not used. The items of client
+    /// schema extensions are not reported, and the fields of unused types are
+    /// only reported as part of their type.
+    pub fn get_unused(&self, schema: &SDLSchema) -> UnusedSchemaItems {
+        let mut usage = SchemaUsage {
+            types: self.types.clone(),
+            fields: self.fields.clone(),
+            arguments: self.arguments.clone(),
+            enum_values: self.enum_values.clone(),
+            fully_used_types: Default::default(),
+        };
+        usage.add_abstract_type_usages(schema);
+
+        let mut types = schema
+            .get_type_map()
+            .map(|(_, type_)| *type_)
+            .filter(|type_| !is_builtin_type(schema, *type_) && !schema.is_extension_type(*type_))
+            .collect::<Vec<_>>();
+        types.sort_by_key(|type_| schema.get_type_name(*type_).lookup());
+
+        let mut unused = UnusedSchemaItems::default();
+        for type_ in types {
+            let type_name = schema.get_type_name(type_);
+            if !usage.types.contains(&type_name) {
+                unused.types.push(type_name.to_string());
+                continue;
+            }
+            match type_ {
+                Type::Object(_) | Type::Interface(_) => {
+                    for field_id in get_fields(schema, type_) {
+                        let field = schema.field(*field_id);
+                        let field_name = field.name.item;
+                        if field.is_extension || field_name.lookup().starts_with("__") {
+                            continue;
+                        }
+                        if !usage.fields.contains(&(type_name, field_name)) {
+                            unused.fields.push(format!("{}.{}", type_name, field_name));
+                            continue;
+                        }
+                        for argument in field.arguments.iter() {
+                            let argument_name = argument.name.item.0;
+                            if !usage
+                                .arguments
+                                .contains(&(type_name, field_name, argument_name))
+                            {
+                                unused.arguments.push(format!(
+                                    "{}.{}({}:)",
+                                    type_name, field_name, argument_name
+                                ));
+                            }
+                        }
+                    }
+                }
+                Type::InputObject(id) => {
+                    for field in schema.input_object(id).fields.iter() {
+                        let field_name = field.name.item.0;
+                        if !usage.fields.contains(&(type_name, field_name)) {
+                            unused.fields.push(format!("{}.{}", type_name, field_name));
+                        }
+                    }
+                }
+                Type::Enum(id) => {
+                    for value in &schema.enum_(id).values {
+                        if !usage.enum_values.contains(&(type_name, value.value)) {
+                            unused
+                                .enum_values
+                                .push(format!("{}.{}", type_name, value.value));
+                        }
+                    }
+                }
+                Type::Scalar(_) | Type::Union(_) => {}
+            }
+        }
+        unused
+    }
+
+    /// The server resolves the selections of abstract types on their concrete
+    /// types, so their possible types, and the fields and arguments of the
+    /// implementations, are used too.
+    fn add_abstract_type_usages(&mut self, schema: &SDLSchema) {
+        let mut types = self.types.iter().copied().collect::<Vec<_>>();
+        while let Some(type_name) = types.pop() {
+            let implementations = match schema.get_type(type_name) {
+                Some(Type::Interface(id)) => {
+                    let interface = schema.interface(id);
+                    interface
+                        .implementing_objects
+                        .iter()
+                        .map(|id| Type::Object(*id))
+                        .chain(
+                            interface
+                                .implementing_interfaces
+                                .iter()
+                                .map(|id| Type::Interface(*id)),
+                        )
+                        .collect::<Vec<_>>()
+                }
+                Some(Type::Union(id)) => schema
+                    .union(id)
+                    .members
+                    .iter()
+                    .map(|id| Type::Object(*id))
+                    .collect(),
+                _ => continue,
+            };
+            for implementation in implementations {
+                let implementation_name = schema.get_type_name(implementation);
+                for field_id in get_fields(schema, implementation) {
+                    let field_name = schema.field(*field_id).name.item;
+                    if self.fields.contains(&(type_name, field_name)) {
+                        self.fields.insert((implementation_name, field_name));
+                    }
+                }
+                let arguments = self
+                    .arguments
+                    .iter()
+                    .filter(|(argument_type, _, _)| *argument_type == type_name)
+                    .map(|(_, field_name, argument_name)| {
+                        (implementation_name, *field_name, *argument_name)
+                    })
+                    .collect::<Vec<_>>();
+                self.arguments.extend(arguments);
+                if self.types.insert(implementation_name) {
+                    types.push(implementation_name);
+                } else if matches!(implementation, Type::Interface(_)) {
+                    // Sub-interfaces pass the fields of this interface on.
+                    types.push(implementation_name);
+                }
+            }
+        }
+    }
+
+    /// Marks the type as used, with all the values that can be passed to an
+    /// input of this type, e.g. by a variable.
+    fn add_input_type(&mut self, schema: &SDLSchema, type_: Type) {
+        let type_name = schema.get_type_name(type_);
+        self.types.insert(type_name);
+        if !self.fully_used_types.insert(type_name) {
+            return;
+        }
+        match type_ {
+            Type::Enum(id) => {
+                for value in &schema.enum_(id).values {
+                    self.enum_values.insert((type_name, value.value));
+                }
+            }
+            Type::InputObject(id) => {
+                for field in schema.input_object(id).fields.iter() {
+                    self.fields.insert((type_name, field.name.item.0));
+                    self.add_input_type(schema, field.type_.inner());
+                }
+            }
+            _ => {}
+        }
+    }
+
+    /// Marks the output type as used. All values of an enum can be returned.
+    fn add_output_type(&mut self, schema: &SDLSchema, type_: Type) {
+        let type_name = schema.get_type_name(type_);
+        self.types.insert(type_name);
+        if self.fully_used_types.insert(type_name) {
+            if let Type::Enum(id) = type_ {
+                for value in &schema.enum_(id).values {
+                    self.enum_values.insert((type_name, value.value));
+                }
+            }
+        }
+    }
+
+    fn add_value(&mut self, schema: &SDLSchema, type_: &TypeReference<Type>, value: &Value) {
+        match value {
+            Value::Constant(value) => self.add_constant_value(schema, type_, value),
+            Value::Variable(variable) => self.add_input_type(schema, variable.type_.inner()),
+            Value::List(values) => {
+                let item_type = type_.list_item_type().unwrap_or(type_);
+                for value in values {
+                    self.add_value(schema, item_type, value);
+                }
+            }
+            Value::Object(fields) => {
+                let type_name = self.add_input_object(schema, type_);
+                for field in fields {
+                    self.fields.insert((type_name, field.name.item.0));
+                    if let Some(field_type) = get_input_field_type(schema, type_, field.name.item) {
+                        self.add_value(schema, field_type, &field.value.item);
+                    }
+                }
+            }
+        }
+    }
+
+    fn add_constant_value(
+        &mut self,
+        schema: &SDLSchema,
+        type_: &TypeReference<Type>,
+        value: &ConstantValue,
+    ) {
+        match value {
+            ConstantValue::Enum(enum_value) => {
+                let type_name = schema.get_type_name(type_.inner());
+                self.types.insert(type_name);
+                self.enum_values.insert((type_name, *enum_value));
+            }
+            ConstantValue::List(values) => {
+                let item_type = type_.list_item_type().unwrap_or(type_);
+                for value in values {
+                    self.add_constant_value(schema, item_type, value);
+                }
+            }
+            ConstantValue::Object(fields) => {
+                let type_name = self.add_input_object(schema, type_);
+                for field in fields {
+                    self.fields.insert((type_name, field.name.item.0));
+                    if let Some(field_type) = get_input_field_type(schema, type_, field.name.item) {
+                        self.add_constant_value(schema, field_type, &field.value.item);
+                    }
+                }
+            }
+            ConstantValue::Int(_)
+            | ConstantValue::Float(_)
+            | ConstantValue::String(_)
+            | ConstantValue::Boolean(_)
+            | ConstantValue::Null() => {
+                self.types.insert(schema.get_type_name(type_.inner()));
+            }
+        }
+    }
+
+    fn add_input_object(&mut self, schema: &SDLSchema, type_: &TypeReference<Type>) -> StringKey {
+        let type_name = schema.get_type_name(type_.inner());
+        self.types.insert(type_name);
+        type_name
+    }
+}
+
+struct SchemaUsageCollector<'a> {
+    schema: &'a SDLSchema,
+    usage: &'a mut SchemaUsage,
+}
+
+impl SchemaUsageCollector<'_> {
+    fn add_field(&mut self, field_id: FieldID, arguments: &[Argument]) {
+        let field = self.schema.field(field_id);
+        let Some(parent_type) = field.parent_type else {
+            // `__typename` and `__id` are defined on every type.
+            return;
+        };
+        let parent_name = self.schema.get_type_name(parent_type);
+        self.usage.types.insert(parent_name);
+        self.usage.fields.insert((parent_name, field.name.item));
+        self.usage.add_output_type(self.schema, field.type_.inner());
+        for argument in arguments {
+            self.usage
+                .arguments
+                .insert((parent_name, field.name.item, argument.name.item.0));
+            if let Some(definition) = field.arguments.named(argument.name.item) {
+                self.usage
+                    .add_value(self.schema, &definition.type_, &argument.value.item);
+            }
+        }
+    }
+}
+
+impl Visitor for SchemaUsageCollector<'_> {
+    const NAME: &'static str = "SchemaUsageCollector";
+    const VISIT_ARGUMENTS: bool = false;
+    const VISIT_DIRECTIVES: bool = false;
+
+    fn visit_operation(&mut self, operation: &OperationDefinition) {
+        self.usage
+            .types
+            .insert(self.schema.get_type_name(operation.type_));
+        for variable in &operation.variable_definitions {
+            self.usage
+                .add_input_type(self.schema, variable.type_.inner());
+        }
+        self.default_visit_operation(operation);
+    }
+
+    fn visit_fragment(&mut self, fragment: &FragmentDefinition) {
+        self.usage
+            .types
+            .insert(self.schema.get_type_name(fragment.type_condition));
+        for variable in &fragment.variable_definitions {
+            self.usage
+                .add_input_type(self.schema, variable.type_.inner());
+        }
+        self.default_visit_fragment(fragment);
+    }
+
+    fn visit_inline_fragment(&mut self, fragment: &InlineFragment) {
+        if let Some(type_condition) = fragment.type_condition {
+            self.usage
+                .types
+                .insert(self.schema.get_type_name(type_condition));
+        }
+        self.default_visit_inline_fragment(fragment);
+    }
+
+    fn visit_scalar_field(&mut self, field: &ScalarField) {
+        self.add_field(field.definition.item, &field.arguments);
+        self.default_visit_scalar_field(field);
+    }
+
+    fn visit_linked_field(&mut self, field: &LinkedField) {
+        self.add_field(field.definition.item, &field.arguments);
+        self.default_visit_linked_field(field);
+    }
+}
+
+fn get_fields(schema: &SDLSchema, type_: Type) -> &[FieldID] {
+    match type_ {
+        Type::Object(id) => &schema.object(id).fields,
+        Type::Interface(id) => &schema.interface(id).fields,
+        _ => &[],
+    }
+}
+
+fn get_input_field_type<'a>(
+    schema: &'a SDLSchema,
+    type_: &TypeReference<Type>,
+    name: ArgumentName,
+) -> Option<&'a TypeReference<Type>> {
+    match type_.inner() {
+        Type::InputObject(id) => schema
+            .input_object(id)
+            .fields
+            .named(name)
+            .map(|field| &field.type_),
+        _ => None,
+    }
+}
+
+fn is_builtin_type(schema: &SDLSchema, type_: Type) -> bool {
+    let name = schema.get_type_name(type_);
+    name.lookup().starts_with("__")
+        || matches!(type_, Type::Scalar(_))
+            && matches!(name.lookup(), "ID" | "String" | "Int" | "Float" | "Boolean")
+}
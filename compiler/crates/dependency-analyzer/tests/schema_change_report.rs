@@ -0,0 +1,66 @@
+/*
+ * Copyright (c) Meta Platforms, Inc. and affiliates.
+ *
+ * This source code is licensed under the MIT license found in the
+ * LICENSE file in the root directory of this source tree.
+ */
+
+use common::SourceLocationKey;
+use dependency_analyzer::*;
+use fixture_tests::Fixture;
+use graphql_ir::*;
+use graphql_syntax::parse_executable;
+use relay_test_schema::get_test_schema_with_extensions;
+use schema_diff::criticality::get_unsafe_type_changes;
+use schema_diff::detect_changes;
+
+// The fixture contains the documents, the schema extensions they are valid
+// against after `%previous%`, and the changed schema extensions after
+// `%current%`.
+pub async fn transform_fixture(fixture: &Fixture<'_>) -> Result<String, String> {
+    let parts = fixture.content.split("%previous%").collect::<Vec<_>>();
+    let (content, extensions) = match parts.as_slice() {
+        [content, extensions] => (content, extensions),
+        _ => panic!("Expected a \"%previous%\" section in the fixture."),
+    };
+    let (previous, current) = match extensions.split("%current%").collect::<Vec<_>>().as_slice() {
+        [previous, current] => (*previous, *current),
+        _ => panic!("Expected a \"%current%\" section in the fixture."),
+    };
+
+    let schema = get_test_schema_with_extensions(previous);
+    let source_location = SourceLocationKey::standalone(fixture.file_name);
+    let asts = parse_executable(content, source_location)
+        .unwrap()
+        .definitions;
+    let definitions = build(&schema, &asts).unwrap();
+
+    let changes = detect_changes(&[current], &[previous]).classify();
+    let affected_definitions = get_affected_definitions_with_dependents(
+        &schema,
+        &definitions,
+        get_unsafe_type_changes(&changes),
+    );
+
+    let mut affected_definitions = affected_definitions
+        .into_iter()
+        .map(|name| match name {
+            ExecutableDefinitionName::OperationDefinitionName(name) => {
+                format!("Operation: {}", name)
+            }
+            ExecutableDefinitionName::FragmentDefinitionName(name) => {
+                format!("Fragment: {}", name)
+            }
+        })
+        .collect::<Vec<_>>();
+    affected_definitions.sort();
+    let changes = changes
+        .iter()
+        .map(|change| format!("{}: {}", change.criticality, change.message))
+        .collect::<Vec<_>>();
+    Ok(format!(
+        "{}\n\nAffected definitions:\n{}",
+        changes.join("\n"),
+        affected_definitions.join("\n")
+    ))
+}
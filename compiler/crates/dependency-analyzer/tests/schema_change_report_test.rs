@@ -0,0 +1,55 @@
+/*
+ * Copyright (c) Meta Platforms, Inc. and affiliates.
+ *
+ * This source code is licensed under the MIT license found in the
+ * LICENSE file in the root directory of this source tree.
+ *
+ * @generated SignedSource<<f5af55a995a4a64263d29ef8f60f5f6c>>
+ */
+
+mod schema_change_report;
+
+use schema_change_report::transform_fixture;
+use fixture_tests::test_fixture;
+
+#[tokio::test]
+async fn enum_value_and_union_member_added() {
+    let input = include_str!("schema_change_report/fixtures/enum-value-and-union-member-added.graphql");
+    let expected = include_str!("schema_change_report/fixtures/enum-value-and-union-member-added.expected");
+    test_fixture(transform_fixture, file!(), "enum-value-and-union-member-added.graphql", "schema_change_report/fixtures/enum-value-and-union-member-added.expected", input, expected).await;
+}
+
+#[tokio::test]
+async fn field_and_argument_types_changed() {
+    let input = include_str!("schema_change_report/fixtures/field-and-argument-types-changed.graphql");
+    let expected = include_str!("schema_change_report/fixtures/field-and-argument-types-changed.expected");
+    test_fixture(transform_fixture, file!(), "field-and-argument-types-changed.graphql", "schema_change_report/fixtures/field-and-argument-types-changed.expected", input, expected).await;
+}
+
+#[tokio::test]
+async fn field_removed() {
+    let input = include_str!("schema_change_report/fixtures/field-removed.graphql");
+    let expected = include_str!("schema_change_report/fixtures/field-removed.expected");
+    test_fixture(transform_fixture, file!(), "field-removed.graphql", "schema_change_report/fixtures/field-removed.expected", input, expected).await;
+}
+
+#[tokio::test]
+async fn input_object_field_removed() {
+    let input = include_str!("schema_change_report/fixtures/input-object-field-removed.graphql");
+    let expected = include_str!("schema_change_report/fixtures/input-object-field-removed.expected");
+    test_fixture(transform_fixture, file!(), "input-object-field-removed.graphql", "schema_change_report/fixtures/input-object-field-removed.expected", input, expected).await;
+}
+
+#[tokio::test]
+async fn safe_changes_only() {
+    let input = include_str!("schema_change_report/fixtures/safe-changes-only.graphql");
+    let expected = include_str!("schema_change_report/fixtures/safe-changes-only.expected");
+    test_fixture(transform_fixture, file!(), "safe-changes-only.graphql", "schema_change_report/fixtures/safe-changes-only.expected", input, expected).await;
+}
+
+#[tokio::test]
+async fn scalar_and_input_object_removed() {
+    let input = include_str!("schema_change_report/fixtures/scalar-and-input-object-removed.graphql");
+    let expected = include_str!("schema_change_report/fixtures/scalar-and-input-object-removed.expected");
+    test_fixture(transform_fixture, file!(), "scalar-and-input-object-removed.graphql", "schema_change_report/fixtures/scalar-and-input-object-removed.expected", input, expected).await;
+}
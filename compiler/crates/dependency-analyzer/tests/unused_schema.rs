@@ -0,0 +1,36 @@
+/*
+ * Copyright (c) Meta Platforms, Inc. and affiliates.
+ *
+ * This source code is licensed under the MIT license found in the
+ * LICENSE file in the root directory of this source tree.
+ */
+
+use std::sync::Arc;
+
+use common::SourceLocationKey;
+use dependency_analyzer::*;
+use fixture_tests::Fixture;
+use graphql_ir::*;
+use graphql_syntax::parse_executable;
+use schema::build_schema;
+
+// The fixture contains the documents, and the schema after `%schema%`.
+pub async fn transform_fixture(fixture: &Fixture<'_>) -> Result<String, String> {
+    let parts = fixture.content.split("%schema%").collect::<Vec<_>>();
+    let (content, sdl) = match parts.as_slice() {
+        [content, sdl] => (content, sdl),
+        _ => panic!("Expected a \"%schema%\" section in the fixture."),
+    };
+
+    let schema = Arc::new(build_schema(sdl).unwrap());
+    let source_location = SourceLocationKey::standalone(fixture.file_name);
+    let asts = parse_executable(content, source_location)
+        .unwrap()
+        .definitions;
+    let definitions = build(&schema, &asts).unwrap();
+    let program = Program::from_definitions(Arc::clone(&schema), definitions);
+
+    let mut usage = SchemaUsage::default();
+    usage.add_program(&program);
+    Ok(serde_json::to_string_pretty(&usage.get_unused(&schema)).unwrap())
+}
@@ -0,0 +1,41 @@
+/*
+ * Copyright (c) Meta Platforms, Inc. and affiliates.
+ *
+ * This source code is licensed under the MIT license found in the
+ * LICENSE file in the root directory of this source tree.
+ *
+ * @generated SignedSource<<fc196f55a32dc4f79265ab4b45791ea9>>
+ */
+
+mod unused_schema;
+
+use unused_schema::transform_fixture;
+use fixture_tests::test_fixture;
+
+#[tokio::test]
+async fn abstract_types() {
+    let input = include_str!("unused_schema/fixtures/abstract-types.graphql");
+    let expected = include_str!("unused_schema/fixtures/abstract-types.expected");
+    test_fixture(transform_fixture, file!(), "abstract-types.graphql", "unused_schema/fixtures/abstract-types.expected", input, expected).await;
+}
+
+#[tokio::test]
+async fn enums_and_input_objects() {
+    let input = include_str!("unused_schema/fixtures/enums-and-input-objects.graphql");
+    let expected = include_str!("unused_schema/fixtures/enums-and-input-objects.expected");
+    test_fixture(transform_fixture, file!(), "enums-and-input-objects.graphql", "unused_schema/fixtures/enums-and-input-objects.expected", input, expected).await;
+}
+
+#[tokio::test]
+async fn fields_and_arguments() {
+    let input = include_str!("unused_schema/fixtures/fields-and-arguments.graphql");
+    let expected = include_str!("unused_schema/fixtures/fields-and-arguments.expected");
+    test_fixture(transform_fixture, file!(), "fields-and-arguments.graphql", "unused_schema/fixtures/fields-and-arguments.expected", input, expected).await;
+}
+
+#[tokio::test]
+async fn fragments() {
+    let input = include_str!("unused_schema/fixtures/fragments.graphql");
+    let expected = include_str!("unused_schema/fixtures/fragments.expected");
+    test_fixture(transform_fixture, file!(), "fragments.graphql", "unused_schema/fixtures/fragments.expected", input, expected).await;
+}
@@ -155,7 +155,25 @@ pub fn build_ir_with_extra_features(
     definitions: &[graphql_syntax::ExecutableDefinition],
     options: &BuilderOptions,
 ) -> DiagnosticsResult<Vec<ExecutableDefinition>> {
-    let signatures = build_signatures(schema, definitions)?;
+    build_ir_with_external_fragments(schema, definitions, std::iter::empty(), options)
+}
+
+/// Converts definitions that may spread fragments defined outside of the
+/// corpus into typed IR. Spreads of the `external_fragments` are checked
+/// against their signature, and the fragments of the corpus take precedence
+/// over external fragments with the same name.
+pub fn build_ir_with_external_fragments<'a>(
+    schema: &SDLSchema,
+    definitions: &[graphql_syntax::ExecutableDefinition],
+    external_fragments: impl IntoIterator<Item = &'a FragmentDefinition>,
+    options: &BuilderOptions,
+) -> DiagnosticsResult<Vec<ExecutableDefinition>> {
+    let mut signatures = build_signatures(schema, definitions)?;
+    for fragment in external_fragments {
+        signatures
+            .entry(fragment.name.item)
+            .or_insert_with(|| FragmentSignature::from(fragment));
+    }
     par_try_map(definitions, |definition| {
         let mut builder = Builder::new(schema, &signatures, definition.location(), options);
         builder.build_definition(definition)
@@ -8,11 +8,13 @@
 use std::fmt::Display;
 
 use common::ArgumentName;
+use common::Diagnostic;
 use common::DiagnosticDisplay;
 use common::DirectiveName;
 use common::ScalarName;
 use common::WithDiagnosticData;
 use graphql_syntax::OperationKind;
+use intern::string_key::Intern;
 use intern::string_key::StringKey;
 use intern::Lookup;
 use schema::suggestion_list::did_you_mean;
@@ -622,6 +624,60 @@ impl WithDiagnosticData for ValidationMessageWithData {
     }
 }
 
+const FIX_KEY: &str = "fix";
+const VARIABLE_NAME_KEY: &str = "variable_name";
+const TYPE_KEY: &str = "type";
+
+/// Mechanical fixes of validation errors, which are attached to their
+/// diagnostics as machine-readable metadata so that tools like the language
+/// server can apply them.
+#[derive(Clone, Debug, Eq, PartialEq, serde::Serialize, serde::Deserialize)]
+#[serde(tag = "fix")]
+pub enum ValidationFix {
+    /// Removes the definition of an unused variable from the operation, or
+    /// from the arguments of the fragment.
+    RemoveVariableDefinition { variable_name: VariableName },
+
+    /// Declares a variable used in a fragment as an argument of the fragment.
+    DeclareFragmentArgument {
+        variable_name: VariableName,
+        #[serde(rename = "type")]
+        type_: String,
+    },
+}
+
+impl ValidationFix {
+    pub fn attach_to(self, diagnostic: Diagnostic) -> Diagnostic {
+        match self {
+            ValidationFix::RemoveVariableDefinition { variable_name } => diagnostic
+                .metadata_for_machine(FIX_KEY, "RemoveVariableDefinition")
+                .metadata_for_machine(VARIABLE_NAME_KEY, variable_name.0.lookup()),
+            ValidationFix::DeclareFragmentArgument {
+                variable_name,
+                type_,
+            } => diagnostic
+                .metadata_for_machine(FIX_KEY, "DeclareFragmentArgument")
+                .metadata_for_machine(VARIABLE_NAME_KEY, variable_name.0.lookup())
+                .metadata_for_machine(TYPE_KEY, type_),
+        }
+    }
+
+    pub fn from_diagnostic(diagnostic: &Diagnostic) -> Option<Self> {
+        let metadata = diagnostic.machine_readable()?;
+        let variable_name = VariableName(metadata.get(VARIABLE_NAME_KEY)?.intern());
+        match metadata.get(FIX_KEY)?.as_str() {
+            "RemoveVariableDefinition" => {
+                Some(ValidationFix::RemoveVariableDefinition { variable_name })
+            }
+            "DeclareFragmentArgument" => Some(ValidationFix::DeclareFragmentArgument {
+                variable_name,
+                type_: metadata.get(TYPE_KEY)?.clone(),
+            }),
+            _ => None,
+        }
+    }
+}
+
 fn into_box(item: StringKey) -> Box<dyn DiagnosticDisplay> {
     Box::new(item)
 }
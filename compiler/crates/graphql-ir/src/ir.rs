@@ -248,7 +248,8 @@ impl ExecutableDefinitionName {
     Ord,
     PartialOrd,
     Hash,
-    serde::Serialize
+    serde::Serialize,
+    serde::Deserialize
 )]
 pub struct VariableName(pub StringKey);
 
@@ -26,6 +26,7 @@ pub use associated_data::AssociatedData;
 pub use build::build_directive;
 pub use build::build_ir as build;
 pub use build::build_ir_in_relay_mode;
+pub use build::build_ir_with_external_fragments;
 pub use build::build_ir_with_extra_features;
 pub use build::BuilderOptions;
 pub use build::FragmentVariablesSemantic;
@@ -47,6 +48,7 @@ pub use transform::Transformer;
 pub use validator::Validator;
 pub use visitor::Visitor;
 
+pub use crate::errors::ValidationFix;
 pub use crate::errors::ValidationMessage;
 pub use crate::errors::ValidationMessageWithData;
 
@@ -0,0 +1,74 @@
+/*
+ * Copyright (c) Meta Platforms, Inc. and affiliates.
+ *
+ * This source code is licensed under the MIT license found in the
+ * LICENSE file in the root directory of this source tree.
+ */
+
+use common::SourceLocationKey;
+use common::TextSource;
+use fixture_tests::Fixture;
+use graphql_cli::DiagnosticPrinter;
+use graphql_ir::build;
+use graphql_ir::build_ir_with_external_fragments;
+use graphql_ir::BuilderOptions;
+use graphql_ir::ExecutableDefinition;
+use graphql_ir::FragmentVariablesSemantic;
+use graphql_ir::RelayMode;
+use graphql_syntax::parse_executable;
+use relay_test_schema::TEST_SCHEMA;
+
+// The fixture contains the document, and the external fragments after
+// `%external%`.
+pub async fn transform_fixture(fixture: &Fixture<'_>) -> Result<String, String> {
+    let (source, external_source) = fixture.content.split_once("%external%").unwrap();
+    let print_errors = |errors: Vec<common::Diagnostic>| {
+        errors
+            .into_iter()
+            .map(|error| {
+                let printer = DiagnosticPrinter::new(|source_location| {
+                    Some(TextSource::from_whole_document(
+                        if source_location == SourceLocationKey::standalone("external.graphql") {
+                            external_source
+                        } else {
+                            source
+                        }
+                        .to_string(),
+                    ))
+                });
+                printer.diagnostic_to_string(&error)
+            })
+            .collect::<Vec<_>>()
+            .join("\n\n")
+    };
+
+    let external_ast = parse_executable(
+        external_source,
+        SourceLocationKey::standalone("external.graphql"),
+    )
+    .unwrap();
+    let external_fragments = build(&TEST_SCHEMA, &external_ast.definitions)
+        .map_err(print_errors)?
+        .into_iter()
+        .filter_map(|definition| match definition {
+            ExecutableDefinition::Fragment(fragment) => Some(fragment),
+            ExecutableDefinition::Operation(_) => None,
+        })
+        .collect::<Vec<_>>();
+
+    let ast = parse_executable(source, SourceLocationKey::standalone(fixture.file_name)).unwrap();
+    build_ir_with_external_fragments(
+        &TEST_SCHEMA,
+        &ast.definitions,
+        &external_fragments,
+        &BuilderOptions {
+            allow_undefined_fragment_spreads: true,
+            fragment_variables_semantic: FragmentVariablesSemantic::PassedValue,
+            relay_mode: Some(RelayMode),
+            default_anonymous_operation_name: None,
+            allow_custom_scalar_literals: true,
+        },
+    )
+    .map(|definitions| format!("{:#?}", definitions))
+    .map_err(print_errors)
+}
@@ -0,0 +1,41 @@
+/*
+ * Copyright (c) Meta Platforms, Inc. and affiliates.
+ *
+ * This source code is licensed under the MIT license found in the
+ * LICENSE file in the root directory of this source tree.
+ *
+ * @generated SignedSource<<d2b5b33ba15105dec36c6f70b83ce39a>>
+ */
+
+mod build_with_external_fragments;
+
+use build_with_external_fragments::transform_fixture;
+use fixture_tests::test_fixture;
+
+#[tokio::test]
+async fn invalid_spread_type_invalid() {
+    let input = include_str!("build_with_external_fragments/fixtures/invalid-spread-type.invalid.graphql");
+    let expected = include_str!("build_with_external_fragments/fixtures/invalid-spread-type.invalid.expected");
+    test_fixture(transform_fixture, file!(), "invalid-spread-type.invalid.graphql", "build_with_external_fragments/fixtures/invalid-spread-type.invalid.expected", input, expected).await;
+}
+
+#[tokio::test]
+async fn local_fragment_takes_precedence() {
+    let input = include_str!("build_with_external_fragments/fixtures/local-fragment-takes-precedence.graphql");
+    let expected = include_str!("build_with_external_fragments/fixtures/local-fragment-takes-precedence.expected");
+    test_fixture(transform_fixture, file!(), "local-fragment-takes-precedence.graphql", "build_with_external_fragments/fixtures/local-fragment-takes-precedence.expected", input, expected).await;
+}
+
+#[tokio::test]
+async fn missing_required_argument_invalid() {
+    let input = include_str!("build_with_external_fragments/fixtures/missing-required-argument.invalid.graphql");
+    let expected = include_str!("build_with_external_fragments/fixtures/missing-required-argument.invalid.expected");
+    test_fixture(transform_fixture, file!(), "missing-required-argument.invalid.graphql", "build_with_external_fragments/fixtures/missing-required-argument.invalid.expected", input, expected).await;
+}
+
+#[tokio::test]
+async fn unknown_argument_invalid() {
+    let input = include_str!("build_with_external_fragments/fixtures/unknown-argument.invalid.graphql");
+    let expected = include_str!("build_with_external_fragments/fixtures/unknown-argument.invalid.expected");
+    test_fixture(transform_fixture, file!(), "unknown-argument.invalid.graphql", "build_with_external_fragments/fixtures/unknown-argument.invalid.expected", input, expected).await;
+}
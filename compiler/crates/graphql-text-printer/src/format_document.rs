@@ -0,0 +1,1053 @@
+/*
+ * Copyright (c) Meta Platforms, Inc. and affiliates.
+ *
+ * This source code is licensed under the MIT license found in the
+ * LICENSE file in the root directory of this source tree.
+ */
+
+//! Formats the text of executable and schema documents from their syntax
+//! tree. Unlike `print_ast_to_text`, the comments, descriptions and blank
+//! lines of the source are kept.
+
+use common::DiagnosticsResult;
+use common::SourceLocationKey;
+use common::Span;
+use graphql_syntax::parse_executable_with_features;
+use graphql_syntax::parse_schema_document;
+use graphql_syntax::Argument;
+use graphql_syntax::ConstantArgument;
+use graphql_syntax::ConstantDirective;
+use graphql_syntax::ConstantValue;
+use graphql_syntax::Directive;
+use graphql_syntax::EnumValueDefinition;
+use graphql_syntax::ExecutableDefinition;
+use graphql_syntax::FieldDefinition;
+use graphql_syntax::FragmentDefinition;
+use graphql_syntax::Identifier;
+use graphql_syntax::InputValueDefinition;
+use graphql_syntax::List;
+use graphql_syntax::OperationDefinition;
+use graphql_syntax::OperationTypeDefinition;
+use graphql_syntax::ParserFeatures;
+use graphql_syntax::Selection;
+use graphql_syntax::TypeSystemDefinition;
+use graphql_syntax::Value;
+use graphql_syntax::VariableDefinition;
+use intern::string_key::StringKey;
+use intern::Lookup;
+
+const INDENT: &str = "  ";
+
+/// Lines longer than this print their arguments or variable definitions one
+/// per line.
+const PRINT_WIDTH: usize = 80;
+
+/// Formats the operations and fragments of a document, with two spaces of
+/// indentation per level and a blank line between definitions. Returns the
+/// parser diagnostics if the document has syntax errors.
+pub fn format_executable_document(
+    source: &str,
+    source_location: SourceLocationKey,
+    features: ParserFeatures,
+) -> DiagnosticsResult<String> {
+    let document = parse_executable_with_features(source, source_location, features)?;
+    let mut formatter = DocumentFormatter::new(source);
+    formatter.format_definitions(&document.definitions);
+    Ok(formatter.output)
+}
+
+/// Formats the type system definitions and extensions of a schema document,
+/// like `format_executable_document`. Returns the parser diagnostics if the
+/// document has syntax errors.
+pub fn format_schema_document(
+    source: &str,
+    source_location: SourceLocationKey,
+) -> DiagnosticsResult<String> {
+    let document = parse_schema_document(source, source_location)?;
+    let mut formatter = DocumentFormatter::new(source);
+    formatter.format_type_system_definitions(&document.definitions);
+    Ok(formatter.output)
+}
+
+/// The byte range of a `#` comment in the source, without its line break.
+struct Comment {
+    start: usize,
+    end: usize,
+}
+
+/// A directive of an executable or a schema document.
+trait DirectiveNode {
+    type Argument: ListItem;
+
+    fn name(&self) -> StringKey;
+
+    fn arguments(&self) -> Option<&List<Self::Argument>>;
+}
+
+impl DirectiveNode for Directive {
+    type Argument = Argument;
+
+    fn name(&self) -> StringKey {
+        self.name.value
+    }
+
+    fn arguments(&self) -> Option<&List<Argument>> {
+        self.arguments.as_ref()
+    }
+}
+
+impl DirectiveNode for ConstantDirective {
+    type Argument = ConstantArgument;
+
+    fn name(&self) -> StringKey {
+        self.name.value
+    }
+
+    fn arguments(&self) -> Option<&List<ConstantArgument>> {
+        self.arguments.as_ref()
+    }
+}
+
+/// An item of a list that is printed on one line, or one item per line.
+trait ListItem {
+    fn span(&self) -> Span;
+
+    /// Renders the item on one line.
+    fn render(&self, source: &str) -> String;
+
+    /// Prints the item, breaking the lists and objects in it that have
+    /// comments so that the comments stay where they are.
+    fn print(&self, formatter: &mut DocumentFormatter<'_>, indent: &str);
+}
+
+impl ListItem for Argument {
+    fn span(&self) -> Span {
+        self.span
+    }
+
+    fn render(&self, source: &str) -> String {
+        format!("{}: {}", self.name.value, render_value(source, &self.value))
+    }
+
+    fn print(&self, formatter: &mut DocumentFormatter<'_>, indent: &str) {
+        formatter.output.push_str(self.name.value.lookup());
+        formatter.output.push_str(": ");
+        self.value.print(formatter, indent);
+    }
+}
+
+impl ListItem for ConstantArgument {
+    fn span(&self) -> Span {
+        self.span
+    }
+
+    fn render(&self, source: &str) -> String {
+        format!(
+            "{}: {}",
+            self.name.value,
+            render_constant_value(source, &self.value)
+        )
+    }
+
+    fn print(&self, formatter: &mut DocumentFormatter<'_>, indent: &str) {
+        formatter.output.push_str(self.name.value.lookup());
+        formatter.output.push_str(": ");
+        self.value.print(formatter, indent);
+    }
+}
+
+impl ListItem for VariableDefinition {
+    fn span(&self) -> Span {
+        self.span
+    }
+
+    fn render(&self, source: &str) -> String {
+        let mut result = format!("{}: {}", self.name, self.type_);
+        if let Some(default_value) = &self.default_value {
+            result.push_str(" = ");
+            result.push_str(&render_constant_value(source, &default_value.value));
+        }
+        result.push_str(&render_directives(source, &self.directives));
+        result
+    }
+
+    fn print(&self, formatter: &mut DocumentFormatter<'_>, indent: &str) {
+        formatter
+            .output
+            .push_str(&format!("{}: {}", self.name, self.type_));
+        if let Some(default_value) = &self.default_value {
+            formatter.output.push_str(" = ");
+            default_value.value.print(formatter, indent);
+        }
+        formatter.print_directives(&self.directives, indent, false);
+    }
+}
+
+impl ListItem for Value {
+    fn span(&self) -> Span {
+        self.span()
+    }
+
+    fn render(&self, source: &str) -> String {
+        render_value(source, self)
+    }
+
+    fn print(&self, formatter: &mut DocumentFormatter<'_>, indent: &str) {
+        match self {
+            Value::Constant(value) => value.print(formatter, indent),
+            Value::List(list) if formatter.has_comment_in(list.span) => {
+                formatter.print_list(list, indent)
+            }
+            Value::Object(object) if formatter.has_comment_in(object.span) => {
+                formatter.print_list(object, indent)
+            }
+            _ => formatter.output.push_str(&self.render(formatter.source)),
+        }
+    }
+}
+
+impl ListItem for ConstantValue {
+    fn span(&self) -> Span {
+        self.span()
+    }
+
+    fn render(&self, source: &str) -> String {
+        render_constant_value(source, self)
+    }
+
+    fn print(&self, formatter: &mut DocumentFormatter<'_>, indent: &str) {
+        match self {
+            ConstantValue::List(list) if formatter.has_comment_in(list.span) => {
+                formatter.print_list(list, indent)
+            }
+            ConstantValue::Object(object) if formatter.has_comment_in(object.span) => {
+                formatter.print_list(object, indent)
+            }
+            _ => formatter.output.push_str(&self.render(formatter.source)),
+        }
+    }
+}
+
+/// The span of a definition starts at its name, after its description.
+impl ListItem for FieldDefinition {
+    fn span(&self) -> Span {
+        Span::new(self.name.span.start, self.span.end)
+    }
+
+    fn render(&self, source: &str) -> String {
+        let arguments = self
+            .arguments
+            .as_ref()
+            .map_or_else(String::new, |arguments| render_list(source, arguments));
+        format!(
+            "{}{}: {}{}",
+            self.name.value,
+            arguments,
+            self.type_,
+            render_directives(source, &self.directives)
+        )
+    }
+
+    fn print(&self, formatter: &mut DocumentFormatter<'_>, indent: &str) {
+        formatter.print_header(
+            indent,
+            self.name.value.to_string(),
+            self.arguments.as_ref(),
+            &format!(": {}", self.type_),
+            &self.directives,
+            false,
+        );
+    }
+}
+
+impl ListItem for InputValueDefinition {
+    fn span(&self) -> Span {
+        Span::new(self.name.span.start, self.span.end)
+    }
+
+    fn render(&self, source: &str) -> String {
+        let mut result = format!("{}: {}", self.name.value, self.type_);
+        if let Some(default_value) = &self.default_value {
+            result.push_str(" = ");
+            result.push_str(&render_constant_value(source, &default_value.value));
+        }
+        result.push_str(&render_directives(source, &self.directives));
+        result
+    }
+
+    fn print(&self, formatter: &mut DocumentFormatter<'_>, indent: &str) {
+        formatter
+            .output
+            .push_str(&format!("{}: {}", self.name.value, self.type_));
+        if let Some(default_value) = &self.default_value {
+            formatter.output.push_str(" = ");
+            default_value.value.print(formatter, indent);
+        }
+        formatter.print_directives(&self.directives, indent, false);
+    }
+}
+
+impl ListItem for EnumValueDefinition {
+    fn span(&self) -> Span {
+        Span::new(self.name.span.start, self.span.end)
+    }
+
+    fn render(&self, source: &str) -> String {
+        format!(
+            "{}{}",
+            self.name.value,
+            render_directives(source, &self.directives)
+        )
+    }
+
+    fn print(&self, formatter: &mut DocumentFormatter<'_>, indent: &str) {
+        formatter.output.push_str(self.name.value.lookup());
+        formatter.print_directives(&self.directives, indent, false);
+    }
+}
+
+impl ListItem for OperationTypeDefinition {
+    fn span(&self) -> Span {
+        self.span
+    }
+
+    fn render(&self, _source: &str) -> String {
+        self.to_string()
+    }
+
+    fn print(&self, formatter: &mut DocumentFormatter<'_>, _indent: &str) {
+        formatter.output.push_str(&self.to_string());
+    }
+}
+
+struct DocumentFormatter<'a> {
+    source: &'a str,
+    comments: Vec<Comment>,
+    next_comment: usize,
+    /// The end of the last node or comment printed, in the source.
+    last_end: usize,
+    output: String,
+}
+
+impl<'a> DocumentFormatter<'a> {
+    fn new(source: &'a str) -> Self {
+        Self {
+            source,
+            comments: find_comments(source),
+            next_comment: 0,
+            last_end: 0,
+            output: String::new(),
+        }
+    }
+
+    fn format_definitions(&mut self, definitions: &[ExecutableDefinition]) {
+        for (index, definition) in definitions.iter().enumerate() {
+            if index > 0 {
+                self.output.push('\n');
+            }
+            let span = definition.location().span();
+            self.print_leading_comments(span.start as usize, "");
+            match definition {
+                ExecutableDefinition::Operation(operation) => self.print_operation(operation),
+                ExecutableDefinition::Fragment(fragment) => self.print_fragment(fragment),
+            }
+            self.print_trailing_comment();
+            self.output.push('\n');
+        }
+        self.print_leading_comments(self.source.len(), "");
+    }
+
+    fn format_type_system_definitions(&mut self, definitions: &[TypeSystemDefinition]) {
+        for (index, definition) in definitions.iter().enumerate() {
+            if index > 0 {
+                self.output.push('\n');
+            }
+            let span = get_type_system_definition_span(definition);
+            self.print_leading_comments(span.start as usize, "");
+            self.print_type_system_definition(definition);
+            self.last_end = self.node_end(span);
+            self.print_trailing_comment();
+            self.output.push('\n');
+        }
+        self.print_leading_comments(self.source.len(), "");
+    }
+
+    fn print_type_system_definition(&mut self, definition: &TypeSystemDefinition) {
+        match definition {
+            TypeSystemDefinition::SchemaDefinition(schema) => self.print_type(
+                "schema".to_string(),
+                &schema.directives,
+                Some(&schema.operation_types),
+            ),
+            TypeSystemDefinition::SchemaExtension(extension) => self.print_type(
+                "extend schema".to_string(),
+                &extension.directives,
+                extension.operation_types.as_ref(),
+            ),
+            TypeSystemDefinition::ObjectTypeDefinition(object) => self.print_type(
+                format!(
+                    "type {}{}",
+                    object.name.value,
+                    render_interfaces(&object.interfaces)
+                ),
+                &object.directives,
+                object.fields.as_ref(),
+            ),
+            TypeSystemDefinition::ObjectTypeExtension(extension) => self.print_type(
+                format!(
+                    "extend type {}{}",
+                    extension.name.value,
+                    render_interfaces(&extension.interfaces)
+                ),
+                &extension.directives,
+                extension.fields.as_ref(),
+            ),
+            TypeSystemDefinition::InterfaceTypeDefinition(interface) => self.print_type(
+                format!(
+                    "interface {}{}",
+                    interface.name.value,
+                    render_interfaces(&interface.interfaces)
+                ),
+                &interface.directives,
+                interface.fields.as_ref(),
+            ),
+            TypeSystemDefinition::InterfaceTypeExtension(extension) => self.print_type(
+                format!(
+                    "extend interface {}{}",
+                    extension.name.value,
+                    render_interfaces(&extension.interfaces)
+                ),
+                &extension.directives,
+                extension.fields.as_ref(),
+            ),
+            TypeSystemDefinition::UnionTypeDefinition(union) => self.print_union(
+                format!("union {}", union.name.value),
+                &union.directives,
+                &union.members,
+            ),
+            TypeSystemDefinition::UnionTypeExtension(extension) => self.print_union(
+                format!("extend union {}", extension.name.value),
+                &extension.directives,
+                &extension.members,
+            ),
+            TypeSystemDefinition::ScalarTypeDefinition(scalar) => self
+                .print_type::<FieldDefinition>(
+                    format!("scalar {}", scalar.name.value),
+                    &scalar.directives,
+                    None,
+                ),
+            TypeSystemDefinition::ScalarTypeExtension(extension) => self
+                .print_type::<FieldDefinition>(
+                    format!("extend scalar {}", extension.name.value),
+                    &extension.directives,
+                    None,
+                ),
+            TypeSystemDefinition::EnumTypeDefinition(enum_) => self.print_type(
+                format!("enum {}", enum_.name.value),
+                &enum_.directives,
+                enum_.values.as_ref(),
+            ),
+            TypeSystemDefinition::EnumTypeExtension(extension) => self.print_type(
+                format!("extend enum {}", extension.name.value),
+                &extension.directives,
+                extension.values.as_ref(),
+            ),
+            TypeSystemDefinition::InputObjectTypeDefinition(input) => self.print_type(
+                format!("input {}", input.name.value),
+                &input.directives,
+                input.fields.as_ref(),
+            ),
+            TypeSystemDefinition::InputObjectTypeExtension(extension) => self.print_type(
+                format!("extend input {}", extension.name.value),
+                &extension.directives,
+                extension.fields.as_ref(),
+            ),
+            TypeSystemDefinition::DirectiveDefinition(directive) => {
+                let locations = directive
+                    .locations
+                    .iter()
+                    .map(|location| location.to_string())
+                    .collect::<Vec<_>>()
+                    .join(" | ");
+                let suffix = format!(
+                    "{} on {}",
+                    if directive.repeatable {
+                        " repeatable"
+                    } else {
+                        ""
+                    },
+                    locations
+                );
+                self.print_header::<_, ConstantDirective>(
+                    "",
+                    format!("directive @{}", directive.name.value),
+                    directive.arguments.as_ref(),
+                    &suffix,
+                    &[],
+                    false,
+                );
+            }
+        }
+    }
+
+    /// Prints a type, or the schema, with its fields, values or operation
+    /// types one per line.
+    fn print_type<T: ListItem>(
+        &mut self,
+        prefix: String,
+        directives: &[ConstantDirective],
+        items: Option<&List<T>>,
+    ) {
+        self.print_header::<Argument, _>("", prefix, None, "", directives, items.is_some());
+        if let Some(items) = items {
+            self.output.push(' ');
+            self.print_list(items, "");
+        }
+    }
+
+    /// Prints the members of a union on the line of the union, or one per
+    /// line when they do not fit or have comments.
+    fn print_union(
+        &mut self,
+        prefix: String,
+        directives: &[ConstantDirective],
+        members: &[Identifier],
+    ) {
+        self.print_header::<Argument, _>("", prefix, None, "", directives, false);
+        let (Some(first), Some(last)) = (members.first(), members.last()) else {
+            return;
+        };
+        let rendered_members = members
+            .iter()
+            .map(|member| member.value.lookup())
+            .collect::<Vec<_>>()
+            .join(" | ");
+        let line_width = self.output.len() - self.output.rfind('\n').map_or(0, |index| index + 1);
+        let has_comments = self.has_comment_in(Span::new(first.span.start, last.span.end));
+        if !has_comments && line_width + rendered_members.len() + 3 <= PRINT_WIDTH {
+            self.output.push_str(" = ");
+            self.output.push_str(&rendered_members);
+            return;
+        }
+        self.output.push_str(" =");
+        for member in members {
+            self.output.push('\n');
+            self.print_leading_comments(member.span.start as usize, INDENT);
+            self.output.push_str(INDENT);
+            self.output.push_str("| ");
+            self.output.push_str(member.value.lookup());
+            self.last_end = member.span.end as usize;
+            self.print_trailing_comment();
+        }
+    }
+
+    fn print_operation(&mut self, operation: &OperationDefinition) {
+        // The query shorthand only has a selection set.
+        if let Some((_, kind)) = &operation.operation {
+            let mut prefix = kind.to_string();
+            if let Some(name) = &operation.name {
+                prefix.push(' ');
+                prefix.push_str(name.value.lookup());
+            }
+            self.print_header(
+                "",
+                prefix,
+                operation.variable_definitions.as_ref(),
+                "",
+                &operation.directives,
+                true,
+            );
+            self.output.push(' ');
+        }
+        self.print_selections(&operation.selections, "");
+    }
+
+    fn print_fragment(&mut self, fragment: &FragmentDefinition) {
+        self.print_header(
+            "",
+            format!("fragment {}", fragment.name.value),
+            fragment.variable_definitions.as_ref(),
+            &format!(" {}", fragment.type_condition),
+            &fragment.directives,
+            true,
+        );
+        self.output.push(' ');
+        self.print_selections(&fragment.selections, "");
+    }
+
+    fn print_selections(&mut self, selections: &List<Selection>, indent: &str) {
+        self.output.push('{');
+        self.last_end = selections.start.span.end as usize;
+        self.print_trailing_comment();
+        self.output.push('\n');
+        let selection_indent = format!("{}{}", indent, INDENT);
+        for selection in &selections.items {
+            self.print_selection(selection, &selection_indent);
+        }
+        self.print_leading_comments(selections.end.span.start as usize, &selection_indent);
+        self.output.push_str(indent);
+        self.output.push('}');
+        self.last_end = selections.end.span.end as usize;
+    }
+
+    fn print_selection(&mut self, selection: &Selection, indent: &str) {
+        let start = selection.span().start as usize;
+        self.print_leading_comments(start, indent);
+        self.print_blank_line_before(start);
+        self.output.push_str(indent);
+        match selection {
+            Selection::ScalarField(field) => {
+                let prefix = match &field.alias {
+                    Some(alias) => format!("{}: {}", alias.alias.value, field.name.value),
+                    None => field.name.value.to_string(),
+                };
+                self.print_header(
+                    indent,
+                    prefix,
+                    field.arguments.as_ref(),
+                    "",
+                    &field.directives,
+                    false,
+                );
+            }
+            Selection::LinkedField(field) => {
+                let prefix = match &field.alias {
+                    Some(alias) => format!("{}: {}", alias.alias.value, field.name.value),
+                    None => field.name.value.to_string(),
+                };
+                self.print_header(
+                    indent,
+                    prefix,
+                    field.arguments.as_ref(),
+                    "",
+                    &field.directives,
+                    true,
+                );
+                self.output.push(' ');
+                self.print_selections(&field.selections, indent);
+            }
+            Selection::FragmentSpread(spread) => {
+                self.print_header(
+                    indent,
+                    format!("...{}", spread.name.value),
+                    spread.arguments.as_ref(),
+                    "",
+                    &spread.directives,
+                    false,
+                );
+            }
+            Selection::InlineFragment(fragment) => {
+                let prefix = match &fragment.type_condition {
+                    Some(type_condition) => format!("... {}", type_condition),
+                    None => "...".to_string(),
+                };
+                self.print_header::<Argument, _>(
+                    indent,
+                    prefix,
+                    None,
+                    "",
+                    &fragment.directives,
+                    true,
+                );
+                self.output.push(' ');
+                self.print_selections(&fragment.selections, indent);
+            }
+        }
+        self.last_end = selection.span().end as usize;
+        self.print_trailing_comment();
+        self.output.push('\n');
+    }
+
+    /// Prints the part of a definition or selection before its selections.
+    /// When it does not fit on the line, or has comments, the arguments or
+    /// variable definitions are printed one per line, or the arguments of
+    /// the directives if there are none.
+    fn print_header<T: ListItem, D: DirectiveNode>(
+        &mut self,
+        indent: &str,
+        prefix: String,
+        list: Option<&List<T>>,
+        suffix: &str,
+        directives: &[D],
+        has_selections: bool,
+    ) {
+        let list = list.filter(|list| !list.items.is_empty());
+        let rendered_list = list.map_or_else(String::new, |list| render_list(self.source, list));
+        let rendered_directives = render_directives(self.source, directives);
+        let width = indent.len()
+            + prefix.len()
+            + rendered_list.len()
+            + suffix.len()
+            + rendered_directives.len()
+            + if has_selections { 2 } else { 0 };
+        let directive_arguments = directives
+            .iter()
+            .filter_map(|directive| directive.arguments())
+            .filter(|arguments| !arguments.items.is_empty())
+            .collect::<Vec<_>>();
+        let break_list =
+            list.is_some_and(|list| width > PRINT_WIDTH || self.has_comment_in(list.span));
+        let break_directives = list.is_none()
+            && !directive_arguments.is_empty()
+            && (width > PRINT_WIDTH
+                || directive_arguments
+                    .iter()
+                    .any(|arguments| self.has_comment_in(arguments.span)));
+
+        self.output.push_str(&prefix);
+        match list {
+            Some(list) if break_list => self.print_list(list, indent),
+            _ => self.output.push_str(&rendered_list),
+        }
+        self.output.push_str(suffix);
+        self.print_directives(directives, indent, break_directives);
+    }
+
+    /// Prints the directives, with the arguments one per line if
+    /// `break_arguments` is set or they have comments.
+    fn print_directives<D: DirectiveNode>(
+        &mut self,
+        directives: &[D],
+        indent: &str,
+        break_arguments: bool,
+    ) {
+        for directive in directives {
+            self.output.push_str(" @");
+            self.output.push_str(directive.name().lookup());
+            if let Some(arguments) = directive.arguments() {
+                if arguments.items.is_empty() {
+                    continue;
+                }
+                if break_arguments || self.has_comment_in(arguments.span) {
+                    self.print_list(arguments, indent);
+                } else {
+                    self.output.push_str(&render_list(self.source, arguments));
+                }
+            }
+        }
+    }
+
+    /// Prints the items of a list one per line, with their comments, between
+    /// the delimiters of the list in the source.
+    fn print_list<T: ListItem>(&mut self, list: &List<T>, indent: &str) {
+        let (open, close) = self.delimiters(list);
+        self.output.push_str(open);
+        self.last_end = list.start.span.end as usize;
+        self.print_trailing_comment();
+        self.output.push('\n');
+        let item_indent = format!("{}{}", indent, INDENT);
+        for item in &list.items {
+            let span = item.span();
+            self.print_leading_comments(span.start as usize, &item_indent);
+            self.print_blank_line_before(span.start as usize);
+            self.output.push_str(&item_indent);
+            item.print(self, &item_indent);
+            self.last_end = self.node_end(span);
+            self.print_trailing_comment();
+            self.output.push('\n');
+        }
+        self.print_leading_comments(list.end.span.start as usize, &item_indent);
+        self.output.push_str(indent);
+        self.output.push_str(close);
+        self.last_end = list.end.span.end as usize;
+    }
+
+    fn delimiters<T>(&self, list: &List<T>) -> (&'a str, &'a str) {
+        let source = self.source;
+        (
+            &source[list.start.span.start as usize..list.start.span.end as usize],
+            &source[list.end.span.start as usize..list.end.span.end as usize],
+        )
+    }
+
+    /// Prints the comments and the descriptions before the position on their
+    /// own lines.
+    fn print_leading_comments(&mut self, position: usize, indent: &str) {
+        loop {
+            let comment_start = self
+                .comments
+                .get(self.next_comment)
+                .map(|comment| comment.start)
+                .filter(|start| *start < position);
+            let (start, end) = match (comment_start, self.find_description(position)) {
+                (Some(start), Some((description_start, _))) if start < description_start => {
+                    self.next_comment += 1;
+                    (start, self.comments[self.next_comment - 1].end)
+                }
+                (_, Some(description)) => description,
+                (Some(start), None) => {
+                    self.next_comment += 1;
+                    (start, self.comments[self.next_comment - 1].end)
+                }
+                (None, None) => break,
+            };
+            self.print_blank_line_before(start);
+            self.output.push_str(indent);
+            self.output.push_str(self.source[start..end].trim_end());
+            self.output.push('\n');
+            self.last_end = end;
+        }
+    }
+
+    /// Returns the byte range of the string before the position that only
+    /// has comments before it, which is the description or the `hackSource`
+    /// of a type system definition.
+    fn find_description(&self, position: usize) -> Option<(usize, usize)> {
+        let bytes = self.source.as_bytes();
+        let mut index = self.last_end;
+        while index < position {
+            match bytes[index] {
+                b'#' => {
+                    while index < position && bytes[index] != b'\n' && bytes[index] != b'\r' {
+                        index += 1;
+                    }
+                }
+                b'"' => return Some((index, string_end(self.source, index))),
+                b',' => index += 1,
+                byte if byte.is_ascii_whitespace() => index += 1,
+                _ => return None,
+            }
+        }
+        None
+    }
+
+    /// The end of the last token of a node, since the spans of type system
+    /// definitions end at the start of the next token.
+    fn node_end(&self, span: Span) -> usize {
+        let bytes = self.source.as_bytes();
+        let mut index = span.start as usize;
+        let mut end = index;
+        while index < span.end as usize {
+            match bytes[index] {
+                b'#' => {
+                    while index < bytes.len() && bytes[index] != b'\n' && bytes[index] != b'\r' {
+                        index += 1;
+                    }
+                }
+                b'"' => {
+                    index = string_end(self.source, index);
+                    end = index;
+                }
+                b',' => index += 1,
+                byte if byte.is_ascii_whitespace() => index += 1,
+                _ => {
+                    index += 1;
+                    end = index;
+                }
+            }
+        }
+        end
+    }
+
+    /// Prints the next comment if it is on the same line as the last node.
+    fn print_trailing_comment(&mut self) {
+        if let Some(comment) = self.comments.get(self.next_comment) {
+            if comment.start < self.last_end {
+                return;
+            }
+            let is_same_line = self.source[self.last_end..comment.start]
+                .chars()
+                .all(|c| c == ',' || (c.is_whitespace() && c != '\n' && c != '\r'));
+            if is_same_line {
+                let (start, end) = (comment.start, comment.end);
+                self.output.push(' ');
+                self.output.push_str(self.source[start..end].trim_end());
+                self.next_comment += 1;
+                self.last_end = end;
+            }
+        }
+    }
+
+    /// Keeps a blank line of the source before a selection or a comment,
+    /// except at the start of a block.
+    fn print_blank_line_before(&mut self, position: usize) {
+        if self.output.is_empty()
+            || self.output.ends_with("\n\n")
+            || self.output.ends_with("{\n")
+            || self.output.ends_with("(\n")
+            || self.output.ends_with("[\n")
+            || position < self.last_end
+        {
+            return;
+        }
+        let lines = self.source[self.last_end..position]
+            .split('\n')
+            .collect::<Vec<_>>();
+        let has_blank_line = lines.len() > 2
+            && lines[1..lines.len() - 1]
+                .iter()
+                .any(|line| line.chars().all(|c| c == ',' || c.is_whitespace()));
+        if has_blank_line {
+            self.output.push('\n');
+        }
+    }
+
+    fn has_comment_in(&self, span: Span) -> bool {
+        self.comments[self.next_comment..]
+            .iter()
+            .any(|comment| comment.start >= span.start as usize && comment.end <= span.end as usize)
+    }
+}
+
+/// Returns the comments of the source, skipping `#` characters in strings.
+fn find_comments(source: &str) -> Vec<Comment> {
+    let bytes = source.as_bytes();
+    let mut comments = vec![];
+    let mut index = 0;
+    while index < bytes.len() {
+        match bytes[index] {
+            b'#' => {
+                let start = index;
+                while index < bytes.len() && bytes[index] != b'\n' && bytes[index] != b'\r' {
+                    index += 1;
+                }
+                comments.push(Comment { start, end: index });
+            }
+            b'"' => index = string_end(source, index),
+            _ => index += 1,
+        }
+    }
+    comments
+}
+
+/// Returns the end of the string or block string that starts at the index.
+fn string_end(source: &str, start: usize) -> usize {
+    let bytes = source.as_bytes();
+    let mut index = start;
+    if source[index..].starts_with("\"\"\"") {
+        index += 3;
+        while index < bytes.len() {
+            if source[index..].starts_with("\\\"\"\"") {
+                index += 4;
+            } else if source[index..].starts_with("\"\"\"") {
+                return index + 3;
+            } else {
+                index += 1;
+            }
+        }
+        return index;
+    }
+    index += 1;
+    while index < bytes.len() {
+        match bytes[index] {
+            b'\\' => index += 2,
+            b'"' => return index + 1,
+            b'\n' | b'\r' => break,
+            _ => index += 1,
+        }
+    }
+    index.min(bytes.len())
+}
+
+fn render_interfaces(interfaces: &[Identifier]) -> String {
+    if interfaces.is_empty() {
+        return String::new();
+    }
+    format!(
+        " implements {}",
+        interfaces
+            .iter()
+            .map(|interface| interface.value.lookup())
+            .collect::<Vec<_>>()
+            .join(" & ")
+    )
+}
+
+/// The span of a type system definition, from its keyword after the
+/// description, or from its name for extensions.
+fn get_type_system_definition_span(definition: &TypeSystemDefinition) -> Span {
+    match definition {
+        TypeSystemDefinition::SchemaDefinition(definition) => definition.span,
+        TypeSystemDefinition::SchemaExtension(definition) => definition.span,
+        TypeSystemDefinition::EnumTypeDefinition(definition) => definition.span,
+        TypeSystemDefinition::EnumTypeExtension(definition) => definition.span,
+        TypeSystemDefinition::InterfaceTypeDefinition(definition) => definition.span,
+        TypeSystemDefinition::InterfaceTypeExtension(definition) => definition.span,
+        TypeSystemDefinition::ObjectTypeDefinition(definition) => definition.span,
+        TypeSystemDefinition::ObjectTypeExtension(definition) => definition.span,
+        TypeSystemDefinition::UnionTypeDefinition(definition) => definition.span,
+        TypeSystemDefinition::UnionTypeExtension(definition) => definition.span,
+        TypeSystemDefinition::InputObjectTypeDefinition(definition) => definition.span,
+        TypeSystemDefinition::InputObjectTypeExtension(definition) => definition.span,
+        TypeSystemDefinition::ScalarTypeDefinition(definition) => definition.span,
+        TypeSystemDefinition::ScalarTypeExtension(definition) => definition.span,
+        TypeSystemDefinition::DirectiveDefinition(definition) => definition.span,
+    }
+}
+
+fn render_list<T: ListItem>(source: &str, list: &List<T>) -> String {
+    format!(
+        "({})",
+        list.items
+            .iter()
+            .map(|item| item.render(source))
+            .collect::<Vec<_>>()
+            .join(", ")
+    )
+}
+
+fn render_directives<D: DirectiveNode>(source: &str, directives: &[D]) -> String {
+    let mut result = String::new();
+    for directive in directives {
+        result.push_str(" @");
+        result.push_str(directive.name().lookup());
+        if let Some(arguments) = directive.arguments() {
+            if !arguments.items.is_empty() {
+                result.push_str(&render_list(source, arguments));
+            }
+        }
+    }
+    result
+}
+
+fn render_value(source: &str, value: &Value) -> String {
+    match value {
+        Value::Constant(value) => render_constant_value(source, value),
+        Value::Variable(variable) => variable.to_string(),
+        Value::List(list) => format!(
+            "[{}]",
+            list.items
+                .iter()
+                .map(|item| render_value(source, item))
+                .collect::<Vec<_>>()
+                .join(", ")
+        ),
+        Value::Object(object) => format!(
+            "{{{}}}",
+            object
+                .items
+                .iter()
+                .map(|field| field.render(source))
+                .collect::<Vec<_>>()
+                .join(", ")
+        ),
+    }
+}
+
+/// Lists and objects are printed on one line, other values as they are
+/// written in the source.
+fn render_constant_value(source: &str, value: &ConstantValue) -> String {
+    match value {
+        ConstantValue::List(list) => format!(
+            "[{}]",
+            list.items
+                .iter()
+                .map(|item| render_constant_value(source, item))
+                .collect::<Vec<_>>()
+                .join(", ")
+        ),
+        ConstantValue::Object(object) => format!(
+            "{{{}}}",
+            object
+                .items
+                .iter()
+                .map(|field| field.render(source))
+                .collect::<Vec<_>>()
+                .join(", ")
+        ),
+        _ => {
+            let span = value.span();
+            source[span.start as usize..span.end as usize].to_string()
+        }
+    }
+}
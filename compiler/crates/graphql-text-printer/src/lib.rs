@@ -9,10 +9,13 @@
 #![deny(rust_2018_idioms)]
 #![deny(clippy::all)]
 
+mod format_document;
 mod print_ast_to_text;
 mod print_full_operation;
 mod print_to_text;
 
+pub use format_document::format_executable_document;
+pub use format_document::format_schema_document;
 pub use print_ast_to_text::print_executable_definition_ast;
 pub use print_ast_to_text::print_fragment_ast;
 pub use print_ast_to_text::print_operation_ast;
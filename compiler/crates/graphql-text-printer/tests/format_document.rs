@@ -0,0 +1,38 @@
+/*
+ * Copyright (c) Meta Platforms, Inc. and affiliates.
+ *
+ * This source code is licensed under the MIT license found in the
+ * LICENSE file in the root directory of this source tree.
+ */
+
+use common::SourceLocationKey;
+use common::TextSource;
+use fixture_tests::Fixture;
+use graphql_cli::DiagnosticPrinter;
+use graphql_syntax::FragmentArgumentSyntaxKind;
+use graphql_syntax::ParserFeatures;
+use graphql_text_printer::format_executable_document;
+
+pub async fn transform_fixture(fixture: &Fixture<'_>) -> Result<String, String> {
+    let source_location = SourceLocationKey::standalone(fixture.file_name);
+    let features = ParserFeatures {
+        fragment_argument_capability:
+            FragmentArgumentSyntaxKind::SpreadArgumentsAndFragmentVariableDefinitions,
+    };
+    let formatted = format_executable_document(fixture.content, source_location, features)
+        .map_err(|diagnostics| {
+            let printer = DiagnosticPrinter::new(|_| {
+                Some(TextSource::from_whole_document(fixture.content.to_string()))
+            });
+            printer.diagnostics_to_string(&diagnostics)
+        })?;
+
+    let reformatted = format_executable_document(&formatted, source_location, features)
+        .expect("Expected the formatted document to be valid");
+    assert_eq!(
+        formatted, reformatted,
+        "Expected the formatting to be stable"
+    );
+
+    Ok(formatted)
+}
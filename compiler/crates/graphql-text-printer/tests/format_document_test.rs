@@ -0,0 +1,62 @@
+/*
+ * Copyright (c) Meta Platforms, Inc. and affiliates.
+ *
+ * This source code is licensed under the MIT license found in the
+ * LICENSE file in the root directory of this source tree.
+ *
+ * @generated SignedSource<<6b93ed8fcec00c4bcd3b267b62706db7>>
+ */
+
+mod format_document;
+
+use format_document::transform_fixture;
+use fixture_tests::test_fixture;
+
+#[tokio::test]
+async fn comments() {
+    let input = include_str!("format_document/fixtures/comments.graphql");
+    let expected = include_str!("format_document/fixtures/comments.expected");
+    test_fixture(transform_fixture, file!(), "comments.graphql", "format_document/fixtures/comments.expected", input, expected).await;
+}
+
+#[tokio::test]
+async fn comments_in_values() {
+    let input = include_str!("format_document/fixtures/comments-in-values.graphql");
+    let expected = include_str!("format_document/fixtures/comments-in-values.expected");
+    test_fixture(transform_fixture, file!(), "comments-in-values.graphql", "format_document/fixtures/comments-in-values.expected", input, expected).await;
+}
+
+#[tokio::test]
+async fn fragment_arguments() {
+    let input = include_str!("format_document/fixtures/fragment-arguments.graphql");
+    let expected = include_str!("format_document/fixtures/fragment-arguments.expected");
+    test_fixture(transform_fixture, file!(), "fragment-arguments.graphql", "format_document/fixtures/fragment-arguments.expected", input, expected).await;
+}
+
+#[tokio::test]
+async fn long_arguments() {
+    let input = include_str!("format_document/fixtures/long-arguments.graphql");
+    let expected = include_str!("format_document/fixtures/long-arguments.expected");
+    test_fixture(transform_fixture, file!(), "long-arguments.graphql", "format_document/fixtures/long-arguments.expected", input, expected).await;
+}
+
+#[tokio::test]
+async fn selections() {
+    let input = include_str!("format_document/fixtures/selections.graphql");
+    let expected = include_str!("format_document/fixtures/selections.expected");
+    test_fixture(transform_fixture, file!(), "selections.graphql", "format_document/fixtures/selections.expected", input, expected).await;
+}
+
+#[tokio::test]
+async fn strings() {
+    let input = include_str!("format_document/fixtures/strings.graphql");
+    let expected = include_str!("format_document/fixtures/strings.expected");
+    test_fixture(transform_fixture, file!(), "strings.graphql", "format_document/fixtures/strings.expected", input, expected).await;
+}
+
+#[tokio::test]
+async fn syntax_error_invalid() {
+    let input = include_str!("format_document/fixtures/syntax-error.invalid.graphql");
+    let expected = include_str!("format_document/fixtures/syntax-error.invalid.expected");
+    test_fixture(transform_fixture, file!(), "syntax-error.invalid.graphql", "format_document/fixtures/syntax-error.invalid.expected", input, expected).await;
+}
@@ -0,0 +1,34 @@
+/*
+ * Copyright (c) Meta Platforms, Inc. and affiliates.
+ *
+ * This source code is licensed under the MIT license found in the
+ * LICENSE file in the root directory of this source tree.
+ *
+ * @generated SignedSource<<def257dacfe2bd561edfcd7aee9a9d05>>
+ */
+
+mod format_schema_document;
+
+use format_schema_document::transform_fixture;
+use fixture_tests::test_fixture;
+
+#[tokio::test]
+async fn extensions() {
+    let input = include_str!("format_schema_document/fixtures/extensions.graphql");
+    let expected = include_str!("format_schema_document/fixtures/extensions.expected");
+    test_fixture(transform_fixture, file!(), "extensions.graphql", "format_schema_document/fixtures/extensions.expected", input, expected).await;
+}
+
+#[tokio::test]
+async fn syntax_error_invalid() {
+    let input = include_str!("format_schema_document/fixtures/syntax-error.invalid.graphql");
+    let expected = include_str!("format_schema_document/fixtures/syntax-error.invalid.expected");
+    test_fixture(transform_fixture, file!(), "syntax-error.invalid.graphql", "format_schema_document/fixtures/syntax-error.invalid.expected", input, expected).await;
+}
+
+#[tokio::test]
+async fn types() {
+    let input = include_str!("format_schema_document/fixtures/types.graphql");
+    let expected = include_str!("format_schema_document/fixtures/types.expected");
+    test_fixture(transform_fixture, file!(), "types.graphql", "format_schema_document/fixtures/types.expected", input, expected).await;
+}
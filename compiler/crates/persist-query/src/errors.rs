@@ -5,13 +5,15 @@
  * LICENSE file in the root directory of this source tree.
  */
 
+use std::sync::Arc;
+
 use thiserror::Error;
 
 #[derive(Debug, Error)]
 pub enum PersistError {
     #[error("Network create error: {error}")]
     NetworkCreateError {
-        error: Box<dyn std::error::Error + Send>,
+        error: Box<dyn std::error::Error + Send + Sync>,
     },
 
     #[error("Network error: {source}")]
@@ -40,4 +42,33 @@ pub enum PersistError {
         source: serde_json::Error,
         raw_response: String,
     },
+
+    #[error("Response has no id at `{path}`. Raw response: {raw_response}")]
+    MissingResponseId { path: String, raw_response: String },
+
+    #[error("Server error {status}. Raw response: {raw_response}")]
+    ServerErrorResponse { status: u16, raw_response: String },
+
+    #[error("Persisting timed out after {timeout_ms}ms")]
+    Timeout { timeout_ms: u64 },
+
+    /// The error of the request of a batch, reported for each of its
+    /// operations.
+    #[error("Failed to persist operation `{operation_name}` in a batch: {source}")]
+    BatchError {
+        operation_name: String,
+        source: Arc<PersistError>,
+    },
+}
+
+impl PersistError {
+    /// Whether the error may be transient, and the request worth retrying.
+    pub fn is_retryable(&self) -> bool {
+        matches!(
+            self,
+            PersistError::NetworkError { .. }
+                | PersistError::ServerErrorResponse { .. }
+                | PersistError::Timeout { .. }
+        )
+    }
 }
@@ -11,21 +11,31 @@
 
 mod errors;
 
+use std::collections::HashMap;
 use std::fmt;
 
 pub use errors::PersistError;
+use hyper::body::Bytes;
 use hyper::Body;
 use hyper::Client;
 use hyper::Method;
 use hyper::Request;
 use hyper_tls::HttpsConnector;
+use serde::de::DeserializeOwned;
 use serde::Deserialize;
+use serde_json::Map;
+use serde_json::Value;
 use url::form_urlencoded;
 
+#[derive(Debug, Deserialize)]
+struct ErrorResponse {
+    error: ResponseError,
+}
+
 #[derive(Debug, Deserialize)]
 #[serde(untagged)]
-enum Response {
-    Success { id: String },
+enum BatchResponse {
+    Success { ids: HashMap<String, String> },
     Error { error: ResponseError },
 }
 
@@ -40,25 +50,153 @@ impl fmt::Display for ResponseError {
     }
 }
 
+/// Encoding of the request body of `persist_with_format`.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum RequestEncoding {
+    /// `application/x-www-form-urlencoded`
+    Form,
+    /// `application/json`
+    Json,
+}
+
+/// The shape of the requests and responses of `persist_with_format`.
+#[derive(Clone, Copy, Debug)]
+pub struct PersistRequestFormat<'a> {
+    pub encoding: RequestEncoding,
+    /// Field of the request body that contains the document.
+    pub document_field: &'a str,
+    /// Dot-separated path of the id in the JSON response, e.g. `data.id`.
+    pub response_id_path: &'a str,
+}
+
+impl Default for PersistRequestFormat<'_> {
+    fn default() -> Self {
+        Self {
+            encoding: RequestEncoding::Form,
+            document_field: "text",
+            response_id_path: "id",
+        }
+    }
+}
+
+/// Persists a document in the `text` field of a form-urlencoded request. The
+/// response is expected to be `{"id": "..."}`, or
+/// `{"error": {"message": "..."}}`.
 pub async fn persist(
     document: &str,
     uri: &str,
     params: impl IntoIterator<Item = (&String, &String)>,
     extra_headers: impl IntoIterator<Item = (&String, &String)>,
 ) -> Result<String, PersistError> {
+    persist_with_format(
+        document,
+        uri,
+        params,
+        extra_headers,
+        PersistRequestFormat::default(),
+    )
+    .await
+}
+
+pub async fn persist_with_format(
+    document: &str,
+    uri: &str,
+    params: impl IntoIterator<Item = (&String, &String)>,
+    extra_headers: impl IntoIterator<Item = (&String, &String)>,
+    format: PersistRequestFormat<'_>,
+) -> Result<String, PersistError> {
+    let (content_type, request_body) = match format.encoding {
+        RequestEncoding::Form => {
+            let mut request_body = form_urlencoded::Serializer::new(String::new());
+            for param in params {
+                request_body.append_pair(param.0, param.1);
+            }
+            request_body.append_pair(format.document_field, document);
+            ("application/x-www-form-urlencoded", request_body.finish())
+        }
+        RequestEncoding::Json => {
+            let mut request_body = Map::new();
+            for (key, value) in params {
+                request_body.insert(key.clone(), Value::String(value.clone()));
+            }
+            request_body.insert(
+                format.document_field.to_string(),
+                Value::String(document.to_string()),
+            );
+            ("application/json", Value::Object(request_body).to_string())
+        }
+    };
+
+    let bytes = post(uri, content_type, request_body, extra_headers).await?;
+
+    let response: Value = parse_response(&bytes)?;
+    let id = format
+        .response_id_path
+        .split('.')
+        .try_fold(&response, |value, key| value.get(key));
+    match id {
+        Some(Value::String(id)) => Ok(id.clone()),
+        Some(Value::Number(id)) => Ok(id.to_string()),
+        _ => match ErrorResponse::deserialize(&response) {
+            Ok(ErrorResponse { error }) => Err(PersistError::ErrorResponse {
+                message: error.message,
+            }),
+            Err(_) => Err(PersistError::MissingResponseId {
+                path: format.response_id_path.to_string(),
+                raw_response: String::from_utf8_lossy(&bytes).to_string(),
+            }),
+        },
+    }
+}
+
+/// Persists many documents with a single request. The documents are sent as
+/// a JSON object, mapping a key of each document to its text, in the
+/// `documents` field of a JSON body, next to the `params`. The response is
+/// expected to map the same keys to the ids in its `ids` field:
+/// `{"ids": {"key": "id"}}`, or to be `{"error": {"message": "..."}}`.
+pub async fn persist_batch<'a>(
+    documents: impl IntoIterator<Item = (&'a String, &'a String)>,
+    uri: &str,
+    params: impl IntoIterator<Item = (&String, &String)>,
+    extra_headers: impl IntoIterator<Item = (&String, &String)>,
+) -> Result<HashMap<String, String>, PersistError> {
     let request_body = {
-        let mut request_body = form_urlencoded::Serializer::new(String::new());
-        for param in params {
-            request_body.append_pair(param.0, param.1);
+        let mut request_body = Map::new();
+        for (key, value) in params {
+            request_body.insert(key.clone(), Value::String(value.clone()));
         }
-        request_body.append_pair("text", document);
-        request_body.finish()
+        request_body.insert(
+            "documents".to_string(),
+            Value::Object(
+                documents
+                    .into_iter()
+                    .map(|(key, text)| (key.clone(), Value::String(text.clone())))
+                    .collect(),
+            ),
+        );
+        Value::Object(request_body).to_string()
     };
 
+    let bytes = post(uri, "application/json", request_body, extra_headers).await?;
+
+    match parse_response(&bytes)? {
+        BatchResponse::Success { ids } => Ok(ids),
+        BatchResponse::Error { error } => Err(PersistError::ErrorResponse {
+            message: error.message,
+        }),
+    }
+}
+
+async fn post(
+    uri: &str,
+    content_type: &str,
+    request_body: String,
+    extra_headers: impl IntoIterator<Item = (&String, &String)>,
+) -> Result<Bytes, PersistError> {
     let mut builder = Request::builder()
         .method(Method::POST)
         .uri(uri)
-        .header("content-type", "application/x-www-form-urlencoded");
+        .header("content-type", content_type);
     for (k, v) in extra_headers {
         builder = builder.header(k, v);
     }
@@ -71,17 +209,20 @@ pub async fn persist(
     let https = HttpsConnector::new();
     let client = Client::builder().build(https);
     let res = client.request(req).await?;
+    let status = res.status();
     let bytes = hyper::body::to_bytes(res.into_body()).await?;
-    let result: Response =
-        serde_json::from_slice(&bytes).map_err(|err| PersistError::DetailedResponseParseError {
-            source: err,
+    if status.is_server_error() {
+        return Err(PersistError::ServerErrorResponse {
+            status: status.as_u16(),
             raw_response: String::from_utf8_lossy(&bytes).to_string(),
-        })?;
-
-    match result {
-        Response::Success { id } => Ok(id),
-        Response::Error { error } => Err(PersistError::ErrorResponse {
-            message: error.message,
-        }),
+        });
     }
+    Ok(bytes)
+}
+
+fn parse_response<T: DeserializeOwned>(bytes: &Bytes) -> Result<T, PersistError> {
+    serde_json::from_slice(bytes).map_err(|err| PersistError::DetailedResponseParseError {
+        source: err,
+        raw_response: String::from_utf8_lossy(bytes).to_string(),
+    })
 }
@@ -20,4 +20,22 @@ pub enum Error {
 
     #[error("Unable to run relay compiler. Error details: \n{details}")]
     CompilerError { details: String },
+
+    #[error("Unable to write saved state. Error details: \n{details}")]
+    SavedStateError { details: String },
+
+    #[error("Unable to diff the schemas. Error details: \n{details}")]
+    SchemaDiffError { details: String },
+
+    #[error("The schema has {count} breaking change(s).")]
+    BreakingSchemaChanges { count: usize },
+
+    #[error("Unable to report the unused schema items. Error details: \n{details}")]
+    UnusedSchemaError { details: String },
+
+    #[error("Unable to format the sources. Error details: \n{details}")]
+    FormatError { details: String },
+
+    #[error("{count} file(s) are not formatted.")]
+    UnformattedSources { count: usize },
 }
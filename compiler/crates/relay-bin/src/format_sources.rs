@@ -0,0 +1,74 @@
+/*
+ * Copyright (c) Meta Platforms, Inc. and affiliates.
+ *
+ * This source code is licensed under the MIT license found in the
+ * LICENSE file in the root directory of this source tree.
+ */
+
+use std::collections::BTreeSet;
+use std::fs;
+use std::path::PathBuf;
+use std::sync::Arc;
+
+use common::ConsoleLogger;
+use common::PerfLogger;
+use relay_compiler::apply_formatted_graphql_tags;
+use relay_compiler::config::Config;
+use relay_compiler::format_graphql_tags;
+use relay_compiler::get_parser_features;
+use relay_compiler::FileSource;
+
+use crate::errors::Error;
+
+/// Formats the `graphql` tags of the source files of the enabled projects,
+/// and returns the files that were not formatted. With `check`, the files are
+/// not written.
+pub async fn format_sources(config: Config, check: bool) -> Result<Vec<PathBuf>, Error> {
+    let config = Arc::new(config);
+    let perf_logger = Arc::new(ConsoleLogger);
+    let log_event = perf_logger.create_event("format_sources");
+    let to_error = |error: relay_compiler::errors::Error| Error::FormatError {
+        details: format!("{}", error),
+    };
+
+    let file_source = FileSource::connect(&config, &log_event)
+        .await
+        .map_err(to_error)?;
+    let compiler_state = file_source
+        .query(&log_event, perf_logger.as_ref())
+        .await
+        .map_err(to_error)?;
+
+    let mut unformatted_files = BTreeSet::new();
+    for (project_name, sources) in &compiler_state.graphql_sources {
+        let project_config = &config.projects[project_name];
+        if !project_config.enabled {
+            continue;
+        }
+        let parser_features = get_parser_features(project_config);
+        for path in sources.pending.keys().chain(sources.processed.keys()) {
+            if unformatted_files.contains(path) {
+                continue;
+            }
+            let absolute_path = config.root_dir.join(path);
+            let text = fs::read_to_string(&absolute_path).map_err(|error| Error::FormatError {
+                details: format!("Unable to read {:?}: {}", absolute_path, error),
+            })?;
+            let formatted_tags = format_graphql_tags(&text, parser_features);
+            if formatted_tags.is_empty() {
+                continue;
+            }
+            if !check {
+                fs::write(
+                    &absolute_path,
+                    apply_formatted_graphql_tags(&text, &formatted_tags),
+                )
+                .map_err(|error| Error::FormatError {
+                    details: format!("Unable to write {:?}: {}", absolute_path, error),
+                })?;
+            }
+            unformatted_files.insert(path.clone());
+        }
+    }
+    Ok(unformatted_files.into_iter().collect())
+}
@@ -7,6 +7,7 @@
 
 use std::env;
 use std::env::current_dir;
+use std::fs;
 use std::path::PathBuf;
 use std::process::Command;
 use std::sync::Arc;
@@ -20,9 +21,13 @@ use log::info;
 use relay_compiler::build_project::artifact_writer::ArtifactValidationWriter;
 use relay_compiler::build_project::generate_extra_artifacts::default_generate_extra_artifacts_fn;
 use relay_compiler::compiler::Compiler;
+use relay_compiler::compiler_state::CompilerState;
 use relay_compiler::config::Config;
 use relay_compiler::config::ConfigFile;
 use relay_compiler::errors::Error as CompilerError;
+use relay_compiler::status_reporter::DiagnosticRecordsStatusReporter;
+use relay_compiler::status_reporter::SarifStatusReporter;
+use relay_compiler::ApqPersister;
 use relay_compiler::FileSourceKind;
 use relay_compiler::LocalPersister;
 use relay_compiler::OperationPersister;
@@ -34,7 +39,10 @@ use relay_lsp::DummyExtraDataProvider;
 use relay_lsp::FieldDefinitionSourceInfo;
 use relay_lsp::FieldSchemaInfo;
 use relay_lsp::LSPExtraDataProvider;
+use relay_saved_state_loader::LocalSavedStateLoader;
 use schema::SDLSchema;
+use schema_diff::criticality::get_unsafe_type_changes;
+use schema_diff::criticality::ChangeCriticality;
 use schema_documentation::SchemaDocumentationLoader;
 use simplelog::ColorChoice;
 use simplelog::ConfigBuilder as SimpleLogConfigBuilder;
@@ -43,6 +51,10 @@ use simplelog::TermLogger;
 use simplelog::TerminalMode;
 
 mod errors;
+mod format_sources;
+mod project_sources;
+mod schema_change_report;
+mod unused_schema_report;
 
 use errors::Error;
 
@@ -68,7 +80,8 @@ struct Opt {
     about = "Compiles Relay files and writes generated files."
 )]
 struct CompileCommand {
-    /// Compile and watch for changes
+    /// Compile and watch for changes. Uses the file system notifications of
+    /// the OS if `watchman` is not available.
     #[clap(long, short)]
     watch: bool,
 
@@ -97,6 +110,44 @@ struct CompileCommand {
     /// writing to disk
     #[clap(long)]
     validate: bool,
+
+    /// Format of the reported diagnostics. `json` and `sarif` print
+    /// structured reports to stdout, and logs are written to stderr.
+    #[clap(long, arg_enum, default_value = "text")]
+    diagnostics_format: DiagnosticsFormatKind,
+
+    /// Directory of a local saved state cache. The compiler starts from the
+    /// saved state of `--savedStateKey` if it is cached, or from the saved
+    /// state of the revision found by watchman if `savedStateConfig` is set.
+    /// Saved states require watchman, or `--changedSince`.
+    #[clap(long)]
+    saved_state_dir: Option<PathBuf>,
+
+    /// Key of the saved state to read and write, e.g. a source control
+    /// revision. When writing, defaults to the watchman clock of the build.
+    #[clap(long)]
+    saved_state_key: Option<String>,
+
+    /// Write the saved state of the build to `--savedStateDir`.
+    #[clap(long, requires = "savedStateDir")]
+    write_saved_state: bool,
+
+    /// Find the files changed since this git revision with `git diff` and
+    /// `git status`, instead of watchman, and compile them incrementally from
+    /// the saved state of `--savedStateKey`, which should be the state of the
+    /// revision. Without saved state, all files are compiled.
+    #[clap(long, conflicts_with = "watch")]
+    changed_since: Option<String>,
+
+    /// Format the `graphql` tags of the source files instead of compiling
+    /// them.
+    #[clap(long, conflicts_with_all = &["watch", "validate", "checkFormat"])]
+    format: bool,
+
+    /// Check that the `graphql` tags of the source files are formatted, and
+    /// exit with a non-zero code if not, instead of compiling them.
+    #[clap(long, conflicts_with_all = &["watch", "validate"])]
+    check_format: bool,
 }
 
 #[derive(Parser)]
@@ -124,11 +175,74 @@ struct LspCommand {
 #[clap(about = "Print the Json Schema definition for the Relay compiler config.")]
 struct ConfigJsonSchemaCommand {}
 
+#[derive(Parser)]
+#[clap(
+    about = "Classify the changes between two versions of a schema as breaking, dangerous or safe, and list the definitions they affect.",
+    rename_all = "camel_case"
+)]
+struct SchemaDiffCommand {
+    /// Path to the old version of the schema, a file or a directory of
+    /// `.graphql` files.
+    old: PathBuf,
+
+    /// Path to the new version of the schema, a file or a directory of
+    /// `.graphql` files.
+    new: PathBuf,
+
+    /// Find the affected definitions in the projects of this config file. If
+    /// not provided, searches for a config in package.json under the `relay`
+    /// key or `relay.config.json` files among other up from the current
+    /// working directory.
+    #[clap(long)]
+    config: Option<PathBuf>,
+
+    /// Find the affected definitions only in this project. You can pass this
+    /// argument multiple times.
+    #[clap(name = "project", long, short)]
+    projects: Vec<String>,
+
+    /// Only classify the changes, without looking for affected definitions.
+    #[clap(long)]
+    skip_definitions: bool,
+
+    /// Exit with a non-zero code if there are breaking changes.
+    #[clap(long)]
+    fail_on_breaking: bool,
+
+    /// Verbosity level
+    #[clap(long, arg_enum, default_value = "quiet-with-errors")]
+    output: OutputKind,
+}
+
+#[derive(Parser)]
+#[clap(
+    about = "Report the types, fields, arguments and enum values of the schemas that are not used by any document, as JSON.",
+    rename_all = "camel_case"
+)]
+struct UnusedSchemaCommand {
+    /// Compile the projects of this config file. If not provided, searches
+    /// for a config in package.json under the `relay` key or
+    /// `relay.config.json` files among other up from the current working
+    /// directory.
+    config: Option<PathBuf>,
+
+    /// Only report the usages of this project. You can pass this argument
+    /// multiple times.
+    #[clap(name = "project", long, short)]
+    projects: Vec<String>,
+
+    /// Verbosity level
+    #[clap(long, arg_enum, default_value = "quiet-with-errors")]
+    output: OutputKind,
+}
+
 #[derive(clap::Subcommand)]
 enum Commands {
     Compiler(CompileCommand),
     Lsp(LspCommand),
     ConfigJsonSchema(ConfigJsonSchemaCommand),
+    SchemaDiff(SchemaDiffCommand),
+    UnusedSchema(UnusedSchemaCommand),
 }
 
 #[derive(ArgEnum, Clone, Copy)]
@@ -139,6 +253,13 @@ enum OutputKind {
     Verbose,
 }
 
+#[derive(ArgEnum, Clone, Copy)]
+enum DiagnosticsFormatKind {
+    Text,
+    Json,
+    Sarif,
+}
+
 #[derive(Parser)]
 #[clap(rename_all = "camel_case")]
 pub struct CliConfig {
@@ -189,6 +310,8 @@ async fn main() {
             println!("{}", ConfigFile::json_schema());
             Ok(())
         }
+        Commands::SchemaDiff(command) => handle_schema_diff_command(command).await,
+        Commands::UnusedSchema(command) => handle_unused_schema_command(command).await,
     };
 
     if let Err(err) = result {
@@ -257,7 +380,13 @@ fn set_project_flag(config: &mut Config, projects: Vec<String>) -> Result<(), Er
 }
 
 async fn handle_compiler_command(command: CompileCommand) -> Result<(), Error> {
-    configure_logger(command.output, TerminalMode::Mixed);
+    configure_logger(
+        command.output,
+        match command.diagnostics_format {
+            DiagnosticsFormatKind::Text => TerminalMode::Mixed,
+            DiagnosticsFormatKind::Json | DiagnosticsFormatKind::Sarif => TerminalMode::Stderr,
+        },
+    );
 
     if command.cli_config.is_defined() {
         return Err(Error::ConfigError(CompilerError::ConfigError {
@@ -276,32 +405,77 @@ async fn handle_compiler_command(command: CompileCommand) -> Result<(), Error> {
         config.artifact_writer = Box::<ArtifactValidationWriter>::default();
     }
 
+    match command.diagnostics_format {
+        DiagnosticsFormatKind::Text => {}
+        DiagnosticsFormatKind::Json => {
+            config.status_reporter = Box::new(DiagnosticRecordsStatusReporter::new(
+                config.root_dir.clone(),
+            ));
+        }
+        DiagnosticsFormatKind::Sarif => {
+            config.status_reporter = Box::new(SarifStatusReporter::new(config.root_dir.clone()));
+        }
+    }
+
     config.create_operation_persister = Some(Box::new(|project_config| {
         project_config.persist.as_ref().map(
             |persist_config| -> Box<dyn OperationPersister + Send + Sync> {
                 match persist_config {
                     PersistConfig::Remote(remote_config) => {
-                        Box::new(RemotePersister::new(remote_config.clone()))
+                        Box::new(RemotePersister::new((**remote_config).clone()))
                     }
                     PersistConfig::Local(local_config) => {
                         Box::new(LocalPersister::new(local_config.clone()))
                     }
+                    PersistConfig::Apq(_) => Box::new(ApqPersister),
                 }
             },
         )
     }));
 
-    config.file_source_config = if should_use_watchman() {
+    config.file_source_config = if let Some(base_revision) = command.changed_since {
+        FileSourceKind::Git(base_revision)
+    } else if should_use_watchman() {
         FileSourceKind::Watchman
     } else {
         FileSourceKind::WalkDir
     };
     config.repersist_operations = command.repersist;
 
-    if command.watch && !matches!(&config.file_source_config, FileSourceKind::Watchman) {
-        panic!(
-            "Cannot run relay in watch mode if `watchman` is not available (or explicitly disabled)."
-        );
+    if command.format || command.check_format {
+        let unformatted_files =
+            format_sources::format_sources(config, command.check_format).await?;
+        if !command.check_format {
+            info!("Formatted {} file(s).", unformatted_files.len());
+            return Ok(());
+        }
+        for file in &unformatted_files {
+            error!("{:?} is not formatted.", file);
+        }
+        if !unformatted_files.is_empty() {
+            return Err(Error::UnformattedSources {
+                count: unformatted_files.len(),
+            });
+        }
+        info!("All files are formatted.");
+        return Ok(());
+    }
+
+    if let Some(saved_state_dir) = &command.saved_state_dir {
+        let saved_state_loader = LocalSavedStateLoader::new(saved_state_dir.clone());
+        if let (Some(saved_state_key), FileSourceKind::Watchman | FileSourceKind::Git(_)) =
+            (&command.saved_state_key, &config.file_source_config)
+        {
+            config.load_saved_state_file =
+                saved_state_loader.path_for_key(saved_state_key, &config.saved_state_version);
+            if config.load_saved_state_file.is_none() {
+                info!(
+                    "No saved state for `{}`, running a full build.",
+                    saved_state_key
+                );
+            }
+        }
+        config.saved_state_loader = Some(Box::new(saved_state_loader));
     }
 
     config.generate_extra_artifacts = Some(Box::new(default_generate_extra_artifacts_fn));
@@ -313,18 +487,58 @@ async fn handle_compiler_command(command: CompileCommand) -> Result<(), Error> {
             details: format!("{:?}", err),
         })?;
     } else {
-        compiler
+        let compiler_state = compiler
             .compile()
             .await
             .map_err(|err| Error::CompilerError {
                 details: format!("{}", err),
             })?;
+        if let (true, Some(saved_state_dir)) = (command.write_saved_state, command.saved_state_dir)
+        {
+            write_saved_state(&compiler_state, saved_state_dir, command.saved_state_key)?;
+        }
     }
 
     info!("Done.");
     Ok(())
 }
 
+fn write_saved_state(
+    compiler_state: &CompilerState,
+    saved_state_dir: PathBuf,
+    saved_state_key: Option<String>,
+) -> Result<(), Error> {
+    let saved_state_key = saved_state_key
+        .or_else(|| compiler_state.clock_string())
+        .ok_or_else(|| Error::SavedStateError {
+            details: "The build has no watchman clock, pass a `--savedStateKey`.".to_string(),
+        })?;
+    fs::create_dir_all(&saved_state_dir).map_err(|err| Error::SavedStateError {
+        details: format!("{}", err),
+    })?;
+    let saved_state_loader = LocalSavedStateLoader::new(saved_state_dir);
+    let staging_path = saved_state_loader.staging_path();
+    compiler_state
+        .serialize_to_file(&staging_path)
+        .map_err(|err| Error::SavedStateError {
+            details: format!("{}", err),
+        })?;
+    let saved_state_path = saved_state_loader
+        .store(
+            &saved_state_key,
+            &compiler_state.saved_state_version,
+            &staging_path,
+        )
+        .map_err(|err| Error::SavedStateError {
+            details: format!("{}", err),
+        })?;
+    info!(
+        "Saved state for `{}` written to {:?}.",
+        saved_state_key, saved_state_path
+    );
+    Ok(())
+}
+
 struct ExtraDataProvider {
     locate_command: String,
 }
@@ -408,6 +622,63 @@ async fn handle_lsp_command(command: LspCommand) -> Result<(), Error> {
     Ok(())
 }
 
+async fn handle_schema_diff_command(command: SchemaDiffCommand) -> Result<(), Error> {
+    configure_logger(command.output, TerminalMode::Stderr);
+
+    let changes = schema_change_report::classify_schema_changes(
+        &schema_change_report::read_schema(&command.old)?,
+        &schema_change_report::read_schema(&command.new)?,
+    )?;
+
+    let affected_definitions = if command.skip_definitions || changes.is_empty() {
+        vec![]
+    } else {
+        let mut config = get_config(command.config)?;
+        set_project_flag(&mut config, command.projects)?;
+        config.file_source_config = if should_use_watchman() {
+            FileSourceKind::Watchman
+        } else {
+            FileSourceKind::WalkDir
+        };
+        schema_change_report::get_affected_definitions(config, get_unsafe_type_changes(&changes))
+            .await?
+    };
+
+    schema_change_report::print_report(&changes, &affected_definitions);
+
+    let breaking_changes_count = changes
+        .iter()
+        .filter(|change| change.criticality == ChangeCriticality::Breaking)
+        .count();
+    if command.fail_on_breaking && breaking_changes_count > 0 {
+        return Err(Error::BreakingSchemaChanges {
+            count: breaking_changes_count,
+        });
+    }
+    Ok(())
+}
+
+async fn handle_unused_schema_command(command: UnusedSchemaCommand) -> Result<(), Error> {
+    configure_logger(command.output, TerminalMode::Stderr);
+
+    let mut config = get_config(command.config)?;
+    set_project_flag(&mut config, command.projects)?;
+    config.file_source_config = if should_use_watchman() {
+        FileSourceKind::Watchman
+    } else {
+        FileSourceKind::WalkDir
+    };
+
+    let reports = unused_schema_report::get_unused_schema_items(config).await?;
+    println!(
+        "{}",
+        serde_json::to_string_pretty(&reports).map_err(|err| Error::UnusedSchemaError {
+            details: format!("Unable to serialize the report: {}", err),
+        })?
+    );
+    Ok(())
+}
+
 /// Check if `watchman` is available.
 /// Additionally, this method is checking for an existence of `FORCE_NO_WATCHMAN`
 /// environment variable. If this `FORCE_NO_WATCHMAN` is set, this method will return `false`
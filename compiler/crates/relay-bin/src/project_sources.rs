@@ -0,0 +1,69 @@
+/*
+ * Copyright (c) Meta Platforms, Inc. and affiliates.
+ *
+ * This source code is licensed under the MIT license found in the
+ * LICENSE file in the root directory of this source tree.
+ */
+
+use std::sync::Arc;
+
+use common::PerfLogEvent;
+use common::PerfLogger;
+use relay_compiler::build_project::get_project_asts;
+use relay_compiler::build_project::ProjectAstData;
+use relay_compiler::build_schema;
+use relay_compiler::compiler_state::CompilerState;
+use relay_compiler::config::Config;
+use relay_compiler::errors::Error as CompilerError;
+use relay_compiler::errors::Result;
+use relay_compiler::FileSource;
+use relay_compiler::GraphQLAsts;
+use relay_compiler::ProjectName;
+use schema::SDLSchema;
+
+/// The schema and the documents of a project.
+pub struct ProjectSources {
+    pub project_name: ProjectName,
+    pub schema: Arc<SDLSchema>,
+    pub ast_data: ProjectAstData,
+}
+
+/// Reads the files of the enabled projects and builds their schemas and
+/// documents, without compiling them.
+pub async fn load_project_sources(
+    config: &Arc<Config>,
+    log_event: &impl PerfLogEvent,
+    perf_logger: &impl PerfLogger,
+) -> Result<(CompilerState, Vec<ProjectSources>)> {
+    let file_source = FileSource::connect(config, log_event).await?;
+    let compiler_state = file_source.query(log_event, perf_logger).await?;
+    let graphql_asts = GraphQLAsts::from_graphql_sources_map(
+        &compiler_state.graphql_sources,
+        &Default::default(),
+        config,
+    )?;
+
+    let mut project_sources = vec![];
+    for project_config in config.enabled_projects() {
+        let schema = build_schema(
+            &compiler_state,
+            config,
+            project_config,
+            &graphql_asts,
+            log_event,
+        )
+        .map_err(|errors| CompilerError::DiagnosticsError { errors })?;
+        let ast_data =
+            get_project_asts(&schema, &graphql_asts, project_config).map_err(|error| {
+                CompilerError::BuildProjectsErrors {
+                    errors: vec![error],
+                }
+            })?;
+        project_sources.push(ProjectSources {
+            project_name: project_config.name,
+            schema,
+            ast_data,
+        });
+    }
+    Ok((compiler_state, project_sources))
+}
@@ -0,0 +1,191 @@
+/*
+ * Copyright (c) Meta Platforms, Inc. and affiliates.
+ *
+ * This source code is licensed under the MIT license found in the
+ * LICENSE file in the root directory of this source tree.
+ */
+
+use std::fs;
+use std::path::Path;
+use std::sync::Arc;
+
+use common::ConsoleLogger;
+use common::PerfLogEvent;
+use common::PerfLogger;
+use dependency_analyzer::get_affected_definitions_with_dependents;
+use graphql_ir::ExecutableDefinition;
+use graphql_ir::ExecutableDefinitionName;
+use relay_compiler::build_project::BuildMode;
+use relay_compiler::build_project::ProjectAstData;
+use relay_compiler::build_raw_program;
+use relay_compiler::config::Config;
+use relay_compiler::errors::Error as CompilerError;
+use relay_compiler::ProjectName;
+use rustc_hash::FxHashSet;
+use schema_diff::check::IncrementalBuildSchemaChange;
+use schema_diff::criticality::ChangeCriticality;
+use schema_diff::criticality::ClassifiedChange;
+use schema_diff::definitions::SchemaChange;
+use schema_diff::detect_changes;
+
+use crate::errors::Error;
+use crate::project_sources::load_project_sources;
+use crate::project_sources::ProjectSources;
+
+/// Reads a schema file, or the `.graphql` files of a schema directory.
+pub fn read_schema(path: &Path) -> Result<Vec<String>, Error> {
+    let read_error = |err| Error::SchemaDiffError {
+        details: format!("Unable to read `{}`: {}", path.display(), err),
+    };
+    if !path.is_dir() {
+        return Ok(vec![fs::read_to_string(path).map_err(read_error)?]);
+    }
+    let mut paths = vec![];
+    let mut dirs = vec![path.to_path_buf()];
+    while let Some(dir) = dirs.pop() {
+        for entry in fs::read_dir(&dir).map_err(read_error)? {
+            let entry_path = entry.map_err(read_error)?.path();
+            if entry_path.is_dir() {
+                dirs.push(entry_path);
+            } else if entry_path.extension().is_some_and(|ext| ext == "graphql") {
+                paths.push(entry_path);
+            }
+        }
+    }
+    paths.sort();
+    paths
+        .iter()
+        .map(|path| fs::read_to_string(path).map_err(read_error))
+        .collect()
+}
+
+pub fn classify_schema_changes(
+    old_schema: &[String],
+    new_schema: &[String],
+) -> Result<Vec<ClassifiedChange>, Error> {
+    let old_schema = old_schema.iter().map(String::as_str).collect::<Vec<_>>();
+    let new_schema = new_schema.iter().map(String::as_str).collect::<Vec<_>>();
+    match detect_changes(&new_schema, &old_schema) {
+        SchemaChange::InvalidSchema => Err(Error::SchemaDiffError {
+            details: "Unable to parse the schemas, both should be valid GraphQL SDL.".to_string(),
+        }),
+        schema_change => Ok(schema_change.classify()),
+    }
+}
+
+/// Finds the definitions of the enabled projects that select the types
+/// changed by the schema changes, or depend on such definitions. The
+/// documents are validated against the schemas of the projects, which should
+/// be the old version of the schema.
+pub async fn get_affected_definitions(
+    config: Config,
+    type_changes: FxHashSet<IncrementalBuildSchemaChange>,
+) -> Result<Vec<(ProjectName, Vec<ExecutableDefinitionName>)>, Error> {
+    let config = Arc::new(config);
+    let perf_logger = ConsoleLogger;
+    let log_event = perf_logger.create_event("schema_diff");
+    let report_error = |error: CompilerError| {
+        config.status_reporter.build_errors(&error);
+        Error::SchemaDiffError {
+            details: format!("Unable to build the projects: {}", error),
+        }
+    };
+
+    let (_, project_sources) = load_project_sources(&config, &log_event, &perf_logger)
+        .await
+        .map_err(report_error)?;
+
+    let mut affected_definitions = vec![];
+    for ProjectSources {
+        project_name,
+        schema,
+        ast_data: ProjectAstData { project_asts, .. },
+    } in project_sources
+    {
+        let project_config = &config.projects[&project_name];
+        let (program, _) = build_raw_program(
+            project_config,
+            project_asts,
+            Arc::clone(&schema),
+            &log_event,
+            BuildMode::Full,
+        )
+        .map_err(|error| {
+            report_error(CompilerError::BuildProjectsErrors {
+                errors: vec![error],
+            })
+        })?;
+
+        let definitions = program
+            .operations()
+            .map(|operation| ExecutableDefinition::Operation(operation.as_ref().clone()))
+            .chain(
+                program
+                    .fragments()
+                    .map(|fragment| ExecutableDefinition::Fragment(fragment.as_ref().clone())),
+            )
+            .collect::<Vec<_>>();
+        let mut names =
+            get_affected_definitions_with_dependents(&schema, &definitions, type_changes.clone())
+                .into_iter()
+                .collect::<Vec<_>>();
+        names.sort_by_key(|name| definition_name(*name));
+        affected_definitions.push((project_name, names));
+    }
+    log_event.complete();
+
+    Ok(affected_definitions)
+}
+
+pub fn print_report(
+    changes: &[ClassifiedChange],
+    affected_definitions: &[(ProjectName, Vec<ExecutableDefinitionName>)],
+) {
+    if changes.is_empty() {
+        println!("No schema changes.");
+        return;
+    }
+    for (criticality, title) in [
+        (ChangeCriticality::Breaking, "Breaking changes"),
+        (ChangeCriticality::Dangerous, "Dangerous changes"),
+        (ChangeCriticality::Safe, "Safe changes"),
+    ] {
+        let messages = changes
+            .iter()
+            .filter(|change| change.criticality == criticality)
+            .map(|change| &change.message)
+            .collect::<Vec<_>>();
+        if !messages.is_empty() {
+            println!("{} ({}):", title, messages.len());
+            for message in messages {
+                println!("  - {}", message);
+            }
+            println!();
+        }
+    }
+    for (project_name, names) in affected_definitions {
+        if names.is_empty() {
+            println!("No definitions affected in project `{}`.", project_name);
+        } else {
+            println!(
+                "Definitions affected in project `{}` ({}):",
+                project_name,
+                names.len()
+            );
+            for name in names {
+                let kind = match name {
+                    ExecutableDefinitionName::OperationDefinitionName(_) => "operation",
+                    ExecutableDefinitionName::FragmentDefinitionName(_) => "fragment",
+                };
+                println!("  - {} ({})", definition_name(*name), kind);
+            }
+        }
+    }
+}
+
+fn definition_name(name: ExecutableDefinitionName) -> String {
+    match name {
+        ExecutableDefinitionName::OperationDefinitionName(name) => name.to_string(),
+        ExecutableDefinitionName::FragmentDefinitionName(name) => name.to_string(),
+    }
+}
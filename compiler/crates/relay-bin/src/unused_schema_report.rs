@@ -0,0 +1,114 @@
+/*
+ * Copyright (c) Meta Platforms, Inc. and affiliates.
+ *
+ * This source code is licensed under the MIT license found in the
+ * LICENSE file in the root directory of this source tree.
+ */
+
+use std::collections::BTreeMap;
+use std::path::PathBuf;
+use std::sync::Arc;
+
+use common::ConsoleLogger;
+use common::PerfLogEvent;
+use common::PerfLogger;
+use dependency_analyzer::SchemaUsage;
+use dependency_analyzer::UnusedSchemaItems;
+use relay_compiler::build_programs;
+use relay_compiler::config::Config;
+use relay_compiler::errors::Error as CompilerError;
+use relay_compiler::BuildProjectFailure;
+use relay_compiler::ProjectName;
+use relay_compiler::SchemaLocation;
+use schema::SDLSchema;
+use serde::Serialize;
+
+use crate::errors::Error;
+use crate::project_sources::load_project_sources;
+use crate::project_sources::ProjectSources;
+
+/// The unused items of a schema, shared by one or more projects.
+#[derive(Serialize)]
+pub struct UnusedSchemaReport {
+    pub schema: PathBuf,
+    pub projects: Vec<ProjectName>,
+    #[serde(flatten)]
+    pub unused: UnusedSchemaItems,
+}
+
+/// Compiles the enabled projects and reports the items of their schemas
+/// that are not used by any of their documents, including the documents
+/// generated by the compiler and the fragments of Relay resolvers. The
+/// usages of the projects that share a schema are merged.
+pub async fn get_unused_schema_items(config: Config) -> Result<Vec<UnusedSchemaReport>, Error> {
+    let config = Arc::new(config);
+    let perf_logger = Arc::new(ConsoleLogger);
+    let log_event = perf_logger.create_event("unused_schema");
+    let report_error = |error: CompilerError| {
+        config.status_reporter.build_errors(&error);
+        Error::UnusedSchemaError {
+            details: format!("Unable to build the projects: {}", error),
+        }
+    };
+
+    let (compiler_state, project_sources) =
+        load_project_sources(&config, &log_event, perf_logger.as_ref())
+            .await
+            .map_err(report_error)?;
+
+    let mut usages: BTreeMap<PathBuf, (Arc<SDLSchema>, Vec<ProjectName>, SchemaUsage)> =
+        BTreeMap::new();
+    for ProjectSources {
+        project_name,
+        schema,
+        ast_data,
+    } in project_sources
+    {
+        let project_config = &config.projects[&project_name];
+        let programs = build_programs(
+            &config,
+            project_config,
+            &compiler_state,
+            ast_data.project_asts,
+            ast_data.base_fragment_names,
+            Arc::clone(&schema),
+            &log_event,
+            Arc::clone(&perf_logger),
+        )
+        .map_err(|failure| match failure {
+            BuildProjectFailure::Error(error) => report_error(CompilerError::BuildProjectsErrors {
+                errors: vec![error],
+            }),
+            BuildProjectFailure::Cancelled => report_error(CompilerError::Cancelled),
+        })?
+        .item
+        .0;
+
+        let schema_path = match &project_config.schema_location {
+            SchemaLocation::File(path) | SchemaLocation::Directory(path) => path.clone(),
+        };
+        let (_, projects, usage) = usages
+            .entry(schema_path)
+            .or_insert_with(|| (schema, vec![], SchemaUsage::default()));
+        projects.push(project_name);
+        for programs in programs {
+            // The source program has every document of the project, and the
+            // operation text program the fields added by the compiler.
+            usage.add_program(&programs.source);
+            usage.add_program(&programs.operation_text);
+        }
+    }
+    log_event.complete();
+
+    Ok(usages
+        .into_iter()
+        .map(|(schema_path, (schema, mut projects, usage))| {
+            projects.sort_by_key(|project_name| project_name.to_string());
+            UnusedSchemaReport {
+                schema: schema_path,
+                projects,
+                unused: usage.get_unused(&schema),
+            }
+        })
+        .collect())
+}
@@ -38,6 +38,7 @@ use lazy_static::lazy_static;
 use md5::Digest;
 use md5::Md5;
 use relay_config::JsModuleFormat;
+use relay_config::PersistConfig;
 use relay_config::ProjectConfig;
 use relay_config::Surface;
 use relay_transforms::extract_connection_metadata_from_directive;
@@ -2408,6 +2409,20 @@ impl<'schema, 'builder, 'config> CodegenBuilder<'schema, 'builder, 'config> {
             metadata_items.push(self.build_connection_metadata(connection_metadata))
         }
 
+        // add the Automatic Persisted Queries extension
+        if let (Some(PersistConfig::Apq(_)), Some(QueryID::Persisted { id, .. })) =
+            (&self.project_config.persist, request_parameters.id)
+        {
+            let persisted_query = object! {
+                sha256_hash: Primitive::RawString(id.clone()),
+                version: Primitive::Int(1),
+            };
+            metadata_items.push(ObjectEntry {
+                key: CODEGEN_CONSTANTS.persisted_query,
+                value: Primitive::Key(self.object(persisted_query)),
+            });
+        }
+
         // sort metadata keys
         metadata_items.sort_unstable_by_key(|entry| entry.key);
 
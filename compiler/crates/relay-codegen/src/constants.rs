@@ -93,6 +93,7 @@ pub struct CodegenConstants {
     pub params: StringKey,
     pub passing_value: StringKey,
     pub path: StringKey,
+    pub persisted_query: StringKey,
     pub plural: StringKey,
     pub preloadable_concrete_request: StringKey,
     pub provided_variables: StringKey,
@@ -113,6 +114,7 @@ pub struct CodegenConstants {
     pub scalar_field: StringKey,
     pub scalar_handle: StringKey,
     pub selections: StringKey,
+    pub sha256_hash: StringKey,
     pub split_operation: StringKey,
     pub storage_key: StringKey,
     pub stream: StringKey,
@@ -125,6 +127,7 @@ pub struct CodegenConstants {
     pub value: StringKey,
     pub variable_name: StringKey,
     pub variable: StringKey,
+    pub version: StringKey,
     pub weak_model: StringKey,
 }
 
@@ -213,6 +216,7 @@ lazy_static! {
         params: "params".intern(),
         passing_value: "passingValue".intern(),
         path: "path".intern(),
+        persisted_query: "persistedQuery".intern(),
         plural: "plural".intern(),
         preloadable_concrete_request: "PreloadableConcreteRequest".intern(),
         provided_variables: "providedVariables".intern(),
@@ -233,6 +237,7 @@ lazy_static! {
         scalar_field: "ScalarField".intern(),
         scalar_handle: "ScalarHandle".intern(),
         selections: "selections".intern(),
+        sha256_hash: "sha256Hash".intern(),
         split_operation: "SplitOperation".intern(),
         storage_key: "storageKey".intern(),
         stream: "Stream".intern(),
@@ -245,6 +250,7 @@ lazy_static! {
         value: "value".intern(),
         variable_name: "variableName".intern(),
         variable: "Variable".intern(),
+        version: "version".intern(),
         weak_model: "WeakModel".intern(),
     };
 }
@@ -12,7 +12,9 @@ use std::sync::Arc;
 
 use common::SourceLocationKey;
 use content::generate_fragment;
+use content::generate_fragment_ref_brands_module_content;
 use content::generate_operation;
+use content::generate_resolver_function_types_module_content;
 use content::generate_resolvers_schema_module_content;
 use content::generate_split_operation;
 use content::generate_updatable_query;
@@ -20,6 +22,8 @@ use graphql_ir::FragmentDefinition;
 use graphql_ir::OperationDefinition;
 use relay_codegen::Printer;
 use relay_codegen::QueryID;
+use relay_typegen::generate_fragment_json_schema;
+use relay_typegen::generate_operation_json_schema;
 use relay_typegen::FragmentLocations;
 use schema::SDLSchema;
 
@@ -57,7 +61,16 @@ pub enum ArtifactContent {
         source_hash: Option<String>,
         no_optional_fields_in_raw_response_type: bool,
     },
+    OperationJsonSchema {
+        normalization_operation: Arc<OperationDefinition>,
+        typegen_operation: Arc<OperationDefinition>,
+    },
+    FragmentJsonSchema {
+        typegen_fragment: Arc<FragmentDefinition>,
+    },
     ResolversSchema,
+    FragmentRefBrandsModule,
+    ResolverFunctionTypesModule,
     Generic {
         content: Vec<u8>,
     },
@@ -163,10 +176,43 @@ impl ArtifactContent {
                 fragment_locations,
             )
             .unwrap(),
+            ArtifactContent::OperationJsonSchema {
+                normalization_operation,
+                typegen_operation,
+            } => generate_operation_json_schema(
+                typegen_operation,
+                normalization_operation,
+                schema,
+                project_config,
+                fragment_locations,
+                printer.print_provided_variables(schema, normalization_operation),
+            )
+            .into_bytes(),
+            ArtifactContent::FragmentJsonSchema { typegen_fragment } => {
+                generate_fragment_json_schema(
+                    typegen_fragment,
+                    schema,
+                    project_config,
+                    fragment_locations,
+                )
+                .into_bytes()
+            }
             ArtifactContent::ResolversSchema => {
                 generate_resolvers_schema_module_content(config, project_config, printer, schema)
                     .unwrap()
             }
+            ArtifactContent::FragmentRefBrandsModule => {
+                generate_fragment_ref_brands_module_content(config, project_config).unwrap()
+            }
+            ArtifactContent::ResolverFunctionTypesModule => {
+                generate_resolver_function_types_module_content(
+                    config,
+                    project_config,
+                    schema,
+                    fragment_locations,
+                )
+                .unwrap()
+            }
             ArtifactContent::Generic { content } => content.clone(),
         }
     }
@@ -23,6 +23,8 @@ use relay_transforms::ASSIGNABLE_DIRECTIVE;
 use relay_typegen::generate_fragment_type_exports_section;
 use relay_typegen::generate_named_validator_export;
 use relay_typegen::generate_operation_type_exports_section;
+use relay_typegen::generate_resolver_function_types_module;
+use relay_typegen::generate_response_validator_export;
 use relay_typegen::generate_split_operation_type_exports_section;
 use relay_typegen::FragmentLocations;
 use relay_typegen::TypegenConfig;
@@ -490,6 +492,20 @@ pub fn generate_operation(
     content_sections.push(ContentSection::Generic(section));
     // -- End Export Section --
 
+    // -- Begin Response Validator Section --
+    if project_config.typegen_config.generate_response_validators {
+        let mut section = GenericSection::default();
+        let response_validator_export = generate_response_validator_export(
+            typegen_operation,
+            schema,
+            project_config,
+            fragment_locations,
+        );
+        writeln!(section, "{}", response_validator_export)?;
+        content_sections.push(ContentSection::Generic(section));
+    }
+    // -- End Response Validator Section --
+
     content_sections.into_signed_bytes()
 }
 
@@ -1142,3 +1158,101 @@ pub fn generate_resolvers_schema_module_content(
 
     content_sections.into_signed_bytes()
 }
+
+pub fn generate_fragment_ref_brands_module_content(
+    config: &Config,
+    project_config: &ProjectConfig,
+) -> Result<Vec<u8>, FmtError> {
+    let mut content_sections = ContentSections::default();
+    // -- Begin Docblock Section --
+    content_sections.push(ContentSection::Docblock(generate_docblock_section(
+        config,
+        project_config,
+        vec![],
+    )?));
+    // -- End Docblock Section --
+
+    // -- Begin Disable Lint Section --
+    content_sections.push(ContentSection::Generic(generate_disable_lint_section(
+        &project_config.typegen_config.language,
+    )?));
+    // -- End Disable Lint Section --
+
+    // -- Begin Types Section --
+    let mut section = GenericSection::default();
+    write_import_type_from(
+        project_config,
+        &mut section,
+        "FragmentRefs as RuntimeFragmentRefs",
+        "relay-runtime",
+    )?;
+    writeln!(section)?;
+    // The brands are never exported, so only the generated artifacts can
+    // produce values of these types.
+    writeln!(section, "declare const fragmentRefsBrand: unique symbol;")?;
+    writeln!(section, "declare const fragmentTypeBrand: unique symbol;")?;
+    writeln!(section)?;
+    writeln!(
+        section,
+        "export type FragmentRefs<TRefs extends string> = RuntimeFragmentRefs<TRefs> & {{\n  readonly [fragmentRefsBrand]: {{ readonly [TRef in TRefs]: true }};\n}};"
+    )?;
+    writeln!(
+        section,
+        "export type FragmentType<TName extends string> = TName & {{\n  readonly [fragmentTypeBrand]: TName;\n}};"
+    )?;
+    content_sections.push(ContentSection::Generic(section));
+    // -- End Types Section --
+
+    content_sections.into_signed_bytes()
+}
+
+pub fn generate_resolver_function_types_module_content(
+    config: &Config,
+    project_config: &ProjectConfig,
+    schema: &SDLSchema,
+    fragment_locations: &FragmentLocations,
+) -> Result<Vec<u8>, FmtError> {
+    let artifact_path = project_config
+        .typegen_config
+        .resolver_function_types_module
+        .clone()
+        .unwrap();
+
+    let mut content_sections = ContentSections::default();
+    // -- Begin Docblock Section --
+    content_sections.push(ContentSection::Docblock(generate_docblock_section(
+        config,
+        project_config,
+        vec![],
+    )?));
+    // -- End Docblock Section --
+
+    // -- Begin Disable Lint Section --
+    content_sections.push(ContentSection::Generic(generate_disable_lint_section(
+        &project_config.typegen_config.language,
+    )?));
+    // -- End Disable Lint Section --
+
+    // -- Begin Use Strict Section --
+    content_sections.push(ContentSection::Generic(generate_use_strict_section(
+        &project_config.typegen_config.language,
+    )?));
+    // -- End Use Strict Section --
+
+    // -- Begin Types Section --
+    let mut section = GenericSection::default();
+    write!(
+        section,
+        "{}",
+        generate_resolver_function_types_module(
+            schema,
+            project_config,
+            fragment_locations,
+            artifact_path,
+        )
+    )?;
+    content_sections.push(ContentSection::Generic(section));
+    // -- End Types Section --
+
+    content_sections.into_signed_bytes()
+}
@@ -16,14 +16,17 @@ use relay_transforms::ArtifactSourceKeyData;
 use serde::Deserialize;
 use serde::Serialize;
 
+use crate::build_project::md5;
 use crate::build_project::Artifact;
 use crate::build_project::ArtifactContent;
 
-/// Record that contains path to the artifact, persisted_operation_id (when available)
+/// Record that contains path to the artifact, persisted_operation_id and
+/// text_hash of operations (when available)
 #[derive(Serialize, Deserialize, Debug, Clone)]
 pub struct ArtifactRecord {
     pub path: PathBuf,
     pub persisted_operation_id: Option<String>,
+    pub text_hash: Option<String>,
 }
 /// A map from DefinitionName to output artifacts records
 #[derive(Default, Serialize, Deserialize, Debug, Clone)]
@@ -47,15 +50,20 @@ impl From<ArtifactSourceKeyData> for ArtifactSourceKey {
 
 impl ArtifactMap {
     pub fn insert(&self, artifact: Artifact) {
+        let (persisted_operation_id, text_hash) = match artifact.content {
+            ArtifactContent::Operation {
+                id_and_text_hash: Some(QueryID::Persisted { id, text_hash }),
+                ..
+            } => (Some(id), Some(text_hash)),
+            ArtifactContent::Operation {
+                text: Some(text), ..
+            } => (None, Some(md5(&text))),
+            _ => (None, None),
+        };
         let artifact_tuple = ArtifactRecord {
             path: artifact.path,
-            persisted_operation_id: match artifact.content {
-                ArtifactContent::Operation {
-                    id_and_text_hash: Some(QueryID::Persisted { id, .. }),
-                    ..
-                } => Some(id),
-                _ => None,
-            },
+            persisted_operation_id,
+            text_hash,
         };
 
         for source_key in artifact.artifact_source_keys {
@@ -18,6 +18,7 @@ pub mod generate_extra_artifacts;
 pub mod get_artifacts_file_hash_map;
 mod log_program_stats;
 mod persist_operations;
+mod persisted_operation_manifest;
 mod project_asts;
 mod source_control;
 mod validate;
@@ -53,10 +54,13 @@ use indexmap::IndexSet;
 use log::debug;
 use log::info;
 use log::warn;
+pub(crate) use persist_operations::md5;
 use petgraph::unionfind::UnionFind;
 use rayon::iter::IntoParallelRefIterator;
 use rayon::slice::ParallelSlice;
 use relay_codegen::Printer;
+use relay_config::LocalPersistConfig;
+use relay_config::PersistConfig;
 use relay_config::ProjectName;
 use relay_transforms::apply_transforms;
 use relay_transforms::CustomTransformsConfig;
@@ -73,6 +77,8 @@ pub use validate::validate_reader;
 pub use validate::AdditionalValidations;
 
 use self::log_program_stats::print_stats;
+use self::persisted_operation_manifest::get_manifest_entries;
+use self::persisted_operation_manifest::write_manifest;
 pub use self::project_asts::find_duplicates;
 pub use self::project_asts::get_project_asts;
 pub use self::project_asts::ProjectAstData;
@@ -568,6 +574,7 @@ pub async fn commit_project(
         return Err(BuildProjectFailure::Cancelled);
     }
 
+    let mut manifest = None;
     if let Some(operation_persister) = config
         .create_operation_persister
         .as_ref()
@@ -585,6 +592,18 @@ pub async fn commit_project(
         )
         .await?;
         log_event.stop(persist_operations_timer);
+
+        if let Some(PersistConfig::Local(LocalPersistConfig {
+            manifest: Some(manifest_path),
+            algorithm,
+            ..
+        })) = &project_config.persist
+        {
+            manifest = Some((
+                manifest_path,
+                get_manifest_entries(&artifacts, schema, algorithm),
+            ));
+        }
     }
 
     if source_control_update_status.is_started() {
@@ -744,6 +763,12 @@ pub async fn commit_project(
         }
     };
 
+    // Written once the artifact map is updated, to remove the entries of the
+    // operations that were deleted
+    if let Some((manifest_path, entries)) = manifest {
+        write_manifest(manifest_path, entries, &next_artifact_map)?;
+    }
+
     if source_control_update_status.is_started() {
         log_event.number("update_artifacts_after_source_control_update", 1);
         debug!(
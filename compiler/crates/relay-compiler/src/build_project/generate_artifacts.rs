@@ -19,6 +19,7 @@ use intern::string_key::StringKey;
 use intern::Lookup;
 use relay_codegen::QueryID;
 use relay_config::ResolversSchemaModuleConfig;
+use relay_config::TypegenLanguage;
 use relay_transforms::ArtifactSourceKeyData;
 use relay_transforms::ClientEdgeGeneratedQueryMetadataDirective;
 use relay_transforms::Programs;
@@ -57,7 +58,7 @@ pub fn generate_artifacts(
         ..Default::default()
     };
     let mut operation_printer = OperationPrinter::new(&programs.operation_text, printer_options);
-    return group_operations(programs).into_values().map(|operations| {
+    let mut artifacts: Vec<Artifact> = group_operations(programs).into_values().map(|operations| {
             if let Some(normalization) = operations.normalization {
                 // We have a normalization AST... so we'll move forward with that
                 if let Some(metadata) = SplitOperationMetadata::find(&normalization.directives)
@@ -196,7 +197,34 @@ pub fn generate_artifacts(
                 _ => vec![],
             }
         )
+        .chain(
+            match project_config.typegen_config.typescript_fragment_ref_brands_module {
+                Some(ref path) if project_config.typegen_config.language == TypegenLanguage::TypeScript =>
+                vec![
+                    generate_fragment_ref_brands_module_artifact(path.clone())
+                ],
+                _ => vec![],
+            }
+        )
+        .chain(
+            match project_config.typegen_config.resolver_function_types_module {
+                Some(ref path) if project_config.typegen_config.language != TypegenLanguage::JavaScript =>
+                vec![
+                    generate_resolver_function_types_module_artifact(path.clone())
+                ],
+                _ => vec![],
+            }
+        )
         .collect();
+
+    if project_config.typegen_config.generate_json_schemas {
+        let json_schema_artifacts: Vec<Artifact> = artifacts
+            .iter()
+            .filter_map(generate_json_schema_artifact)
+            .collect();
+        artifacts.extend(json_schema_artifacts);
+    }
+    artifacts
 }
 
 fn generate_normalization_artifact(
@@ -299,6 +327,33 @@ fn generate_reader_artifact(
     }
 }
 
+/// The JSON Schema document of an operation or fragment is written next to its
+/// artifact, e.g. `__generated__/FooQuery.graphql.schema.json`.
+fn generate_json_schema_artifact(artifact: &Artifact) -> Option<Artifact> {
+    let content = match &artifact.content {
+        ArtifactContent::Operation {
+            normalization_operation,
+            typegen_operation,
+            ..
+        } => ArtifactContent::OperationJsonSchema {
+            normalization_operation: Arc::clone(normalization_operation),
+            typegen_operation: Arc::clone(typegen_operation),
+        },
+        ArtifactContent::Fragment {
+            typegen_fragment, ..
+        } => ArtifactContent::FragmentJsonSchema {
+            typegen_fragment: Arc::clone(typegen_fragment),
+        },
+        _ => return None,
+    };
+    Some(Artifact {
+        artifact_source_keys: artifact.artifact_source_keys.clone(),
+        path: artifact.path.with_extension("schema.json"),
+        content,
+        source_file: artifact.source_file,
+    })
+}
+
 /// Operation with the same name from different `Program`s.
 struct OperationGroup<'a> {
     normalization: Option<&'a Arc<OperationDefinition>>,
@@ -391,3 +446,21 @@ fn generate_resolvers_schema_module_artifact(path: PathBuf) -> Artifact {
         source_file: SourceLocationKey::generated(),
     }
 }
+
+fn generate_fragment_ref_brands_module_artifact(path: PathBuf) -> Artifact {
+    Artifact {
+        artifact_source_keys: vec![ArtifactSourceKey::Schema()],
+        path,
+        content: ArtifactContent::FragmentRefBrandsModule,
+        source_file: SourceLocationKey::generated(),
+    }
+}
+
+fn generate_resolver_function_types_module_artifact(path: PathBuf) -> Artifact {
+    Artifact {
+        artifact_source_keys: vec![ArtifactSourceKey::Schema()],
+        path,
+        content: ArtifactContent::ResolverFunctionTypesModule,
+        source_file: SourceLocationKey::generated(),
+    }
+}
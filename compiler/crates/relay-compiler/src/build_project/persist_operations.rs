@@ -42,7 +42,7 @@ pub async fn persist_operations(
     log_event: &impl PerfLogEvent,
     programs: &Programs,
 ) -> Result<(), BuildProjectError> {
-    let handles = artifacts
+    let pending = artifacts
         .par_iter_mut()
         .flat_map(|artifact| {
             if let ArtifactContent::Operation {
@@ -74,6 +74,7 @@ pub async fn persist_operations(
                             normalization_operation,
                         );
                     }
+                    let operation_name = normalization_operation.name.item.to_string();
                     let artifact_path = root_dir.join(&artifact.path);
                     let extracted_persist_id = if config.repersist_operations {
                         None
@@ -84,19 +85,15 @@ pub async fn persist_operations(
                         *id_and_text_hash = Some(QueryID::Persisted { id, text_hash });
                         None
                     } else {
-                        let text = text.clone();
-                        Some(async move {
-                            operation_persister
-                                .persist_artifact(ArtifactForPersister {
-                                    text,
-                                    relative_path,
-                                    override_schema,
-                                })
-                                .await
-                                .map(|id| {
-                                    *id_and_text_hash = Some(QueryID::Persisted { id, text_hash });
-                                })
-                        })
+                        Some((
+                            (id_and_text_hash, text_hash),
+                            ArtifactForPersister {
+                                text: text.clone(),
+                                relative_path,
+                                override_schema,
+                                operation_name,
+                            },
+                        ))
                     }
                 } else {
                     None
@@ -106,8 +103,11 @@ pub async fn persist_operations(
             }
         })
         .collect::<Vec<_>>();
-    log_event.number("persist_documents", handles.len());
-    let results = futures::future::join_all(handles).await;
+    log_event.number("persist_documents", pending.len());
+    let (targets, artifacts_for_persister): (Vec<_>, Vec<_>) = pending.into_iter().unzip();
+    let results = operation_persister
+        .persist_artifacts(artifacts_for_persister)
+        .await;
     operation_persister
         .finalize()
         .map_err(|error| BuildProjectError::PersistErrors {
@@ -115,10 +115,13 @@ pub async fn persist_operations(
             project_name: project_config.name,
         })?;
     debug!("done persisting");
-    let errors = results
-        .into_iter()
-        .filter_map(Result::err)
-        .collect::<Vec<_>>();
+    let mut errors = vec![];
+    for ((id_and_text_hash, text_hash), result) in targets.into_iter().zip(results) {
+        match result {
+            Ok(id) => *id_and_text_hash = Some(QueryID::Persisted { id, text_hash }),
+            Err(error) => errors.push(error),
+        }
+    }
     if !errors.is_empty() {
         let error = BuildProjectError::PersistErrors {
             errors,
@@ -160,7 +163,7 @@ fn extract_request_id(content: &str) -> Option<String> {
         .map(|captures| captures[1].to_owned())
 }
 
-fn md5(data: &str) -> String {
+pub(crate) fn md5(data: &str) -> String {
     let mut md5 = Md5::new();
     md5.update(data);
     hex::encode(md5.finalize())
@@ -0,0 +1,131 @@
+/*
+ * Copyright (c) Meta Platforms, Inc. and affiliates.
+ *
+ * This source code is licensed under the MIT license found in the
+ * LICENSE file in the root directory of this source tree.
+ */
+
+use std::collections::BTreeMap;
+use std::fs::File;
+use std::io;
+use std::io::BufWriter;
+use std::io::Write;
+use std::path::Path;
+use std::path::PathBuf;
+
+use fnv::FnvHashSet;
+use indexmap::IndexMap;
+use relay_codegen::QueryID;
+use relay_config::LocalPersistAlgorithm;
+use schema::SDLSchema;
+use schema::Schema;
+use serde::Deserialize;
+use serde::Serialize;
+
+use crate::artifact_map::ArtifactMap;
+use crate::errors::BuildProjectError;
+use crate::Artifact;
+use crate::ArtifactContent;
+
+/// An entry of the manifest written to `LocalPersistConfig::manifest`.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ManifestEntry {
+    id: String,
+    name: String,
+    kind: String,
+    algorithm: LocalPersistAlgorithm,
+    source_file: PathBuf,
+    /// Variable names of the operation, mapped to their printed GraphQL types.
+    variables: IndexMap<String, String>,
+    text: String,
+}
+
+/// Returns the manifest entries of the persisted operations of the artifacts.
+pub fn get_manifest_entries(
+    artifacts: &[Artifact],
+    schema: &SDLSchema,
+    algorithm: &LocalPersistAlgorithm,
+) -> Vec<ManifestEntry> {
+    artifacts
+        .iter()
+        .filter_map(|artifact| {
+            let ArtifactContent::Operation {
+                normalization_operation,
+                text: Some(text),
+                id_and_text_hash: Some(QueryID::Persisted { id, .. }),
+                ..
+            } = &artifact.content
+            else {
+                return None;
+            };
+            Some(ManifestEntry {
+                id: id.clone(),
+                name: normalization_operation.name.item.to_string(),
+                kind: normalization_operation.kind.to_string(),
+                algorithm: algorithm.clone(),
+                source_file: PathBuf::from(artifact.source_file.path()),
+                variables: normalization_operation
+                    .variable_definitions
+                    .iter()
+                    .map(|variable| {
+                        (
+                            variable.name.item.to_string(),
+                            schema.get_type_string(&variable.type_),
+                        )
+                    })
+                    .collect(),
+                text: text.clone(),
+            })
+        })
+        .collect()
+}
+
+/// Writes the manifest with the entries of the operations of the artifact map
+/// of the project. The entries of the operations that were not persisted by
+/// this build are kept from the previous manifest, and the ones of the
+/// operations that are no longer in the artifact map are removed.
+pub fn write_manifest(
+    path: &Path,
+    entries: Vec<ManifestEntry>,
+    artifact_map: &ArtifactMap,
+) -> Result<(), BuildProjectError> {
+    let persisted_ids = artifact_map
+        .0
+        .iter()
+        .flat_map(|entry| {
+            entry
+                .value()
+                .iter()
+                .filter_map(|record| record.persisted_operation_id.clone())
+                .collect::<Vec<_>>()
+        })
+        .collect::<FnvHashSet<_>>();
+    let previous_entries: Vec<ManifestEntry> = std::fs::read_to_string(path)
+        .ok()
+        .and_then(|content| serde_json::from_str(&content).ok())
+        .unwrap_or_default();
+
+    // Sort by name, then id, to keep the manifest stable across builds.
+    let manifest = previous_entries
+        .into_iter()
+        .chain(entries)
+        .filter(|entry| persisted_ids.contains(&entry.id))
+        .map(|entry| ((entry.name.clone(), entry.id.clone()), entry))
+        .collect::<BTreeMap<_, _>>();
+
+    write_entries(path, manifest.values()).map_err(|source| BuildProjectError::WriteFileError {
+        file: path.to_path_buf(),
+        source,
+    })
+}
+
+fn write_entries<'a>(
+    path: &Path,
+    entries: impl Iterator<Item = &'a ManifestEntry>,
+) -> io::Result<()> {
+    let mut writer = BufWriter::new(File::create(path)?);
+    serde_json::to_writer_pretty(&mut writer, &entries.collect::<Vec<_>>())?;
+    writer.write_all(b"\n")?;
+    writer.flush()
+}
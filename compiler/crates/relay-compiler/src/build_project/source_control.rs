@@ -100,19 +100,52 @@ impl SourceControlStartAndStopCommands for Sapling {
     }
 }
 
+/// Stages added artifacts, and removes deleted artifacts from the index.
+/// Unlike the commands of other source control tools, git commands are run
+/// one after the other, as they would fail to take the lock of the index
+/// concurrently.
 struct Git;
 
-impl SourceControlStartAndStopCommands for Git {
-    fn start_tracking_command() -> Command {
-        let mut command = Command::new("git");
-        command.arg("add");
-        command
+impl Git {
+    fn run(root_dir: &Path, args: &[&str], files: &Mutex<Vec<PathBuf>>) {
+        let mut files = files.lock().unwrap();
+        for files_chunk in files.chunks(100) {
+            let status = Command::new("git")
+                .current_dir(root_dir)
+                .args(args)
+                .arg("--")
+                .args(files_chunk)
+                .stdout(Stdio::null())
+                .stderr(Stdio::null())
+                .stdin(Stdio::null())
+                .status();
+            // `git add` fails if some of the files are ignored, but it still
+            // stages the other files.
+            if !matches!(status, Ok(status) if status.success()) {
+                debug!("Failed to run `git {}` for some files.", args.join(" "));
+            }
+        }
+        files.clear();
     }
+}
 
-    fn stop_tracking_command() -> Command {
-        let mut command = Command::new("git");
-        command.arg("rm").arg("--cached");
-        command
+impl SourceControl for Git {
+    fn add_files(&self, root_dir: &Path, added: &Mutex<Vec<PathBuf>>) -> crate::errors::Result<()> {
+        Self::run(root_dir, &["add"], added);
+        Ok(())
+    }
+
+    fn remove_files(
+        &self,
+        root_dir: &Path,
+        removed: &Mutex<Vec<PathBuf>>,
+    ) -> crate::errors::Result<()> {
+        Self::run(
+            root_dir,
+            &["rm", "--cached", "--quiet", "--ignore-unmatch"],
+            removed,
+        );
+        Ok(())
     }
 }
 
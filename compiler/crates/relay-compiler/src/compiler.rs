@@ -16,6 +16,7 @@ use docblock_shared::ResolverSourceHash;
 use futures::future::join_all;
 use graphql_watchman::WatchmanFileSourceSubscriptionNextChange;
 use log::debug;
+use log::error;
 use log::info;
 use rayon::prelude::*;
 use tokio::sync::Notify;
@@ -148,6 +149,21 @@ impl<TPerfLogger: PerfLogger> Compiler<TPerfLogger> {
                                     WatchmanFileSourceSubscriptionNextChange::None => {}
                                 }
                             }
+                            Ok(FileSourceSubscriptionNextChange::Notify(file_source_changes)) => {
+                                pending_file_source_changes
+                                    .write()
+                                    .unwrap()
+                                    .push(FileSourceResult::WalkDir(file_source_changes));
+                                notify_sender.notify_one();
+                            }
+                            Err(err @ (Error::FileWatcher { .. } | Error::FileWatcherStopped)) => {
+                                // The changes since the error are unknown, so the
+                                // subscription is restarted with a full build.
+                                error!("Restarting the file watcher after an error: {}", err);
+                                source_control_update_status.mark_as_completed();
+                                notify_sender.notify_one();
+                                break;
+                            }
                             Err(err) => {
                                 panic!("Watchman subscription error: {}", err);
                             }
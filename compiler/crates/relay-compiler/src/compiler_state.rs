@@ -38,6 +38,8 @@ use schema_diff::detect_changes;
 use schemars::JsonSchema;
 use serde::Deserialize;
 use serde::Serialize;
+use watchman_client::prelude::ClockSpec;
+use watchman_client::prelude::FatClockData;
 use zstd::stream::read::Decoder as ZstdDecoder;
 use zstd::stream::write::Encoder as ZstdEncoder;
 
@@ -669,6 +671,18 @@ impl CompilerState {
         result
     }
 
+    /// The watchman clock of the state, as a string.
+    pub fn clock_string(&self) -> Option<String> {
+        match &self.clock {
+            Some(Clock::Spec(ClockSpec::StringClock(clock)))
+            | Some(Clock::ScmAware(FatClockData {
+                clock: ClockSpec::StringClock(clock),
+                ..
+            })) => Some(clock.clone()),
+            _ => None,
+        }
+    }
+
     pub fn serialize_to_file(&self, path: &PathBuf) -> Result<()> {
         let zstd_level: i32 = env::var("RELAY_SAVED_STATE_ZSTD_LEVEL").map_or_else(
             |_| 12,
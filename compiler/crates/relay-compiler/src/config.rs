@@ -31,6 +31,7 @@ use js_config_loader::LoaderSource;
 use persist_query::PersistError;
 use rayon::prelude::*;
 use regex::Regex;
+pub use relay_config::ApqPersistConfig;
 use relay_config::CustomType;
 use relay_config::DiagnosticReportConfig;
 pub use relay_config::ExtraArtifactsConfig;
@@ -40,7 +41,9 @@ use relay_config::ModuleImportConfig;
 pub use relay_config::PersistConfig;
 pub use relay_config::ProjectConfig;
 use relay_config::ProjectName;
+pub use relay_config::RemotePersistBatchConfig;
 pub use relay_config::RemotePersistConfig;
+pub use relay_config::RequestEncoding;
 use relay_config::ResolversSchemaModuleConfig;
 use relay_config::SchemaConfig;
 pub use relay_config::SchemaLocation;
@@ -79,6 +82,11 @@ use crate::GraphQLAsts;
 
 type FnvIndexMap<K, V> = IndexMap<K, V, FnvBuildHasher>;
 
+/// The version of the format of the saved state, part of the saved state
+/// version. Bump it when the serialized `CompilerState` changes, as saved
+/// states of other formats can't be deserialized.
+const SAVED_STATE_FORMAT_VERSION: u32 = 1;
+
 type PostArtifactsWriter = Box<
     dyn Fn(&Config) -> std::result::Result<(), Box<dyn std::error::Error + Send + Sync>>
         + Send
@@ -203,6 +211,9 @@ pub enum FileSourceKind {
     /// This can be used to replace watchman queries
     External(PathBuf),
     WalkDir,
+    /// Files changed since this git revision, merged into the saved state of
+    /// the revision. Without saved state, all files are compiled.
+    Git(String),
 }
 
 fn normalize_path_from_config(
@@ -325,6 +336,7 @@ impl Config {
         validate_fs: bool,
     ) -> Result<Self> {
         let mut hash = Sha1::new();
+        hash.update(SAVED_STATE_FORMAT_VERSION.to_le_bytes());
         serde_json::to_writer(&mut hash, &config_file).unwrap();
 
         let is_multi_project = match config_file {
@@ -1087,17 +1099,44 @@ pub struct ArtifactForPersister {
     pub text: String,
     pub relative_path: PathBuf,
     pub override_schema: Option<String>,
+    /// Name of the persisted operation.
+    pub operation_name: String,
 }
 
 #[async_trait]
 pub trait OperationPersister {
     async fn persist_artifact(&self, artifact: ArtifactForPersister) -> PersistResult<PersistId>;
 
+    /// Persists all the artifacts of a build. The results are in the same
+    /// order as the artifacts. By default, each artifact is persisted with
+    /// `persist_artifact`, concurrently.
+    async fn persist_artifacts(
+        &self,
+        artifacts: Vec<ArtifactForPersister>,
+    ) -> Vec<PersistResult<PersistId>> {
+        persist_each_artifact(self, artifacts).await
+    }
+
     fn finalize(&self) -> PersistResult<()> {
         Ok(())
     }
 }
 
+/// Persists each artifact with `persist_artifact`, concurrently. This is the
+/// default `persist_artifacts`, for persisters that override it only for some
+/// of their configs.
+pub async fn persist_each_artifact<P: OperationPersister + Sync + ?Sized>(
+    persister: &P,
+    artifacts: Vec<ArtifactForPersister>,
+) -> Vec<PersistResult<PersistId>> {
+    futures::future::join_all(
+        artifacts
+            .into_iter()
+            .map(|artifact| persister.persist_artifact(artifact)),
+    )
+    .await
+}
+
 // Below are structs that we use as part of our config that are defined in
 // crates that are not part of Relay. We are not able to implement `JsonSchema`
 // for these structs directly, so we define these shadow structs which match the
@@ -99,6 +99,15 @@ pub enum Error {
         source: watchman_client::Error,
     },
 
+    #[error("File watcher error: {source}")]
+    FileWatcher {
+        #[serde(skip_serializing)]
+        source: notify::Error,
+    },
+
+    #[error("File watcher stopped unexpectedly.")]
+    FileWatcherStopped,
+
     #[error("Watchman query returned no results.")]
     EmptyQueryResult,
 
@@ -131,6 +140,9 @@ pub enum Error {
     #[error("Unable to parse changed files list. {reason}")]
     ExternalSourceParseError { reason: String },
 
+    #[error("Unable to compute changed files with git. {reason}")]
+    GitChangedFilesError { reason: String },
+
     #[error("JSON parse error in `{file}`: {source}")]
     SerdeError {
         file: PathBuf,
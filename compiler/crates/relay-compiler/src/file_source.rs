@@ -10,6 +10,8 @@ mod extract_graphql;
 mod file_categorizer;
 mod file_filter;
 mod file_group;
+mod git_file_source;
+mod notify_file_source_subscription;
 mod read_file_to_string;
 mod source_control_update_status;
 mod walk_dir_file_source;
@@ -25,10 +27,12 @@ use external_file_source::ExternalFileSource;
 pub use file_categorizer::categorize_files;
 pub use file_categorizer::FileCategorizer;
 pub use file_group::FileGroup;
+use git_file_source::GitFileSource;
 use graphql_watchman::WatchmanFileSourceResult;
 use graphql_watchman::WatchmanFileSourceSubscription;
 use graphql_watchman::WatchmanFileSourceSubscriptionNextChange;
 use log::warn;
+pub use notify_file_source_subscription::NotifyFileSourceSubscription;
 pub use read_file_to_string::read_file_to_string;
 use serde::Deserialize;
 use serde_bser::value::Value;
@@ -45,7 +49,7 @@ pub use self::extract_graphql::LocatedGraphQLSource;
 pub use self::extract_graphql::LocatedJavascriptSourceFeatures;
 pub use self::extract_graphql::SourceReader;
 use self::walk_dir_file_source::WalkDirFileSource;
-use self::walk_dir_file_source::WalkDirFileSourceResult;
+pub use self::walk_dir_file_source::WalkDirFileSourceResult;
 use crate::compiler_state::CompilerState;
 use crate::config::Config;
 use crate::config::FileSourceKind;
@@ -56,6 +60,7 @@ pub enum FileSource {
     Watchman(WatchmanFileSource),
     External(ExternalFileSource),
     WalkDir(WalkDirFileSource),
+    Git(GitFileSource),
 }
 
 impl FileSource {
@@ -73,6 +78,10 @@ impl FileSource {
             FileSourceKind::WalkDir => {
                 Ok(Self::WalkDir(WalkDirFileSource::new(Arc::clone(config))))
             }
+            FileSourceKind::Git(base_revision) => Ok(Self::Git(GitFileSource::new(
+                base_revision.clone(),
+                Arc::clone(config),
+            ))),
         }
     }
 
@@ -104,6 +113,25 @@ impl FileSource {
                 }
             }
             Self::WalkDir(file_source) => file_source.create_compiler_state(perf_logger),
+            Self::Git(file_source) => {
+                if file_source.config.load_saved_state_file.is_some() {
+                    let result = file_source.create_compiler_state(perf_logger);
+                    if let Err(err) = &result {
+                        perf_logger_event.string(
+                            "git_file_source_create_compiler_state_error",
+                            format!("{:?}", err),
+                        );
+                        warn!(
+                            "Unable to create state from git changes: {:?}. Compiling all files...",
+                            err
+                        );
+                    } else {
+                        return result;
+                    }
+                }
+                WalkDirFileSource::new(Arc::clone(&file_source.config))
+                    .create_compiler_state(perf_logger)
+            }
         }
     }
 
@@ -122,9 +150,20 @@ impl FileSource {
                     FileSourceSubscription::Watchman(watchman_subscription),
                 ))
             }
-            Self::External(_) | Self::WalkDir(_) => {
+            Self::WalkDir(file_source) => {
+                // Start watching before the files are read, so that no change
+                // is missed.
+                let notify_subscription =
+                    NotifyFileSourceSubscription::new(Arc::clone(&file_source.config))?;
+                let compiler_state = file_source.create_compiler_state(perf_logger)?;
+                Ok((
+                    compiler_state,
+                    FileSourceSubscription::Notify(notify_subscription),
+                ))
+            }
+            Self::External(_) | Self::Git(_) => {
                 unimplemented!(
-                    "watch-mode (subscribe) is not available for non-watchman file sources."
+                    "watch-mode (subscribe) is not available for external and git file sources."
                 )
             }
         }
@@ -187,7 +226,8 @@ impl FileSourceResult {
 }
 
 pub enum FileSourceSubscription {
-    Watchman(WatchmanFileSourceSubscription), // Oss(OssFileSourceSubscription)
+    Watchman(WatchmanFileSourceSubscription),
+    Notify(NotifyFileSourceSubscription),
 }
 
 impl FileSourceSubscription {
@@ -199,6 +239,10 @@ impl FileSourceSubscription {
                     |next_change| Ok(FileSourceSubscriptionNextChange::Watchman(next_change)),
                 )
             }
+            Self::Notify(file_source_subscription) => file_source_subscription
+                .next_change()
+                .await
+                .map(FileSourceSubscriptionNextChange::Notify),
         }
     }
 }
@@ -206,4 +250,7 @@ impl FileSourceSubscription {
 #[derive(Debug)]
 pub enum FileSourceSubscriptionNextChange {
     Watchman(WatchmanFileSourceSubscriptionNextChange),
+    /// The files changed since the previous change, from the file system
+    /// notifications of the OS.
+    Notify(WalkDirFileSourceResult),
 }
@@ -60,28 +60,39 @@ impl ExternalFileSource {
     }
 
     pub fn create_compiler_state(&self, perf_logger: &impl PerfLogger) -> Result<CompilerState> {
-        let load_saved_state_file = self.config.load_saved_state_file.as_ref().unwrap();
-        let mut compiler_state = CompilerState::deserialize_from_file(load_saved_state_file)?;
-        if std::env::var("RELAY_COMPILER_IGNORE_SAVED_STATE_VERSION").is_err()
-            && compiler_state.saved_state_version != self.config.saved_state_version
-        {
-            return Err(Error::SavedStateVersionMismatch {
-                saved_state_version: compiler_state.saved_state_version,
-                config_version: self.config.saved_state_version.clone(),
-            });
-        }
-
         let root_dir = &self.config.root_dir;
-        compiler_state
-            .pending_file_source_changes
-            .write()
-            .unwrap()
-            .push(FileSourceResult::External(
-                ExternalFileSourceResult::read_from_fs(&self.changed_files_list, root_dir.clone())?,
-            ));
-
-        compiler_state.merge_file_source_changes(&self.config, perf_logger, true)?;
+        create_compiler_state_from_saved_state(
+            &self.config,
+            || ExternalFileSourceResult::read_from_fs(&self.changed_files_list, root_dir.clone()),
+            perf_logger,
+        )
+    }
+}
 
-        Ok(compiler_state)
+/// Loads the saved state of the config, and merges the changed files into it.
+pub(crate) fn create_compiler_state_from_saved_state(
+    config: &Config,
+    changed_files: impl FnOnce() -> Result<ExternalFileSourceResult>,
+    perf_logger: &impl PerfLogger,
+) -> Result<CompilerState> {
+    let load_saved_state_file = config.load_saved_state_file.as_ref().unwrap();
+    let mut compiler_state = CompilerState::deserialize_from_file(load_saved_state_file)?;
+    if std::env::var("RELAY_COMPILER_IGNORE_SAVED_STATE_VERSION").is_err()
+        && compiler_state.saved_state_version != config.saved_state_version
+    {
+        return Err(Error::SavedStateVersionMismatch {
+            saved_state_version: compiler_state.saved_state_version,
+            config_version: config.saved_state_version.clone(),
+        });
     }
+
+    compiler_state
+        .pending_file_source_changes
+        .write()
+        .unwrap()
+        .push(FileSourceResult::External(changed_files()?));
+
+    compiler_state.merge_file_source_changes(config, perf_logger, true)?;
+
+    Ok(compiler_state)
 }
@@ -0,0 +1,284 @@
+/*
+ * Copyright (c) Meta Platforms, Inc. and affiliates.
+ *
+ * This source code is licensed under the MIT license found in the
+ * LICENSE file in the root directory of this source tree.
+ */
+
+use std::collections::HashSet;
+use std::path::PathBuf;
+use std::process::Command;
+use std::sync::Arc;
+
+use common::PerfLogger;
+use log::debug;
+
+use super::external_file_source::create_compiler_state_from_saved_state;
+use super::walk_dir_file_source::get_expected_file_extensions;
+use super::File;
+use crate::compiler_state::CompilerState;
+use crate::config::Config;
+use crate::errors::Error;
+use crate::errors::Result;
+use crate::ExternalFileSourceResult;
+
+/// Computes the files changed since a base revision with git, and merges them
+/// into the saved state, which should be the state of the base revision.
+/// The changes include the commits since the base revision, the uncommitted
+/// changes, and the untracked files.
+pub struct GitFileSource {
+    base_revision: String,
+    pub config: Arc<Config>,
+}
+
+impl GitFileSource {
+    pub fn new(base_revision: String, config: Arc<Config>) -> Self {
+        Self {
+            base_revision,
+            config,
+        }
+    }
+
+    pub fn create_compiler_state(&self, perf_logger: &impl PerfLogger) -> Result<CompilerState> {
+        create_compiler_state_from_saved_state(
+            &self.config,
+            || {
+                Ok(ExternalFileSourceResult {
+                    files: self.changed_files()?,
+                    resolved_root: self.config.root_dir.clone(),
+                })
+            },
+            perf_logger,
+        )
+    }
+
+    fn changed_files(&self) -> Result<Vec<File>> {
+        let expected_file_extensions = get_expected_file_extensions(&self.config);
+        let mut names = HashSet::new();
+        // Renames are listed as a deleted and an added file.
+        names.extend(self.run_git(&[
+            "diff",
+            "--name-only",
+            "--no-renames",
+            "--relative",
+            "-z",
+            &self.base_revision,
+            "--",
+        ])?);
+        names.extend(self.run_git(&["ls-files", "--others", "--exclude-standard", "-z"])?);
+
+        let mut files = names
+            .into_iter()
+            .filter(|name| {
+                matches!(
+                    name.extension().and_then(|extension| extension.to_str()),
+                    Some(extension) if expected_file_extensions.contains(extension)
+                )
+            })
+            .map(|name| File {
+                exists: self.config.root_dir.join(&name).exists(),
+                name,
+            })
+            .collect::<Vec<_>>();
+        files.sort_by(|a, b| a.name.cmp(&b.name));
+        debug!(
+            "{} files changed since revision {}",
+            files.len(),
+            self.base_revision
+        );
+        Ok(files)
+    }
+
+    /// Runs a git command in the root directory, and returns the paths of
+    /// its NUL-separated output.
+    fn run_git(&self, args: &[&str]) -> Result<Vec<PathBuf>> {
+        let output = Command::new("git")
+            .current_dir(&self.config.root_dir)
+            .args(args)
+            .output()
+            .map_err(|err| Error::GitChangedFilesError {
+                reason: format!("Failed to run `git {}`: {}", args.join(" "), err),
+            })?;
+        if !output.status.success() {
+            return Err(Error::GitChangedFilesError {
+                reason: format!(
+                    "`git {}` failed: {}",
+                    args.join(" "),
+                    String::from_utf8_lossy(&output.stderr).trim()
+                ),
+            });
+        }
+        Ok(String::from_utf8_lossy(&output.stdout)
+            .split('\0')
+            .filter(|name| !name.is_empty())
+            .map(PathBuf::from)
+            .collect())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::fs;
+    use std::path::Path;
+
+    use common::NoopPerfLogger;
+    use intern::string_key::Intern;
+    use tempfile::TempDir;
+
+    use super::*;
+    use crate::config::FileSourceKind;
+    use crate::FileSource;
+
+    fn git(root_dir: &Path, args: &[&str]) -> String {
+        let output = Command::new("git")
+            .current_dir(root_dir)
+            .args(["-c", "user.name=Test", "-c", "user.email=test@example.com"])
+            .args(args)
+            .output()
+            .unwrap();
+        assert!(
+            output.status.success(),
+            "`git {}` failed: {}",
+            args.join(" "),
+            String::from_utf8_lossy(&output.stderr)
+        );
+        String::from_utf8(output.stdout).unwrap().trim().to_string()
+    }
+
+    fn write_file(root_dir: &Path, name: &str, content: &str) {
+        let path = root_dir.join(name);
+        fs::create_dir_all(path.parent().unwrap()).unwrap();
+        fs::write(path, content).unwrap();
+    }
+
+    /// Creates a git repository with a commit of the files, and returns the
+    /// hash of the commit.
+    fn create_repository(files: &[&str]) -> (TempDir, String) {
+        let dir = tempfile::tempdir().unwrap();
+        git(dir.path(), &["init", "-q"]);
+        for name in files {
+            write_file(dir.path(), name, "// original");
+        }
+        git(dir.path(), &["add", "-A"]);
+        git(dir.path(), &["commit", "-q", "-m", "base"]);
+        let revision = git(dir.path(), &["rev-parse", "HEAD"]);
+        (dir, revision)
+    }
+
+    fn create_config(root_dir: &Path, base_revision: &str) -> Arc<Config> {
+        let mut config = Config::from_string_for_test(
+            r#"
+                {
+                    "sources": {
+                        "src": "project"
+                    },
+                    "projects": {
+                        "project": {
+                            "schema": "schema.graphql",
+                            "language": "javascript"
+                        }
+                    }
+                }
+            "#,
+        )
+        .unwrap();
+        config.root_dir = root_dir.to_path_buf();
+        config.file_source_config = FileSourceKind::Git(base_revision.to_string());
+        Arc::new(config)
+    }
+
+    fn get_changed_files(root_dir: &Path, base_revision: &str) -> Vec<(String, bool)> {
+        GitFileSource::new(
+            base_revision.to_string(),
+            create_config(root_dir, base_revision),
+        )
+        .changed_files()
+        .unwrap()
+        .into_iter()
+        .map(|file| (file.name.to_str().unwrap().to_string(), file.exists))
+        .collect()
+    }
+
+    #[test]
+    fn test_changed_files_since_revision() {
+        let (dir, revision) = create_repository(&[
+            "src/Modified.js",
+            "src/Deleted.js",
+            "src/Uncommitted.js",
+            "src/Unchanged.js",
+            "README.md",
+        ]);
+        let root_dir = dir.path();
+        write_file(root_dir, "src/Modified.js", "// modified");
+        write_file(root_dir, "src/Added.js", "// added");
+        write_file(root_dir, "README.md", "modified");
+        fs::remove_file(root_dir.join("src/Deleted.js")).unwrap();
+        git(root_dir, &["add", "-A"]);
+        git(root_dir, &["commit", "-q", "-m", "changes"]);
+        write_file(root_dir, "src/Uncommitted.js", "// uncommitted");
+
+        assert_eq!(
+            get_changed_files(root_dir, &revision),
+            vec![
+                ("src/Added.js".to_string(), true),
+                ("src/Deleted.js".to_string(), false),
+                ("src/Modified.js".to_string(), true),
+                ("src/Uncommitted.js".to_string(), true),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_untracked_files() {
+        let (dir, revision) = create_repository(&["src/Tracked.js", ".gitignore"]);
+        let root_dir = dir.path();
+        write_file(root_dir, ".gitignore", "src/Ignored.js\n");
+        git(root_dir, &["commit", "-q", "-am", "ignore"]);
+        write_file(root_dir, "src/Untracked.js", "// untracked");
+        write_file(root_dir, "src/Ignored.js", "// ignored");
+
+        assert_eq!(
+            get_changed_files(root_dir, &revision),
+            vec![("src/Untracked.js".to_string(), true)]
+        );
+    }
+
+    #[tokio::test]
+    async fn test_compiles_all_files_without_saved_state() {
+        let (dir, revision) = create_repository(&["src/Unchanged.js"]);
+        let root_dir = dir.path();
+        write_file(
+            root_dir,
+            "src/Unchanged.js",
+            "graphql`query UnchangedQuery { me { id } }`",
+        );
+        git(root_dir, &["commit", "-q", "-am", "query"]);
+        let revision_with_query = git(root_dir, &["rev-parse", "HEAD"]);
+        assert_ne!(revision, revision_with_query);
+
+        let config = create_config(root_dir, &revision_with_query);
+        let perf_logger = NoopPerfLogger;
+        let perf_logger_event = perf_logger.create_event("test");
+        let file_source = FileSource::connect(&config, &perf_logger_event)
+            .await
+            .unwrap();
+        let compiler_state = file_source
+            .query(&perf_logger_event, &perf_logger)
+            .await
+            .unwrap();
+
+        // No file changed since the revision, but all files are compiled
+        let sources = compiler_state
+            .graphql_sources
+            .get(&"project".intern().into())
+            .unwrap()
+            .get_all();
+        assert_eq!(
+            sources
+                .into_iter()
+                .map(|(name, _)| name.to_str().unwrap())
+                .collect::<Vec<_>>(),
+            vec!["src/Unchanged.js"]
+        );
+    }
+}
@@ -0,0 +1,377 @@
+/*
+ * Copyright (c) Meta Platforms, Inc. and affiliates.
+ *
+ * This source code is licensed under the MIT license found in the
+ * LICENSE file in the root directory of this source tree.
+ */
+
+use std::collections::BTreeSet;
+use std::collections::HashSet;
+use std::path::Path;
+use std::path::PathBuf;
+use std::sync::Arc;
+
+use log::debug;
+use notify::Event;
+use notify::EventKind;
+use notify::RecommendedWatcher;
+use notify::RecursiveMode;
+use notify::Watcher;
+use tokio::sync::mpsc;
+use tokio::sync::mpsc::UnboundedReceiver;
+use walkdir::WalkDir;
+
+use super::walk_dir_file_source::get_expected_file_extensions;
+use super::watchman_query_builder::get_all_roots;
+use super::File;
+use super::WalkDirFileSourceResult;
+use crate::config::Config;
+use crate::errors::Error;
+use crate::errors::Result;
+
+/// A subscription to the changes of the files in the roots of the config,
+/// with the native file system notifications of the OS. Used in watch mode
+/// when watchman is not available.
+pub struct NotifyFileSourceSubscription {
+    config: Arc<Config>,
+    canonical_root_dir: PathBuf,
+    expected_file_extensions: HashSet<&'static str>,
+    /// The files in the roots, to report the files of a directory that is
+    /// removed or moved away as deleted.
+    tracked_files: BTreeSet<PathBuf>,
+    receiver: UnboundedReceiver<notify::Result<Event>>,
+    // Files are watched until the watcher is dropped.
+    _watcher: RecommendedWatcher,
+}
+
+impl NotifyFileSourceSubscription {
+    pub fn new(config: Arc<Config>) -> Result<Self> {
+        let (sender, receiver) = mpsc::unbounded_channel();
+        let mut watcher = notify::recommended_watcher(move |event| {
+            // The receiver is only dropped with the watcher.
+            let _ = sender.send(event);
+        })
+        .map_err(|source| Error::FileWatcher { source })?;
+        for root in get_all_roots(&config) {
+            let root = config.root_dir.join(root);
+            // Files, like the schema files, are often replaced instead of
+            // modified, so their directory is watched instead.
+            let (path, recursive_mode) = if root.is_dir() {
+                (root.as_path(), RecursiveMode::Recursive)
+            } else if let Some(parent) = root.parent().filter(|_| root.is_file()) {
+                (parent, RecursiveMode::NonRecursive)
+            } else {
+                debug!("Not watching {:?}, it does not exist.", root);
+                continue;
+            };
+            watcher
+                .watch(path, recursive_mode)
+                .map_err(|source| Error::FileWatcher { source })?;
+        }
+        let canonical_root_dir = config
+            .root_dir
+            .canonicalize()
+            .unwrap_or_else(|_| config.root_dir.clone());
+        let expected_file_extensions = get_expected_file_extensions(&config);
+        let mut subscription = Self {
+            config,
+            canonical_root_dir,
+            expected_file_extensions,
+            tracked_files: Default::default(),
+            receiver,
+            _watcher: watcher,
+        };
+        subscription.tracked_files = subscription.find_files();
+        Ok(subscription)
+    }
+
+    /// Waits for the next changes of relevant files. The events received in
+    /// the meantime are merged into a single result.
+    pub async fn next_change(&mut self) -> Result<WalkDirFileSourceResult> {
+        loop {
+            let event = self
+                .receiver
+                .recv()
+                .await
+                .ok_or(Error::FileWatcherStopped)?;
+            let mut names = BTreeSet::new();
+            let mut need_rescan = self.add_changed_files(event, &mut names)?;
+            while let Ok(event) = self.receiver.try_recv() {
+                need_rescan |= self.add_changed_files(event, &mut names)?;
+            }
+            if need_rescan {
+                return Ok(self.rescan());
+            }
+            if !names.is_empty() {
+                let files = names
+                    .into_iter()
+                    .map(|name| File {
+                        exists: self.config.root_dir.join(&name).is_file(),
+                        name,
+                    })
+                    .collect::<Vec<_>>();
+                for file in &files {
+                    if file.exists {
+                        self.tracked_files.insert(file.name.clone());
+                    } else {
+                        self.tracked_files.remove(&file.name);
+                    }
+                }
+                return Ok(WalkDirFileSourceResult {
+                    files,
+                    resolved_root: self.config.root_dir.clone(),
+                });
+            }
+        }
+    }
+
+    /// Adds the files changed by the event, and returns whether some events
+    /// were missed and the roots have to be rescanned.
+    fn add_changed_files(
+        &self,
+        event: notify::Result<Event>,
+        names: &mut BTreeSet<PathBuf>,
+    ) -> Result<bool> {
+        let event = event.map_err(|source| Error::FileWatcher { source })?;
+        if event.need_rescan() {
+            return Ok(true);
+        }
+        if matches!(event.kind, EventKind::Access(_)) {
+            return Ok(false);
+        }
+        for path in event.paths {
+            // Only the directory is reported when a directory is removed or
+            // moved away, so its tracked files are reported as deleted.
+            if !path.exists() {
+                if let Some(name) = self.get_name(&path) {
+                    names.extend(
+                        self.tracked_files
+                            .iter()
+                            .filter(|tracked_file| tracked_file.starts_with(name))
+                            .cloned(),
+                    );
+                }
+            }
+            self.add_files(&path, names);
+        }
+        Ok(false)
+    }
+
+    /// Finds all the files of the roots again, as the changes since the
+    /// previous result are unknown. The tracked files that are not found
+    /// anymore are reported as deleted.
+    fn rescan(&mut self) -> WalkDirFileSourceResult {
+        debug!("File watcher events were missed, rescanning the roots.");
+        let tracked_files = self.find_files();
+        let previous_tracked_files = std::mem::replace(&mut self.tracked_files, tracked_files);
+        let files = previous_tracked_files
+            .difference(&self.tracked_files)
+            .map(|name| File {
+                name: name.clone(),
+                exists: false,
+            })
+            .chain(self.tracked_files.iter().map(|name| File {
+                name: name.clone(),
+                exists: true,
+            }))
+            .collect();
+        WalkDirFileSourceResult {
+            files,
+            resolved_root: self.config.root_dir.clone(),
+        }
+    }
+
+    /// Returns the relevant files in the roots.
+    fn find_files(&self) -> BTreeSet<PathBuf> {
+        let mut names = BTreeSet::new();
+        for root in get_all_roots(&self.config) {
+            self.add_files(&self.config.root_dir.join(root), &mut names);
+        }
+        names
+    }
+
+    /// Adds the relevant files at the path, or in the directory at the path.
+    fn add_files(&self, path: &Path, names: &mut BTreeSet<PathBuf>) {
+        // Changes of the files of a directory that is created or moved
+        // are not always reported.
+        if path.is_dir() {
+            for entry in WalkDir::new(path)
+                .into_iter()
+                .filter_map(|entry| entry.ok())
+            {
+                self.add_file(entry.path(), names);
+            }
+        } else {
+            self.add_file(path, names);
+        }
+    }
+
+    fn add_file(&self, path: &Path, names: &mut BTreeSet<PathBuf>) {
+        let name = match self.get_name(path) {
+            Some(name) => name,
+            None => return,
+        };
+        if matches!(
+            name.extension().and_then(|extension| extension.to_str()),
+            Some(extension) if self.expected_file_extensions.contains(extension)
+        ) {
+            names.insert(name.to_path_buf());
+        }
+    }
+
+    /// Returns the path relative to the root directory.
+    fn get_name<'path>(&self, path: &'path Path) -> Option<&'path Path> {
+        path.strip_prefix(&self.config.root_dir)
+            .or_else(|_| path.strip_prefix(&self.canonical_root_dir))
+            .ok()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::fs;
+    use std::time::Duration;
+
+    use tempfile::TempDir;
+
+    use super::*;
+
+    fn write_file(root_dir: &Path, name: &str) {
+        let path = root_dir.join(name);
+        fs::create_dir_all(path.parent().unwrap()).unwrap();
+        fs::write(path, "// file").unwrap();
+    }
+
+    /// Creates the files in a temporary directory, and subscribes to the
+    /// changes of the `src` directory.
+    fn subscribe(files: &[&str]) -> (TempDir, NotifyFileSourceSubscription) {
+        let dir = tempfile::tempdir().unwrap();
+        fs::create_dir(dir.path().join("src")).unwrap();
+        for name in files {
+            write_file(dir.path(), name);
+        }
+        let mut config = Config::from_string_for_test(
+            r#"
+                {
+                    "sources": {
+                        "src": "project"
+                    },
+                    "projects": {
+                        "project": {
+                            "schema": "schema.graphql",
+                            "language": "javascript"
+                        }
+                    }
+                }
+            "#,
+        )
+        .unwrap();
+        config.root_dir = dir.path().to_path_buf();
+        let subscription = NotifyFileSourceSubscription::new(Arc::new(config)).unwrap();
+        (dir, subscription)
+    }
+
+    /// Returns the changed files, and whether they exist, until `count` files
+    /// are reported.
+    async fn next_changes(
+        subscription: &mut NotifyFileSourceSubscription,
+        count: usize,
+    ) -> Vec<(String, bool)> {
+        let mut files = BTreeSet::new();
+        while files.len() < count {
+            let result = tokio::time::timeout(Duration::from_secs(10), subscription.next_change())
+                .await
+                .expect("Expected a file change")
+                .unwrap();
+            files.extend(
+                result
+                    .files
+                    .into_iter()
+                    .map(|file| (file.name.to_str().unwrap().to_string(), file.exists)),
+            );
+        }
+        files.into_iter().collect()
+    }
+
+    #[tokio::test]
+    async fn test_created_file() {
+        let (dir, mut subscription) = subscribe(&[]);
+        write_file(dir.path(), "src/Created.js");
+        assert_eq!(
+            next_changes(&mut subscription, 1).await,
+            vec![("src/Created.js".to_string(), true)]
+        );
+    }
+
+    #[tokio::test]
+    async fn test_modified_file() {
+        let (dir, mut subscription) = subscribe(&["src/Modified.js"]);
+        fs::write(dir.path().join("src/Modified.js"), "// modified").unwrap();
+        assert_eq!(
+            next_changes(&mut subscription, 1).await,
+            vec![("src/Modified.js".to_string(), true)]
+        );
+    }
+
+    #[tokio::test]
+    async fn test_deleted_file() {
+        let (dir, mut subscription) = subscribe(&["src/Deleted.js"]);
+        fs::remove_file(dir.path().join("src/Deleted.js")).unwrap();
+        assert_eq!(
+            next_changes(&mut subscription, 1).await,
+            vec![("src/Deleted.js".to_string(), false)]
+        );
+    }
+
+    #[tokio::test]
+    async fn test_rescan() {
+        let (dir, mut subscription) = subscribe(&["src/Deleted.js", "src/Unchanged.js"]);
+        fs::remove_file(dir.path().join("src/Deleted.js")).unwrap();
+        write_file(dir.path(), "src/Created.js");
+        let files = subscription
+            .rescan()
+            .files
+            .into_iter()
+            .map(|file| (file.name.to_str().unwrap().to_string(), file.exists))
+            .collect::<BTreeSet<_>>();
+        assert_eq!(
+            files.into_iter().collect::<Vec<_>>(),
+            vec![
+                ("src/Created.js".to_string(), true),
+                ("src/Deleted.js".to_string(), false),
+                ("src/Unchanged.js".to_string(), true),
+            ]
+        );
+    }
+
+    #[tokio::test]
+    async fn test_moved_directory() {
+        let (dir, mut subscription) = subscribe(&[
+            "src/moved/First.js",
+            "src/moved/nested/Second.js",
+            "src/Unchanged.js",
+        ]);
+        let other_dir = tempfile::tempdir().unwrap();
+        fs::rename(dir.path().join("src/moved"), other_dir.path().join("moved")).unwrap();
+        assert_eq!(
+            next_changes(&mut subscription, 2).await,
+            vec![
+                ("src/moved/First.js".to_string(), false),
+                ("src/moved/nested/Second.js".to_string(), false),
+            ]
+        );
+
+        fs::rename(
+            other_dir.path().join("moved"),
+            dir.path().join("src/renamed"),
+        )
+        .unwrap();
+        assert_eq!(
+            next_changes(&mut subscription, 2).await,
+            vec![
+                ("src/renamed/First.js".to_string(), true),
+                ("src/renamed/nested/Second.js".to_string(), true),
+            ]
+        );
+    }
+}
@@ -22,6 +22,8 @@ use crate::config::Config;
 use crate::errors::Result;
 use crate::FileSourceResult;
 
+/// The files found by walking the roots, or the files changed since the
+/// previous result in watch mode.
 #[derive(Debug)]
 pub struct WalkDirFileSourceResult {
     pub files: Vec<File>,
@@ -33,7 +35,7 @@ pub struct WalkDirFileSource {
     expected_file_extensions: HashSet<&'static str>,
 }
 
-fn get_expected_file_extensions(config: &Config) -> HashSet<&'static str> {
+pub(super) fn get_expected_file_extensions(config: &Config) -> HashSet<&'static str> {
     let mut file_extensions = HashSet::<&str>::with_capacity(6);
     file_extensions.insert("graphql");
     file_extensions.insert("gql");
@@ -0,0 +1,111 @@
+/*
+ * Copyright (c) Meta Platforms, Inc. and affiliates.
+ *
+ * This source code is licensed under the MIT license found in the
+ * LICENSE file in the root directory of this source tree.
+ */
+
+use std::ops::Range;
+
+use common::SourceLocationKey;
+use extract_graphql::JavaScriptSourceFeature;
+use graphql_syntax::GraphQLSource;
+use graphql_syntax::ParserFeatures;
+use graphql_text_printer::format_executable_document;
+
+const INDENT: &str = "  ";
+
+/// A `graphql` tag of a JavaScript file, or a whole GraphQL document, whose
+/// text is not formatted.
+#[derive(Debug)]
+pub struct FormattedGraphQLTag {
+    pub graphql_source: GraphQLSource,
+    /// The byte range of the text of the tag, between the backticks, or of the
+    /// whole document.
+    pub range: Range<usize>,
+    pub formatted_text: String,
+}
+
+/// Formats the `graphql` tags of a JavaScript file, and returns the ones that
+/// changed. The document of a tag starts on a new line, indented one level
+/// deeper than the line of the tag, and the closing backtick is on its own
+/// line. Tags with syntax errors are left as they are.
+pub fn format_graphql_tags(text: &str, features: ParserFeatures) -> Vec<FormattedGraphQLTag> {
+    let mut formatted_tags = vec![];
+    for feature in extract_graphql::extract(text) {
+        let JavaScriptSourceFeature::GraphQL(graphql_source) = feature else {
+            continue;
+        };
+        let text_source = graphql_source.text_source();
+        let Some(start) = get_byte_offset(text, text_source.line_index, text_source.column_index)
+        else {
+            continue;
+        };
+        let range = start..start + text_source.text.len();
+        let Ok(formatted_document) =
+            format_executable_document(&text_source.text, SourceLocationKey::generated(), features)
+        else {
+            continue;
+        };
+
+        let line_start = text[..start]
+            .rfind(['\n', '\r'])
+            .map_or(0, |index| index + 1);
+        let line = &text[line_start..start];
+        let base_indent = &line[..line.len() - line.trim_start().len()];
+        let mut formatted_text = String::from("\n");
+        for line in formatted_document.lines() {
+            if !line.is_empty() {
+                formatted_text.push_str(base_indent);
+                formatted_text.push_str(INDENT);
+                formatted_text.push_str(line);
+            }
+            formatted_text.push('\n');
+        }
+        formatted_text.push_str(base_indent);
+
+        if formatted_text != text_source.text {
+            formatted_tags.push(FormattedGraphQLTag {
+                graphql_source,
+                range,
+                formatted_text,
+            });
+        }
+    }
+    formatted_tags
+}
+
+/// Replaces the text of the tags with their formatted text.
+pub fn apply_formatted_graphql_tags(text: &str, formatted_tags: &[FormattedGraphQLTag]) -> String {
+    let mut result = String::with_capacity(text.len());
+    let mut last_end = 0;
+    for formatted_tag in formatted_tags {
+        result.push_str(&text[last_end..formatted_tag.range.start]);
+        result.push_str(&formatted_tag.formatted_text);
+        last_end = formatted_tag.range.end;
+    }
+    result.push_str(&text[last_end..]);
+    result
+}
+
+/// Returns the byte offset of a position, with the lines and columns counted
+/// like `extract_graphql::CharReader` does.
+fn get_byte_offset(text: &str, line_index: usize, column_index: usize) -> Option<usize> {
+    let mut line = 0;
+    let mut column = 0;
+    let mut chars = text.char_indices().peekable();
+    while let Some((index, ch)) = chars.next() {
+        if line == line_index && column == column_index {
+            return Some(index);
+        }
+        match ch {
+            '\r' if matches!(chars.peek(), Some((_, '\n'))) => {}
+            '\n' | '\r' | '\u{2028}' | '\u{2029}' => {
+                line += 1;
+                column = 0;
+            }
+            _ => column += 1,
+        }
+    }
+    (line == line_index && column == column_index).then_some(text.len())
+}
@@ -18,12 +18,14 @@ pub mod config;
 mod docblocks;
 pub mod errors;
 pub mod file_source;
+mod format_graphql_tags;
 mod graphql_asts;
 mod operation_persister;
 mod red_to_green;
 pub mod status_reporter;
 mod utils;
 
+pub use artifact_map::ArtifactRecord;
 pub use artifact_map::ArtifactSourceKey;
 pub use build_project::artifact_writer::ArtifactDifferenceShardedWriter;
 pub use build_project::artifact_writer::ArtifactDifferenceWriter;
@@ -48,6 +50,7 @@ pub use build_project::ArtifactContent;
 pub use build_project::ArtifactGeneratedTypes;
 pub use build_project::BuildProjectFailure;
 pub use build_project::SourceHashes;
+pub use config::ApqPersistConfig;
 pub use config::ConfigFile;
 pub use config::ConfigFileProject;
 pub use config::FileSourceKind;
@@ -55,7 +58,9 @@ pub use config::LocalPersistConfig;
 pub use config::OperationPersister;
 pub use config::PersistConfig;
 pub use config::ProjectConfig;
+pub use config::RemotePersistBatchConfig;
 pub use config::RemotePersistConfig;
+pub use config::RequestEncoding;
 pub use config::SchemaLocation;
 pub use file_source::source_for_location;
 pub use file_source::ExternalFileSourceResult;
@@ -67,9 +72,15 @@ pub use file_source::FileSourceResult;
 pub use file_source::FileSourceSubscription;
 pub use file_source::FileSourceSubscriptionNextChange;
 pub use file_source::FsSourceReader;
+pub use file_source::NotifyFileSourceSubscription;
 pub use file_source::SourceControlUpdateStatus;
 pub use file_source::SourceReader;
+pub use file_source::WalkDirFileSourceResult;
+pub use format_graphql_tags::apply_formatted_graphql_tags;
+pub use format_graphql_tags::format_graphql_tags;
+pub use format_graphql_tags::FormattedGraphQLTag;
 pub use graphql_asts::GraphQLAsts;
+pub use operation_persister::ApqPersister;
 pub use operation_persister::LocalPersister;
 pub use operation_persister::RemotePersister;
 pub use relay_config::ProjectName;
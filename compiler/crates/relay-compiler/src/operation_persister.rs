@@ -5,8 +5,10 @@
  * LICENSE file in the root directory of this source tree.
  */
 
+mod apq_persister;
 mod local_persister;
 mod remote_persister;
 
+pub use apq_persister::ApqPersister;
 pub use local_persister::LocalPersister;
 pub use remote_persister::RemotePersister;
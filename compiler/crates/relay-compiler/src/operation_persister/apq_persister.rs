@@ -0,0 +1,31 @@
+/*
+ * Copyright (c) Meta Platforms, Inc. and affiliates.
+ *
+ * This source code is licensed under the MIT license found in the
+ * LICENSE file in the root directory of this source tree.
+ */
+
+use async_trait::async_trait;
+use persist_query::PersistError;
+use sha2::Digest;
+use sha2::Sha256;
+
+use crate::config::ArtifactForPersister;
+use crate::OperationPersister;
+
+/// Computes Automatic Persisted Queries (APQ) ids: the hex-encoded SHA-256
+/// hash of the operation text. Nothing is sent to the server at build time,
+/// operations are registered by the client on the first cache miss.
+pub struct ApqPersister;
+
+#[async_trait]
+impl OperationPersister for ApqPersister {
+    async fn persist_artifact(
+        &self,
+        artifact: ArtifactForPersister,
+    ) -> Result<String, PersistError> {
+        let mut hash = Sha256::new();
+        hash.update(&artifact.text);
+        Ok(hex::encode(hash.finalize()))
+    }
+}
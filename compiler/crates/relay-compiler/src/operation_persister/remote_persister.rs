@@ -5,12 +5,22 @@
  * LICENSE file in the root directory of this source tree.
  */
 
+use std::future::Future;
+use std::sync::Arc;
+use std::time::Duration;
+
 use async_trait::async_trait;
-use persist_query::persist;
+use persist_query::persist_batch;
+use persist_query::persist_with_format;
 use persist_query::PersistError;
+use persist_query::PersistRequestFormat;
+use persist_query::RequestEncoding;
+use relay_config::RemotePersistBatchConfig;
 use relay_config::RemotePersistConfig;
+use relay_config::RequestEncoding as RequestEncodingConfig;
 use tokio::sync::Semaphore;
 
+use crate::config::persist_each_artifact;
 use crate::config::ArtifactForPersister;
 use crate::OperationPersister;
 
@@ -24,6 +34,89 @@ impl RemotePersister {
         let semaphore = config.semaphore_permits.map(Semaphore::new);
         Self { config, semaphore }
     }
+
+    /// Sends a request, limited by the concurrency of the config, and retries
+    /// it with an exponential backoff if it fails with a retryable error.
+    async fn send<T, F, Fut>(&self, request: F) -> Result<T, PersistError>
+    where
+        F: Fn() -> Fut,
+        Fut: Future<Output = Result<T, PersistError>>,
+    {
+        let mut delay = Duration::from_millis(self.config.retry_delay_ms);
+        let mut retries = 0;
+        loop {
+            let permit = match &self.semaphore {
+                Some(semaphore) => Some(semaphore.acquire().await.unwrap()),
+                None => None,
+            };
+            let result = match self.config.timeout_ms {
+                Some(timeout_ms) => {
+                    tokio::time::timeout(Duration::from_millis(timeout_ms), request())
+                        .await
+                        .unwrap_or(Err(PersistError::Timeout { timeout_ms }))
+                }
+                None => request().await,
+            };
+            drop(permit);
+            match result {
+                Err(error) if error.is_retryable() && retries < self.config.retries => {
+                    retries += 1;
+                    tokio::time::sleep(delay).await;
+                    delay *= 2;
+                }
+                result => return result,
+            }
+        }
+    }
+
+    async fn persist_batch(
+        &self,
+        batch_config: &RemotePersistBatchConfig,
+        artifacts: &[ArtifactForPersister],
+    ) -> Vec<Result<String, PersistError>> {
+        let url = batch_config.url.as_ref().unwrap_or(&self.config.url);
+        let documents = artifacts
+            .iter()
+            .map(|artifact| (&artifact.operation_name, &artifact.text))
+            .collect::<Vec<_>>();
+        let result = self
+            .send(|| {
+                persist_batch(
+                    documents.iter().copied(),
+                    url,
+                    &self.config.params,
+                    &self.config.headers,
+                )
+            })
+            .await;
+        match result {
+            Ok(mut ids) => artifacts
+                .iter()
+                .map(|artifact| {
+                    ids.remove(&artifact.operation_name).ok_or_else(|| {
+                        PersistError::ErrorResponse {
+                            message: format!(
+                                "The batch response contains no id for operation `{}`.",
+                                artifact.operation_name
+                            ),
+                        }
+                    })
+                })
+                .collect(),
+            Err(error) => {
+                let error = Arc::new(error);
+                artifacts
+                    .iter()
+                    .map(|artifact| {
+                        Err(PersistError::BatchError {
+                            operation_name: artifact.operation_name.clone(),
+                            source: Arc::clone(&error),
+                        })
+                    })
+                    .collect()
+            }
+        }
+    }
 }
 
 #[async_trait]
@@ -36,13 +129,33 @@ impl OperationPersister for RemotePersister {
         let headers = &self.config.headers;
 
         let url = &self.config.url;
-        if let Some(semaphore) = &self.semaphore {
-            let permit = (*semaphore).acquire().await.unwrap();
-            let result = persist(&artifact.text, url, params, headers).await;
-            drop(permit);
-            result
-        } else {
-            persist(&artifact.text, url, params, headers).await
+        let format = PersistRequestFormat {
+            encoding: match self.config.request_encoding {
+                RequestEncodingConfig::Form => RequestEncoding::Form,
+                RequestEncodingConfig::Json => RequestEncoding::Json,
+            },
+            document_field: &self.config.document_field,
+            response_id_path: &self.config.response_id_path,
+        };
+        self.send(|| persist_with_format(&artifact.text, url, params, headers, format))
+            .await
+    }
+
+    async fn persist_artifacts(
+        &self,
+        artifacts: Vec<ArtifactForPersister>,
+    ) -> Vec<Result<String, PersistError>> {
+        match &self.config.batch {
+            Some(batch_config) => futures::future::join_all(
+                artifacts
+                    .chunks(batch_config.size)
+                    .map(|batch| self.persist_batch(batch_config, batch)),
+            )
+            .await
+            .into_iter()
+            .flatten()
+            .collect(),
+            None => persist_each_artifact(self, artifacts).await,
         }
     }
 }
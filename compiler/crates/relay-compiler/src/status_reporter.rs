@@ -5,14 +5,22 @@
  * LICENSE file in the root directory of this source tree.
  */
 
+use std::collections::BTreeMap;
 use std::path::PathBuf;
 
 use common::Diagnostic;
 use common::DiagnosticSeverity;
+use common::DiagnosticTag;
+use common::Location;
+use common::TextSource;
 use graphql_cli::DiagnosticPrinter;
 use log::error;
 use log::info;
 use log::warn;
+use relay_config::ProjectName;
+use serde::Serialize;
+use serde_json::json;
+use serde_json::Value;
 
 use crate::errors::BuildProjectError;
 use crate::errors::Error;
@@ -171,3 +179,514 @@ impl StatusReporter for JSONStatusReporter {
         );
     }
 }
+
+/// Prints a JSON object per build on stdout, with a record for every
+/// diagnostic, so that CI systems can annotate sources without parsing log
+/// lines. Failed builds also have the serialized error. Like the console,
+/// builds cancelled by new changes are not reported.
+pub struct DiagnosticRecordsStatusReporter {
+    records: DiagnosticRecords,
+}
+
+impl DiagnosticRecordsStatusReporter {
+    pub fn new(root_dir: PathBuf) -> Self {
+        Self {
+            records: DiagnosticRecords::new(root_dir),
+        }
+    }
+
+    fn completed_report(&self, diagnostics: &[Diagnostic]) -> Value {
+        json!({
+            "completed": true,
+            "diagnostics": self.records.diagnostic_records(diagnostics),
+        })
+    }
+
+    fn error_report(&self, error: &Error) -> Value {
+        json!({
+            "completed": false,
+            "error": error,
+            "diagnostics": self.records.error_records(error),
+        })
+    }
+}
+
+impl StatusReporter for DiagnosticRecordsStatusReporter {
+    fn build_starts(&self) {}
+
+    fn build_completes(&self, diagnostics: &[Diagnostic]) {
+        println!("{}", self.completed_report(diagnostics));
+    }
+
+    fn build_errors(&self, error: &Error) {
+        if matches!(error, Error::Cancelled) {
+            return;
+        }
+        println!("{}", self.error_report(error));
+    }
+}
+
+/// Prints a SARIF 2.1.0 log per build on stdout, consumable by code scanning
+/// tools. Builds cancelled by new changes are not reported.
+pub struct SarifStatusReporter {
+    records: DiagnosticRecords,
+}
+
+impl SarifStatusReporter {
+    pub fn new(root_dir: PathBuf) -> Self {
+        Self {
+            records: DiagnosticRecords::new(root_dir),
+        }
+    }
+}
+
+impl StatusReporter for SarifStatusReporter {
+    fn build_starts(&self) {}
+
+    fn build_completes(&self, diagnostics: &[Diagnostic]) {
+        println!(
+            "{}",
+            sarif_log(&self.records.diagnostic_records(diagnostics))
+        );
+    }
+
+    fn build_errors(&self, error: &Error) {
+        if matches!(error, Error::Cancelled) {
+            return;
+        }
+        println!("{}", sarif_log(&self.records.error_records(error)));
+    }
+}
+
+/// Converts diagnostics to structured records, with their locations resolved
+/// to 1-based line and column ranges.
+struct DiagnosticRecords {
+    source_reader: Box<dyn SourceReader + Send + Sync>,
+    root_dir: PathBuf,
+}
+
+impl DiagnosticRecords {
+    fn new(root_dir: PathBuf) -> Self {
+        Self {
+            root_dir,
+            source_reader: Box::new(FsSourceReader),
+        }
+    }
+
+    fn diagnostic_records(&self, diagnostics: &[Diagnostic]) -> Vec<DiagnosticRecord> {
+        diagnostics
+            .iter()
+            .map(|diagnostic| self.diagnostic_record(diagnostic, None))
+            .collect()
+    }
+
+    fn error_records(&self, error: &Error) -> Vec<DiagnosticRecord> {
+        match error {
+            Error::DiagnosticsError { errors } => self.diagnostic_records(errors),
+            Error::BuildProjectsErrors { errors } => errors
+                .iter()
+                .flat_map(|error| self.project_error_records(error))
+                .collect(),
+            Error::Cancelled => vec![],
+            error => vec![DiagnosticRecord::from_message(error.to_string(), None)],
+        }
+    }
+
+    fn project_error_records(&self, error: &BuildProjectError) -> Vec<DiagnosticRecord> {
+        match error {
+            BuildProjectError::ValidationErrors {
+                errors,
+                project_name,
+            } => errors
+                .iter()
+                .map(|diagnostic| self.diagnostic_record(diagnostic, Some(*project_name)))
+                .collect(),
+            BuildProjectError::PersistErrors {
+                errors,
+                project_name,
+            } => errors
+                .iter()
+                .map(|error| DiagnosticRecord::from_message(error.to_string(), Some(*project_name)))
+                .collect(),
+            error => vec![DiagnosticRecord::from_message(error.to_string(), None)],
+        }
+    }
+
+    fn diagnostic_record(
+        &self,
+        diagnostic: &Diagnostic,
+        project_name: Option<ProjectName>,
+    ) -> DiagnosticRecord {
+        DiagnosticRecord {
+            project: project_name.map(|project_name| project_name.to_string()),
+            severity: severity_name(diagnostic.severity()),
+            message: diagnostic.message().to_string(),
+            location: self.location_record(diagnostic.location()),
+            related_information: diagnostic
+                .related_information()
+                .iter()
+                .map(|related| RelatedInformationRecord {
+                    message: related.message.to_string(),
+                    location: self.location_record(related.location),
+                })
+                .collect(),
+            tags: diagnostic.tags().into_iter().filter_map(tag_name).collect(),
+            machine_readable: diagnostic.machine_readable().unwrap_or_default(),
+        }
+    }
+
+    fn location_record(&self, location: Location) -> Option<LocationRecord> {
+        let source_location = location.source_location();
+        if source_location.is_generated() {
+            return None;
+        }
+        let range =
+            source_for_location(&self.root_dir, source_location, self.source_reader.as_ref())
+                .map(|source| RangeRecord::new(source.text_source(), location));
+        Some(LocationRecord {
+            path: source_location.path().to_string(),
+            range,
+        })
+    }
+}
+
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+struct DiagnosticRecord {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    project: Option<String>,
+    severity: &'static str,
+    message: String,
+    location: Option<LocationRecord>,
+    related_information: Vec<RelatedInformationRecord>,
+    tags: Vec<&'static str>,
+    machine_readable: BTreeMap<String, String>,
+}
+
+impl DiagnosticRecord {
+    fn from_message(message: String, project_name: Option<ProjectName>) -> Self {
+        Self {
+            project: project_name.map(|project_name| project_name.to_string()),
+            severity: severity_name(DiagnosticSeverity::ERROR),
+            message,
+            location: None,
+            related_information: Vec::new(),
+            tags: Vec::new(),
+            machine_readable: BTreeMap::new(),
+        }
+    }
+}
+
+#[derive(Debug, Serialize)]
+struct RelatedInformationRecord {
+    message: String,
+    location: Option<LocationRecord>,
+}
+
+#[derive(Debug, Serialize)]
+struct LocationRecord {
+    /// Path relative to the root directory of the config.
+    path: String,
+    /// `None` if the source file could not be read.
+    range: Option<RangeRecord>,
+}
+
+/// A 1-based line and column range, the end column is exclusive.
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+struct RangeRecord {
+    start_line: u32,
+    start_column: u32,
+    end_line: u32,
+    end_column: u32,
+}
+
+impl RangeRecord {
+    fn new(text_source: &TextSource, location: Location) -> Self {
+        let range = text_source.to_span_range(location.span());
+        Self {
+            start_line: range.start.line + 1,
+            start_column: range.start.character + 1,
+            end_line: range.end.line + 1,
+            end_column: range.end.character + 1,
+        }
+    }
+}
+
+fn severity_name(severity: DiagnosticSeverity) -> &'static str {
+    match severity {
+        DiagnosticSeverity::ERROR => "error",
+        DiagnosticSeverity::WARNING => "warning",
+        DiagnosticSeverity::INFORMATION => "information",
+        _ => "hint",
+    }
+}
+
+fn tag_name(tag: DiagnosticTag) -> Option<&'static str> {
+    match tag {
+        DiagnosticTag::UNNECESSARY => Some("unnecessary"),
+        DiagnosticTag::DEPRECATED => Some("deprecated"),
+        _ => None,
+    }
+}
+
+fn sarif_log(records: &[DiagnosticRecord]) -> Value {
+    let results = records
+        .iter()
+        .map(|record| {
+            let mut result = json!({
+                "level": match record.severity {
+                    "error" => "error",
+                    "warning" => "warning",
+                    _ => "note",
+                },
+                "message": { "text": record.message },
+                "locations": record.location.iter().map(sarif_location).collect::<Vec<_>>(),
+                "relatedLocations": record
+                    .related_information
+                    .iter()
+                    .filter_map(|related| {
+                        related.location.as_ref().map(|location| {
+                            let mut location = sarif_location(location);
+                            location["message"] = json!({ "text": related.message });
+                            location
+                        })
+                    })
+                    .collect::<Vec<_>>(),
+            });
+            let mut properties = serde_json::Map::new();
+            if let Some(project) = &record.project {
+                properties.insert("project".to_string(), json!(project));
+            }
+            if !record.tags.is_empty() {
+                properties.insert("tags".to_string(), json!(record.tags));
+            }
+            if !record.machine_readable.is_empty() {
+                properties.insert(
+                    "machineReadable".to_string(),
+                    json!(record.machine_readable),
+                );
+            }
+            if !properties.is_empty() {
+                result["properties"] = Value::Object(properties);
+            }
+            result
+        })
+        .collect::<Vec<_>>();
+
+    json!({
+        "version": "2.1.0",
+        "$schema": "https://json.schemastore.org/sarif-2.1.0.json",
+        "runs": [{
+            "tool": {
+                "driver": {
+                    "name": "relay-compiler",
+                    "informationUri": "https://relay.dev",
+                }
+            },
+            "results": results,
+        }],
+    })
+}
+
+fn sarif_location(location: &LocationRecord) -> Value {
+    let mut physical_location = json!({
+        "artifactLocation": { "uri": location.path },
+    });
+    if let Some(range) = &location.range {
+        physical_location["region"] = json!({
+            "startLine": range.start_line,
+            "startColumn": range.start_column,
+            "endLine": range.end_line,
+            "endColumn": range.end_column,
+        });
+    }
+    json!({ "physicalLocation": physical_location })
+}
+
+#[cfg(test)]
+mod tests {
+    use std::path::Path;
+
+    use common::SourceLocationKey;
+    use common::Span;
+    use graphql_ir::ValidationMessage;
+    use intern::string_key::Intern;
+
+    use super::*;
+
+    struct TestSourceReader;
+
+    impl SourceReader for TestSourceReader {
+        fn read_file_to_string(&self, _path: &Path) -> std::io::Result<String> {
+            Ok("query FooQuery {\n  me {\n    name\n  }\n}\n".to_string())
+        }
+    }
+
+    fn create_records() -> DiagnosticRecords {
+        DiagnosticRecords {
+            source_reader: Box::new(TestSourceReader),
+            root_dir: PathBuf::from("/root"),
+        }
+    }
+
+    fn create_diagnostics() -> Vec<Diagnostic> {
+        let source_location = SourceLocationKey::standalone("src/FooQuery.graphql");
+        vec![
+            Diagnostic::warning(
+                ValidationMessage::DeprecatedField {
+                    parent_name: "User".intern(),
+                    field_name: "name".intern(),
+                    deprecation_reason: None,
+                },
+                Location::new(source_location, Span::new(28, 32)),
+                vec![DiagnosticTag::DEPRECATED],
+            )
+            .annotate(
+                "Selected in this query",
+                Location::new(source_location, Span::new(6, 14)),
+            )
+            .metadata_for_machine("field", "User.name"),
+            Diagnostic::error(
+                ValidationMessage::DuplicateDefinition("FooQuery".intern()),
+                Location::generated(),
+            ),
+        ]
+    }
+
+    #[test]
+    fn diagnostic_records_report() {
+        let reporter = DiagnosticRecordsStatusReporter {
+            records: create_records(),
+        };
+        assert_eq!(
+            serde_json::to_string_pretty(&reporter.completed_report(&create_diagnostics()))
+                .unwrap(),
+            r#"{
+  "completed": true,
+  "diagnostics": [
+    {
+      "location": {
+        "path": "src/FooQuery.graphql",
+        "range": {
+          "endColumn": 9,
+          "endLine": 3,
+          "startColumn": 5,
+          "startLine": 3
+        }
+      },
+      "machineReadable": {
+        "field": "User.name"
+      },
+      "message": "The field `User.name` is deprecated.",
+      "relatedInformation": [
+        {
+          "location": {
+            "path": "src/FooQuery.graphql",
+            "range": {
+              "endColumn": 15,
+              "endLine": 1,
+              "startColumn": 7,
+              "startLine": 1
+            }
+          },
+          "message": "Selected in this query"
+        }
+      ],
+      "severity": "warning",
+      "tags": [
+        "deprecated"
+      ]
+    },
+    {
+      "location": null,
+      "machineReadable": {},
+      "message": "Duplicate definitions for 'FooQuery'",
+      "relatedInformation": [],
+      "severity": "error",
+      "tags": []
+    }
+  ]
+}"#
+        );
+    }
+
+    #[test]
+    fn sarif_report() {
+        let records = create_records().diagnostic_records(&create_diagnostics());
+        assert_eq!(
+            serde_json::to_string_pretty(&sarif_log(&records)).unwrap(),
+            r#"{
+  "$schema": "https://json.schemastore.org/sarif-2.1.0.json",
+  "runs": [
+    {
+      "results": [
+        {
+          "level": "warning",
+          "locations": [
+            {
+              "physicalLocation": {
+                "artifactLocation": {
+                  "uri": "src/FooQuery.graphql"
+                },
+                "region": {
+                  "endColumn": 9,
+                  "endLine": 3,
+                  "startColumn": 5,
+                  "startLine": 3
+                }
+              }
+            }
+          ],
+          "message": {
+            "text": "The field `User.name` is deprecated."
+          },
+          "properties": {
+            "machineReadable": {
+              "field": "User.name"
+            },
+            "tags": [
+              "deprecated"
+            ]
+          },
+          "relatedLocations": [
+            {
+              "message": {
+                "text": "Selected in this query"
+              },
+              "physicalLocation": {
+                "artifactLocation": {
+                  "uri": "src/FooQuery.graphql"
+                },
+                "region": {
+                  "endColumn": 15,
+                  "endLine": 1,
+                  "startColumn": 7,
+                  "startLine": 1
+                }
+              }
+            }
+          ]
+        },
+        {
+          "level": "error",
+          "locations": [],
+          "message": {
+            "text": "Duplicate definitions for 'FooQuery'"
+          },
+          "relatedLocations": []
+        }
+      ],
+      "tool": {
+        "driver": {
+          "informationUri": "https://relay.dev",
+          "name": "relay-compiler"
+        }
+      }
+    }
+  ],
+  "version": "2.1.0"
+}"#
+        );
+    }
+}
@@ -0,0 +1,24 @@
+/*
+ * Copyright (c) Meta Platforms, Inc. and affiliates.
+ *
+ * This source code is licensed under the MIT license found in the
+ * LICENSE file in the root directory of this source tree.
+ */
+
+use fixture_tests::Fixture;
+use graphql_syntax::ParserFeatures;
+use relay_compiler::apply_formatted_graphql_tags;
+use relay_compiler::format_graphql_tags;
+
+pub async fn transform_fixture(fixture: &Fixture<'_>) -> Result<String, String> {
+    let formatted_tags = format_graphql_tags(fixture.content, ParserFeatures::default());
+    let formatted = apply_formatted_graphql_tags(fixture.content, &formatted_tags);
+
+    let reformatted_tags = format_graphql_tags(&formatted, ParserFeatures::default());
+    assert!(
+        reformatted_tags.is_empty(),
+        "Expected the formatting to be stable"
+    );
+
+    Ok(formatted)
+}
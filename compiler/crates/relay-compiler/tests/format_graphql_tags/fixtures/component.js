@@ -0,0 +1,15 @@
+import {graphql, useFragment} from 'react-relay';
+
+function UserName(props) {
+  const data = useFragment(
+    graphql`fragment UserName_user on User { # the name
+      name, id }`,
+    props.user,
+  );
+  return data.name;
+}
+
+const query = graphql`
+query UserNameQuery($id: ID!) {
+  node(id: $id) { ...UserName_user }
+}`;
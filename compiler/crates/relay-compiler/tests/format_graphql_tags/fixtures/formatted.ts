@@ -0,0 +1,13 @@
+const fragment = graphql`
+  fragment Formatted_user on User {
+    name
+  }
+`;
+
+const invalid = graphql`
+  fragment Invalid_user on User {
+    name(
+  }
+`;
+
+const other = graphql`fragment Other_user on User { id }`;
@@ -0,0 +1,27 @@
+/*
+ * Copyright (c) Meta Platforms, Inc. and affiliates.
+ *
+ * This source code is licensed under the MIT license found in the
+ * LICENSE file in the root directory of this source tree.
+ *
+ * @generated SignedSource<<571343be60964b8ca2123286eeb60bb9>>
+ */
+
+mod format_graphql_tags;
+
+use format_graphql_tags::transform_fixture;
+use fixture_tests::test_fixture;
+
+#[tokio::test]
+async fn component() {
+    let input = include_str!("format_graphql_tags/fixtures/component.js");
+    let expected = include_str!("format_graphql_tags/fixtures/component.expected");
+    test_fixture(transform_fixture, file!(), "component.js", "format_graphql_tags/fixtures/component.expected", input, expected).await;
+}
+
+#[tokio::test]
+async fn formatted() {
+    let input = include_str!("format_graphql_tags/fixtures/formatted.ts");
+    let expected = include_str!("format_graphql_tags/fixtures/formatted.expected");
+    test_fixture(transform_fixture, file!(), "formatted.ts", "format_graphql_tags/fixtures/formatted.expected", input, expected).await;
+}
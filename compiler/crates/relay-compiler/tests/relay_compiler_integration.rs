@@ -22,6 +22,7 @@ use relay_compiler::config::Config;
 use relay_compiler::errors::BuildProjectError;
 use relay_compiler::errors::Error;
 use relay_compiler::source_for_location;
+use relay_compiler::ApqPersister;
 use relay_compiler::FileSourceKind;
 use relay_compiler::FsSourceReader;
 use relay_compiler::LocalPersister;
@@ -51,11 +52,12 @@ pub async fn transform_fixture(fixture: &Fixture<'_>) -> Result<String, String>
                 |persist_config| -> Box<dyn OperationPersister + Send + Sync> {
                     match persist_config {
                         PersistConfig::Remote(remote_config) => {
-                            Box::new(RemotePersister::new(remote_config.clone()))
+                            Box::new(RemotePersister::new((**remote_config).clone()))
                         }
                         PersistConfig::Local(local_config) => {
                             Box::new(LocalPersister::new(local_config.clone()))
                         }
+                        PersistConfig::Apq(_) => Box::new(ApqPersister),
                     }
                 },
             )
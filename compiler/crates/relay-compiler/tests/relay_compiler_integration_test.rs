@@ -4,7 +4,7 @@
  * This source code is licensed under the MIT license found in the
  * LICENSE file in the root directory of this source tree.
  *
- * @generated SignedSource<<4a187a561407aa9fb9576d43e789725b>>
+ * @generated SignedSource<<ab1bd5242b4255a73daafa6b3d82e8c1>>
  */
 
 mod relay_compiler_integration;
@@ -12,6 +12,13 @@ mod relay_compiler_integration;
 use relay_compiler_integration::transform_fixture;
 use fixture_tests::test_fixture;
 
+#[tokio::test]
+async fn apq_persist() {
+    let input = include_str!("relay_compiler_integration/fixtures/apq_persist.input");
+    let expected = include_str!("relay_compiler_integration/fixtures/apq_persist.expected");
+    test_fixture(transform_fixture, file!(), "apq_persist.input", "relay_compiler_integration/fixtures/apq_persist.expected", input, expected).await;
+}
+
 #[tokio::test]
 async fn client_mutation_extension() {
     let input = include_str!("relay_compiler_integration/fixtures/client_mutation_extension.input");
@@ -96,6 +103,20 @@ async fn fragment_arguments() {
     test_fixture(transform_fixture, file!(), "fragment_arguments.input", "relay_compiler_integration/fixtures/fragment_arguments.expected", input, expected).await;
 }
 
+#[tokio::test]
+async fn fragment_ref_brands_typescript() {
+    let input = include_str!("relay_compiler_integration/fixtures/fragment_ref_brands_typescript.input");
+    let expected = include_str!("relay_compiler_integration/fixtures/fragment_ref_brands_typescript.expected");
+    test_fixture(transform_fixture, file!(), "fragment_ref_brands_typescript.input", "relay_compiler_integration/fixtures/fragment_ref_brands_typescript.expected", input, expected).await;
+}
+
+#[tokio::test]
+async fn json_schema_artifacts() {
+    let input = include_str!("relay_compiler_integration/fixtures/json_schema_artifacts.input");
+    let expected = include_str!("relay_compiler_integration/fixtures/json_schema_artifacts.expected");
+    test_fixture(transform_fixture, file!(), "json_schema_artifacts.input", "relay_compiler_integration/fixtures/json_schema_artifacts.expected", input, expected).await;
+}
+
 #[tokio::test]
 async fn live_resolver_implements_interface_field() {
     let input = include_str!("relay_compiler_integration/fixtures/live_resolver_implements_interface_field.input");
@@ -103,6 +124,13 @@ async fn live_resolver_implements_interface_field() {
     test_fixture(transform_fixture, file!(), "live_resolver_implements_interface_field.input", "relay_compiler_integration/fixtures/live_resolver_implements_interface_field.expected", input, expected).await;
 }
 
+#[tokio::test]
+async fn local_persist_manifest() {
+    let input = include_str!("relay_compiler_integration/fixtures/local_persist_manifest.input");
+    let expected = include_str!("relay_compiler_integration/fixtures/local_persist_manifest.expected");
+    test_fixture(transform_fixture, file!(), "local_persist_manifest.input", "relay_compiler_integration/fixtures/local_persist_manifest.expected", input, expected).await;
+}
+
 #[tokio::test]
 async fn multiple_resolvers_on_interface_of_all_strong_model_type() {
     let input = include_str!("relay_compiler_integration/fixtures/multiple_resolvers_on_interface_of_all_strong_model_type.input");
@@ -145,6 +173,20 @@ async fn required_conditional_field() {
     test_fixture(transform_fixture, file!(), "required_conditional_field.input", "relay_compiler_integration/fixtures/required_conditional_field.expected", input, expected).await;
 }
 
+#[tokio::test]
+async fn resolver_function_types_module_flow() {
+    let input = include_str!("relay_compiler_integration/fixtures/resolver_function_types_module_flow.input");
+    let expected = include_str!("relay_compiler_integration/fixtures/resolver_function_types_module_flow.expected");
+    test_fixture(transform_fixture, file!(), "resolver_function_types_module_flow.input", "relay_compiler_integration/fixtures/resolver_function_types_module_flow.expected", input, expected).await;
+}
+
+#[tokio::test]
+async fn resolver_function_types_module_typescript() {
+    let input = include_str!("relay_compiler_integration/fixtures/resolver_function_types_module_typescript.input");
+    let expected = include_str!("relay_compiler_integration/fixtures/resolver_function_types_module_typescript.expected");
+    test_fixture(transform_fixture, file!(), "resolver_function_types_module_typescript.input", "relay_compiler_integration/fixtures/resolver_function_types_module_typescript.expected", input, expected).await;
+}
+
 #[tokio::test]
 async fn resolver_on_interface() {
     let input = include_str!("relay_compiler_integration/fixtures/resolver_on_interface.input");
@@ -362,6 +404,13 @@ async fn resolvers_with_context_path_import() {
     test_fixture(transform_fixture, file!(), "resolvers_with_context_path_import.input", "relay_compiler_integration/fixtures/resolvers_with_context_path_import.expected", input, expected).await;
 }
 
+#[tokio::test]
+async fn response_validator_flow() {
+    let input = include_str!("relay_compiler_integration/fixtures/response_validator_flow.input");
+    let expected = include_str!("relay_compiler_integration/fixtures/response_validator_flow.expected");
+    test_fixture(transform_fixture, file!(), "response_validator_flow.input", "relay_compiler_integration/fixtures/response_validator_flow.expected", input, expected).await;
+}
+
 #[tokio::test]
 async fn semantic_null_require_bubble_to_required_parent() {
     let input = include_str!("relay_compiler_integration/fixtures/semantic_null_require_bubble_to_required_parent.input");
@@ -0,0 +1,368 @@
+/*
+ * Copyright (c) Meta Platforms, Inc. and affiliates.
+ *
+ * This source code is licensed under the MIT license found in the
+ * LICENSE file in the root directory of this source tree.
+ */
+
+use std::path::PathBuf;
+use std::sync::Arc;
+use std::sync::Mutex;
+use std::time::Duration;
+
+use persist_query::PersistError;
+use relay_compiler::config::ArtifactForPersister;
+use relay_compiler::OperationPersister;
+use relay_compiler::PersistConfig;
+use relay_compiler::RemotePersistConfig;
+use relay_compiler::RemotePersister;
+use serde_json::json;
+use serde_json::Value;
+use tokio::io::AsyncReadExt;
+use tokio::io::AsyncWriteExt;
+use tokio::net::TcpListener;
+use tokio::net::TcpStream;
+
+struct MockResponse {
+    status: u16,
+    body: &'static str,
+    delay: Duration,
+}
+
+impl MockResponse {
+    fn new(status: u16, body: &'static str) -> Self {
+        Self {
+            status,
+            body,
+            delay: Duration::ZERO,
+        }
+    }
+
+    fn delayed(mut self, delay: Duration) -> Self {
+        self.delay = delay;
+        self
+    }
+}
+
+/// Serves the responses, one per connection and in order, on a local port.
+/// Returns the URL of the server and the bodies of the received requests.
+async fn start_server(responses: Vec<MockResponse>) -> (String, Arc<Mutex<Vec<String>>>) {
+    let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+    let url = format!("http://{}/persist", listener.local_addr().unwrap());
+    let requests = Arc::new(Mutex::new(vec![]));
+    let received = Arc::clone(&requests);
+    tokio::spawn(async move {
+        for response in responses {
+            let (stream, _) = listener.accept().await.unwrap();
+            let received = Arc::clone(&received);
+            tokio::spawn(async move { respond(stream, response, received).await });
+        }
+    });
+    (url, requests)
+}
+
+async fn respond(mut stream: TcpStream, response: MockResponse, received: Arc<Mutex<Vec<String>>>) {
+    let mut request = vec![];
+    let mut buffer = [0; 4096];
+    let body_start = loop {
+        let read = stream.read(&mut buffer).await.unwrap();
+        request.extend_from_slice(&buffer[..read]);
+        if let Some(position) = request.windows(4).position(|window| window == b"\r\n\r\n") {
+            break position + 4;
+        }
+    };
+    let head = String::from_utf8_lossy(&request[..body_start]).to_lowercase();
+    let content_length: usize = head
+        .lines()
+        .find_map(|line| line.strip_prefix("content-length:"))
+        .map_or(0, |length| length.trim().parse().unwrap());
+    while request.len() < body_start + content_length {
+        let read = stream.read(&mut buffer).await.unwrap();
+        request.extend_from_slice(&buffer[..read]);
+    }
+    received
+        .lock()
+        .unwrap()
+        .push(String::from_utf8_lossy(&request[body_start..]).to_string());
+
+    tokio::time::sleep(response.delay).await;
+    let reply = format!(
+        "HTTP/1.1 {} Mock\r\ncontent-type: application/json\r\ncontent-length: {}\r\nconnection: close\r\n\r\n{}",
+        response.status,
+        response.body.len(),
+        response.body
+    );
+    // The client may have given up on the request already.
+    let _ = stream.write_all(reply.as_bytes()).await;
+}
+
+fn persister(url: &str, options: Value) -> RemotePersister {
+    let mut config = json!({ "url": url, "retryDelayMs": 1 });
+    config
+        .as_object_mut()
+        .unwrap()
+        .extend(options.as_object().unwrap().clone());
+    RemotePersister::new(serde_json::from_value::<RemotePersistConfig>(config).unwrap())
+}
+
+fn artifact(name: &str) -> ArtifactForPersister {
+    ArtifactForPersister {
+        text: format!("query {} {{ me {{ id }} }}", name),
+        relative_path: PathBuf::from(format!("__generated__/{}.graphql.js", name)),
+        override_schema: None,
+        operation_name: name.to_string(),
+    }
+}
+
+#[tokio::test]
+async fn retries_server_errors() {
+    let (url, requests) = start_server(vec![
+        MockResponse::new(503, "unavailable"),
+        MockResponse::new(200, r#"{"id": "1"}"#),
+    ])
+    .await;
+    let persister = persister(&url, json!({ "retries": 2 }));
+
+    let id = persister.persist_artifact(artifact("FooQuery")).await;
+
+    assert_eq!(id.unwrap(), "1");
+    assert_eq!(requests.lock().unwrap().len(), 2);
+}
+
+#[tokio::test]
+async fn gives_up_after_the_configured_retries() {
+    let (url, requests) = start_server(vec![
+        MockResponse::new(500, "error"),
+        MockResponse::new(502, "error"),
+        MockResponse::new(200, r#"{"id": "1"}"#),
+    ])
+    .await;
+    let persister = persister(&url, json!({ "retries": 1 }));
+
+    let result = persister.persist_artifact(artifact("FooQuery")).await;
+
+    assert!(matches!(
+        result,
+        Err(PersistError::ServerErrorResponse { status: 502, .. })
+    ));
+    assert_eq!(requests.lock().unwrap().len(), 2);
+}
+
+#[tokio::test]
+async fn does_not_retry_error_responses() {
+    let (url, requests) = start_server(vec![
+        MockResponse::new(400, r#"{"error": {"message": "Invalid query"}}"#),
+        MockResponse::new(200, r#"{"id": "1"}"#),
+    ])
+    .await;
+    let persister = persister(&url, json!({ "retries": 3 }));
+
+    let result = persister.persist_artifact(artifact("FooQuery")).await;
+
+    assert!(
+        matches!(result, Err(PersistError::ErrorResponse { message }) if message == "Invalid query")
+    );
+    assert_eq!(requests.lock().unwrap().len(), 1);
+}
+
+#[tokio::test]
+async fn retries_slow_responses_after_the_timeout() {
+    let (url, requests) = start_server(vec![
+        MockResponse::new(200, r#"{"id": "slow"}"#).delayed(Duration::from_secs(5)),
+        MockResponse::new(200, r#"{"id": "fast"}"#),
+    ])
+    .await;
+
+    let result = persister(&url, json!({ "timeoutMs": 100 }))
+        .persist_artifact(artifact("FooQuery"))
+        .await;
+    assert!(matches!(
+        result,
+        Err(PersistError::Timeout { timeout_ms: 100 })
+    ));
+
+    let (url, _) = start_server(vec![
+        MockResponse::new(200, r#"{"id": "slow"}"#).delayed(Duration::from_secs(5)),
+        MockResponse::new(200, r#"{"id": "fast"}"#),
+    ])
+    .await;
+    let id = persister(&url, json!({ "timeoutMs": 100, "retries": 1 }))
+        .persist_artifact(artifact("FooQuery"))
+        .await;
+    assert_eq!(id.unwrap(), "fast");
+    assert_eq!(requests.lock().unwrap().len(), 1);
+}
+
+#[tokio::test]
+async fn sends_form_requests_by_default() {
+    let (url, requests) = start_server(vec![MockResponse::new(200, r#"{"id": "1"}"#)]).await;
+    let persister = persister(&url, json!({ "params": { "app": "test" } }));
+
+    let id = persister.persist_artifact(artifact("FooQuery")).await;
+
+    assert_eq!(id.unwrap(), "1");
+    assert_eq!(
+        requests.lock().unwrap()[0],
+        "app=test&text=query+FooQuery+%7B+me+%7B+id+%7D+%7D"
+    );
+}
+
+#[tokio::test]
+async fn sends_json_requests_and_reads_nested_ids() {
+    let (url, requests) = start_server(vec![MockResponse::new(
+        200,
+        r#"{"data": {"persistedQuery": {"id": 42}}}"#,
+    )])
+    .await;
+    let persister = persister(
+        &url,
+        json!({
+            "params": { "app": "test" },
+            "requestEncoding": "json",
+            "documentField": "query",
+            "responseIdPath": "data.persistedQuery.id",
+        }),
+    );
+
+    let id = persister.persist_artifact(artifact("FooQuery")).await;
+
+    assert_eq!(id.unwrap(), "42");
+    assert_eq!(
+        serde_json::from_str::<Value>(&requests.lock().unwrap()[0]).unwrap(),
+        json!({ "app": "test", "query": "query FooQuery { me { id } }" })
+    );
+}
+
+#[tokio::test]
+async fn reports_responses_without_id() {
+    let (url, _) = start_server(vec![
+        MockResponse::new(200, r#"{"data": {}}"#),
+        MockResponse::new(200, r#"{"error": {"message": "Not allowed"}}"#),
+    ])
+    .await;
+    let persister = persister(&url, json!({ "responseIdPath": "data.id" }));
+
+    let result = persister.persist_artifact(artifact("FooQuery")).await;
+    assert!(
+        matches!(result, Err(PersistError::MissingResponseId { path, .. }) if path == "data.id")
+    );
+
+    let result = persister.persist_artifact(artifact("FooQuery")).await;
+    assert!(
+        matches!(result, Err(PersistError::ErrorResponse { message }) if message == "Not allowed")
+    );
+}
+
+#[tokio::test]
+async fn persists_batches() {
+    // The batches are sent concurrently, so both responses contain all ids.
+    let ids = r#"{"ids": {"AQuery": "a", "BQuery": "b", "CQuery": "c"}}"#;
+    let (url, requests) = start_server(vec![
+        MockResponse::new(200, ids),
+        MockResponse::new(200, ids),
+    ])
+    .await;
+    let persister = persister(
+        &url,
+        json!({ "params": { "app": "test" }, "batch": { "size": 2 } }),
+    );
+
+    let ids = persister
+        .persist_artifacts(vec![
+            artifact("AQuery"),
+            artifact("BQuery"),
+            artifact("CQuery"),
+        ])
+        .await
+        .into_iter()
+        .collect::<Result<Vec<_>, _>>()
+        .unwrap();
+
+    let requests = requests.lock().unwrap();
+    let mut bodies = requests
+        .iter()
+        .map(|body| serde_json::from_str::<Value>(body).unwrap())
+        .collect::<Vec<_>>();
+    bodies.sort_by_key(|body| body["documents"].as_object().unwrap().len());
+    assert_eq!(ids, vec!["a", "b", "c"]);
+    assert_eq!(
+        bodies,
+        vec![
+            json!({
+                "app": "test",
+                "documents": { "CQuery": "query CQuery { me { id } }" },
+            }),
+            json!({
+                "app": "test",
+                "documents": {
+                    "AQuery": "query AQuery { me { id } }",
+                    "BQuery": "query BQuery { me { id } }",
+                },
+            }),
+        ]
+    );
+}
+
+#[tokio::test]
+async fn reports_operations_missing_from_the_batch_response() {
+    let (url, _) = start_server(vec![MockResponse::new(200, r#"{"ids": {"AQuery": "a"}}"#)]).await;
+    let persister = persister(&url, json!({ "batch": {} }));
+
+    let results = persister
+        .persist_artifacts(vec![artifact("AQuery"), artifact("BQuery")])
+        .await;
+
+    assert_eq!(results[0].as_ref().unwrap(), "a");
+    assert!(
+        matches!(&results[1], Err(PersistError::ErrorResponse { message }) if message.contains("`BQuery`"))
+    );
+}
+
+#[tokio::test]
+async fn reports_the_batch_error_for_each_operation() {
+    let (url, _) = start_server(vec![MockResponse::new(500, "error")]).await;
+    let persister = persister(&url, json!({ "batch": {} }));
+
+    let results = persister
+        .persist_artifacts(vec![artifact("AQuery"), artifact("BQuery")])
+        .await;
+
+    for (result, name) in results.iter().zip(["AQuery", "BQuery"]) {
+        assert!(
+            matches!(
+                result,
+                Err(PersistError::BatchError { operation_name, source })
+                    if operation_name == name
+                        && matches!(**source, PersistError::ServerErrorResponse { status: 500, .. })
+            ),
+            "{:?}",
+            result
+        );
+    }
+}
+
+#[test]
+fn rejects_single_request_options_in_batches() {
+    let error = serde_json::from_value::<PersistConfig>(json!({
+        "url": "http://localhost/persist",
+        "documentField": "query",
+        "responseIdPath": "data.id",
+        "batch": {},
+    }))
+    .unwrap_err();
+    assert!(
+        error
+            .to_string()
+            .contains("`documentField`, `responseIdPath` cannot be used with `batch`"),
+        "{}",
+        error
+    );
+
+    assert!(
+        serde_json::from_value::<PersistConfig>(json!({
+            "url": "http://localhost/persist",
+            "documentField": "query",
+        }))
+        .is_ok()
+    );
+}
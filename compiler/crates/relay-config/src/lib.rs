@@ -29,12 +29,16 @@ pub use module_import_config::DynamicModuleProvider;
 pub use module_import_config::ModuleImportConfig;
 pub use module_import_config::Surface;
 pub use non_node_id_fields_config::NonNodeIdFieldsConfig;
+pub use project_config::ApqPersistConfig;
+pub use project_config::ApqPersistKind;
 pub use project_config::ExtraArtifactsConfig;
 pub use project_config::LocalPersistAlgorithm;
 pub use project_config::LocalPersistConfig;
 pub use project_config::PersistConfig;
 pub use project_config::ProjectConfig;
+pub use project_config::RemotePersistBatchConfig;
 pub use project_config::RemotePersistConfig;
+pub use project_config::RequestEncoding;
 pub use project_config::SchemaConfig;
 pub use project_config::SchemaLocation;
 pub use project_name::ProjectName;
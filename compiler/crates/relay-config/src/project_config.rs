@@ -48,11 +48,24 @@ type FnvIndexMap<K, V> = IndexMap<K, V, FnvBuildHasher>;
 pub struct RemotePersistConfig {
     /// URL to send a POST request to to persist.
     pub url: String,
-    /// The document will be in a POST parameter `text`. This map can contain
-    /// additional parameters to send.
+    /// The document will be in a POST parameter `text`, or `documentField`.
+    /// This map can contain additional parameters to send.
     #[serde(default)]
     pub params: FnvIndexMap<String, String>,
 
+    /// Encoding of the request body, `form` (urlencoded) or `json`.
+    #[serde(default)]
+    pub request_encoding: RequestEncoding,
+
+    /// Name of the parameter that contains the document.
+    #[serde(default = "default_document_field")]
+    pub document_field: String,
+
+    /// Dot-separated path of the id in the JSON response, e.g.
+    /// `data.persistedQuery.id`.
+    #[serde(default = "default_response_id_path")]
+    pub response_id_path: String,
+
     /// Additional headers to send
     #[serde(default)]
     pub headers: FnvIndexMap<String, String>,
@@ -66,6 +79,110 @@ pub struct RemotePersistConfig {
 
     #[serde(default)]
     pub include_query_text: bool,
+
+    /// Timeout of a single request, in milliseconds.
+    #[serde(default)]
+    pub timeout_ms: Option<u64>,
+
+    /// Number of times a request is retried after a network error, a timeout
+    /// or a 5xx response.
+    #[serde(default)]
+    pub retries: usize,
+
+    /// Delay before the first retry, in milliseconds. The delay is doubled
+    /// after each retry.
+    #[serde(default = "default_retry_delay_ms")]
+    pub retry_delay_ms: u64,
+
+    /// If set, operations are sent in batches, with a single request per
+    /// batch. The batch requests have their own JSON shape, so
+    /// `requestEncoding`, `documentField` and `responseIdPath` cannot be set.
+    #[serde(default)]
+    pub batch: Option<RemotePersistBatchConfig>,
+}
+
+/// How the document and the params are encoded in the body of a request.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[derive(JsonSchema)]
+#[serde(rename_all = "lowercase")]
+pub enum RequestEncoding {
+    /// `application/x-www-form-urlencoded`
+    #[default]
+    Form,
+    /// `application/json`
+    Json,
+}
+
+fn default_document_field() -> String {
+    "text".to_string()
+}
+
+fn default_response_id_path() -> String {
+    "id".to_string()
+}
+
+fn default_retry_delay_ms() -> u64 {
+    100
+}
+
+impl RemotePersistConfig {
+    /// The options of single requests cannot be combined with `batch`.
+    fn validate(&self) -> Result<(), String> {
+        if self.batch.is_none() {
+            return Ok(());
+        }
+        let mut options = vec![];
+        if self.request_encoding != RequestEncoding::default() {
+            options.push("requestEncoding");
+        }
+        if self.document_field != default_document_field() {
+            options.push("documentField");
+        }
+        if self.response_id_path != default_response_id_path() {
+            options.push("responseIdPath");
+        }
+        if options.is_empty() {
+            Ok(())
+        } else {
+            Err(format!(
+                "Invalid `persistConfig`: `{}` cannot be used with `batch`. Batches are sent as JSON, with the documents in the `documents` field, and their ids are read from the `ids` field of the response.",
+                options.join("`, `")
+            ))
+        }
+    }
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize, JsonSchema)]
+#[serde(deny_unknown_fields, rename_all = "camelCase")]
+pub struct RemotePersistBatchConfig {
+    /// URL to send the batches to. Defaults to the `url` of the remote
+    /// persist config.
+    #[serde(default)]
+    pub url: Option<String>,
+
+    /// The maximum number of operations in a single batch.
+    #[serde(
+        default = "default_batch_size",
+        deserialize_with = "deserialize_batch_size"
+    )]
+    pub size: usize,
+}
+
+fn default_batch_size() -> usize {
+    100
+}
+
+fn deserialize_batch_size<'de, D>(d: D) -> Result<usize, D::Error>
+where
+    D: Deserializer<'de>,
+{
+    let size: usize = Deserialize::deserialize(d)?;
+    if size == 0 {
+        return Err(Error::custom(
+            "Invalid `persistConfig.batch.size` value. A batch should contain at least one operation.",
+        ));
+    }
+    Ok(size)
 }
 
 fn deserialize_semaphore_permits<'de, D>(d: D) -> Result<Option<usize>, D::Error>
@@ -106,13 +223,47 @@ pub struct LocalPersistConfig {
 
     #[serde(default)]
     pub include_query_text: bool,
+
+    /// If set, the compiler will also write a JSON manifest to this path,
+    /// describing each persisted operation: its name, kind, id, text, hash
+    /// algorithm, source file and variable types.
+    #[serde(default)]
+    pub manifest: Option<PathBuf>,
+}
+
+/// Computes ids compatible with Automatic Persisted Queries (APQ) without
+/// calling a server: the id is the SHA-256 hash of the operation text, and
+/// it is also added to the request `metadata` as `persistedQuery`, in the
+/// shape of the APQ `extensions.persistedQuery` request field.
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+#[serde(deny_unknown_fields)]
+pub struct ApqPersistConfig {
+    pub kind: ApqPersistKind,
+
+    /// The query text is needed by APQ clients to register the operation
+    /// when the server does not know its hash, so it is included by default.
+    #[serde(default = "default_apq_include_query_text")]
+    pub include_query_text: bool,
+}
+
+/// The `kind` tag of an `ApqPersistConfig`, which has no required fields
+/// that would tell it apart from the other persist configs.
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, JsonSchema)]
+#[serde(rename_all = "lowercase")]
+pub enum ApqPersistKind {
+    Apq,
+}
+
+fn default_apq_include_query_text() -> bool {
+    true
 }
 
 #[derive(Debug, Serialize, Clone, JsonSchema)]
 #[serde(untagged)]
 pub enum PersistConfig {
-    Remote(RemotePersistConfig),
+    Remote(Box<RemotePersistConfig>),
     Local(LocalPersistConfig),
+    Apq(ApqPersistConfig),
 }
 
 impl PersistConfig {
@@ -120,6 +271,7 @@ impl PersistConfig {
         match self {
             PersistConfig::Remote(remote_config) => remote_config.include_query_text,
             PersistConfig::Local(local_config) => local_config.include_query_text,
+            PersistConfig::Apq(apq_config) => apq_config.include_query_text,
         }
     }
 }
@@ -127,8 +279,16 @@ impl PersistConfig {
 impl<'de> Deserialize<'de> for PersistConfig {
     fn deserialize<D: Deserializer<'de>>(deserializer: D) -> std::result::Result<Self, D::Error> {
         let value = Value::deserialize(deserializer)?;
+        if value.get("kind").is_some() {
+            return ApqPersistConfig::deserialize(value)
+                .map(PersistConfig::Apq)
+                .map_err(|error| Error::custom(error.to_string()));
+        }
         match RemotePersistConfig::deserialize(value.clone()) {
-            Ok(remote_config) => Ok(PersistConfig::Remote(remote_config)),
+            Ok(remote_config) => match remote_config.validate() {
+                Ok(()) => Ok(PersistConfig::Remote(Box::new(remote_config))),
+                Err(message) => Err(Error::custom(message)),
+            },
             Err(remote_error) => match LocalPersistConfig::deserialize(value) {
                 Ok(local_config) => {
                     if !local_config.file.exists() {
@@ -117,6 +117,22 @@ pub struct TypegenConfig {
     #[serde(default)]
     pub custom_scalar_types: FnvIndexMap<ScalarName, CustomType>,
 
+    /// A map from GraphQL scalar types to the custom JS type they accept in
+    /// input positions (variables and input object fields), overriding
+    /// `customScalarTypes` there, example:
+    /// { "DateTime": "Date | string" }
+    #[serde(default)]
+    pub custom_scalar_input_types: FnvIndexMap<ScalarName, CustomType>,
+
+    /// A map from schema coordinates of fields (`Type.field`) to a custom JS
+    /// type, overriding the type of their scalar for these fields only. The
+    /// type of an interface field also applies to the implementing types,
+    /// example:
+    /// { "User.birthday": "string" }
+    /// { "DateRangeInput.end": {"name": "EndDate", "path": "../src/Dates"} }
+    #[serde(default)]
+    pub custom_field_types: FnvIndexMap<StringKey, CustomType>,
+
     /// Require all GraphQL scalar types mapping to be defined, will throw
     /// if a GraphQL scalar type doesn't have a JS type
     #[serde(default)]
@@ -146,6 +162,37 @@ pub struct TypegenConfig {
     /// Indicates the type to import and use as the context for live resolvers.
     #[serde(default)]
     pub resolver_context_type: Option<ResolverContextTypeInput>,
+
+    /// Emits a `validateResponse` function in the artifact of each operation,
+    /// which checks the response data at runtime against the generated types:
+    /// nullability, enum values, `__typename` of unions and the presence of
+    /// custom scalars. It returns the list of the errors it found.
+    #[serde(default)]
+    pub generate_response_validators: bool,
+
+    /// Emits a JSON Schema document next to the artifact of each operation and
+    /// fragment (`<Name>.graphql.schema.json`), describing the same types as the
+    /// generated Flow or TypeScript types: variables and response data of
+    /// operations, and the data of fragments.
+    #[serde(default)]
+    pub generate_json_schemas: bool,
+
+    /// # For Typescript type generation
+    /// Brands fragment references with `unique symbol` types, so that plain
+    /// objects can no longer be passed where a fragment reference is expected.
+    /// The compiler generates the module declaring the brands at this path,
+    /// relative to the root directory, and the artifacts import
+    /// `FragmentRefs` and `FragmentType` from it.
+    #[serde(default)]
+    pub typescript_fragment_ref_brands_module: Option<PathBuf>,
+
+    /// Generates a module at this path, relative to the root directory, that
+    /// exports the type of the function implementing each Relay Resolver field,
+    /// e.g. `User__name$resolver`. It covers the arguments, root fragment key,
+    /// context and return type of the resolver, so resolver modules can be
+    /// annotated with it to be checked against the schema.
+    #[serde(default)]
+    pub resolver_function_types_module: Option<PathBuf>,
 }
 
 impl Default for TypegenConfig {
@@ -156,12 +203,18 @@ impl Default for TypegenConfig {
             optional_input_fields: Default::default(),
             use_import_type_syntax: Default::default(),
             custom_scalar_types: Default::default(),
+            custom_scalar_input_types: Default::default(),
+            custom_field_types: Default::default(),
             require_custom_scalar_types: Default::default(),
             no_future_proof_enums: Default::default(),
             eager_es_modules: Default::default(),
             typescript_exclude_undefined_from_nullable_union: Default::default(),
             custom_error_type: None,
             resolver_context_type: Default::default(),
+            generate_response_validators: Default::default(),
+            generate_json_schemas: Default::default(),
+            typescript_fragment_ref_brands_module: Default::default(),
+            resolver_function_types_module: Default::default(),
         }
     }
 }
@@ -0,0 +1,516 @@
+/*
+ * Copyright (c) Meta Platforms, Inc. and affiliates.
+ *
+ * This source code is licensed under the MIT license found in the
+ * LICENSE file in the root directory of this source tree.
+ */
+
+//! Utilities for providing the call hierarchy feature, over the graph of
+//! fragment spreads and Relay Resolver root fragments
+
+use std::path::Path;
+
+use common::Location;
+use dependency_analyzer::get_ir_selection_references;
+use fnv::FnvHashMap;
+use graphql_ir::ExecutableDefinitionName;
+use graphql_ir::FragmentDefinitionName;
+use graphql_ir::OperationDefinitionName;
+use graphql_ir::Program;
+use graphql_ir::Selection;
+use graphql_syntax::OperationKind;
+use intern::string_key::StringKey;
+use lsp_types::request::CallHierarchyIncomingCalls;
+use lsp_types::request::CallHierarchyOutgoingCalls;
+use lsp_types::request::CallHierarchyPrepare;
+use lsp_types::request::Request;
+use lsp_types::CallHierarchyIncomingCall;
+use lsp_types::CallHierarchyItem;
+use lsp_types::CallHierarchyOutgoingCall;
+use lsp_types::Range;
+use lsp_types::SymbolKind;
+use relay_docblock::DocblockIr;
+use relay_docblock::On;
+use relay_docblock::ResolverFieldDocblockIr;
+use relay_transforms::get_resolver_fragment_dependency_name;
+use schema::Field;
+use schema::SDLSchema;
+use schema::Schema;
+use serde::Deserialize;
+use serde::Serialize;
+
+use crate::docblock_resolution_info::DocblockResolutionInfo;
+use crate::location::transform_relay_location_on_disk_to_lsp_location;
+use crate::lsp_runtime_error::LSPRuntimeError;
+use crate::lsp_runtime_error::LSPRuntimeResult;
+use crate::node_resolution_info::NodeKind;
+use crate::server::GlobalState;
+use crate::FeatureResolutionInfo;
+
+/// A node of the call hierarchy: operations and fragments call the fragments
+/// they spread and the Relay Resolvers they select, and Relay Resolvers call
+/// their root fragment.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(tag = "kind", rename_all = "camelCase")]
+pub enum CallHierarchyNode {
+    Operation {
+        name: StringKey,
+    },
+    Fragment {
+        name: StringKey,
+    },
+    #[serde(rename_all = "camelCase")]
+    Resolver {
+        parent_type: StringKey,
+        field_name: StringKey,
+    },
+}
+
+/// An item of the call hierarchy, with a Relay location on disk.
+#[derive(Debug)]
+pub struct GraphQLCallHierarchyItem {
+    pub node: CallHierarchyNode,
+    pub name: String,
+    pub detail: Option<String>,
+    pub kind: SymbolKind,
+    /// The location of the name of the definition or of the resolver field.
+    pub location: Location,
+}
+
+/// A call from or to an item, with the locations of the calls in the caller.
+#[derive(Debug)]
+pub struct GraphQLCallHierarchyCall {
+    pub item: GraphQLCallHierarchyItem,
+    pub locations: Vec<Location>,
+}
+
+/// The `data` of the call hierarchy items sent to the client, which is sent
+/// back with the incoming and outgoing calls requests.
+#[derive(Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct CallHierarchyItemData {
+    project_name: StringKey,
+    node: CallHierarchyNode,
+}
+
+/// The callers of the fragments of a program, to find the incoming calls of
+/// an item without walking the whole program.
+#[derive(Debug, Default)]
+pub struct CallHierarchyIndex {
+    /// The definitions that reference each fragment, as in the dependency
+    /// graph of the compiler: they spread it, or select a Relay Resolver with
+    /// it as root fragment.
+    referrers: FnvHashMap<FragmentDefinitionName, Vec<ExecutableDefinitionName>>,
+    /// The Relay Resolvers with each fragment as root fragment.
+    resolvers: FnvHashMap<FragmentDefinitionName, Vec<CallHierarchyNode>>,
+}
+
+impl CallHierarchyIndex {
+    pub fn new(program: &Program) -> Self {
+        let mut index = Self::default();
+        let definitions = program
+            .operations()
+            .map(|operation| (operation.name.item.into(), &operation.selections))
+            .chain(
+                program
+                    .fragments()
+                    .map(|fragment| (fragment.name.item.into(), &fragment.selections)),
+            );
+        for (name, selections) in definitions {
+            for reference in get_ir_selection_references(&program.schema, selections) {
+                if let ExecutableDefinitionName::FragmentDefinitionName(fragment_name) = reference {
+                    index.referrers.entry(fragment_name).or_default().push(name);
+                }
+            }
+        }
+        for field in program.schema.fields() {
+            if let (Some(fragment_name), Some(resolver)) = (
+                get_resolver_fragment_dependency_name(field),
+                get_resolver_node(&program.schema, field),
+            ) {
+                index
+                    .resolvers
+                    .entry(fragment_name)
+                    .or_default()
+                    .push(resolver);
+            }
+        }
+        index
+    }
+}
+
+pub fn on_prepare_call_hierarchy(
+    state: &impl GlobalState,
+    params: <CallHierarchyPrepare as Request>::Params,
+) -> LSPRuntimeResult<<CallHierarchyPrepare as Request>::Result> {
+    let text_document_position = params.text_document_position_params;
+    let project_name =
+        state.extract_project_name_from_url(&text_document_position.text_document.uri)?;
+    let program = state.get_program(&project_name)?;
+    let node = match state.resolve_node(&text_document_position)? {
+        FeatureResolutionInfo::GraphqlNode(node_resolution_info) => {
+            match node_resolution_info.kind {
+                NodeKind::OperationDefinition(operation) => CallHierarchyNode::Operation {
+                    name: operation.name.ok_or(LSPRuntimeError::ExpectedError)?.value,
+                },
+                NodeKind::FragmentDefinition(fragment) => CallHierarchyNode::Fragment {
+                    name: fragment.name.value,
+                },
+                NodeKind::FragmentSpread(name) => CallHierarchyNode::Fragment { name },
+                _ => return Err(LSPRuntimeError::ExpectedError),
+            }
+        }
+        FeatureResolutionInfo::DocblockNode(docblock_node) => {
+            let DocblockResolutionInfo::FieldName(field_name) = docblock_node.resolution_info
+            else {
+                return Err(LSPRuntimeError::ExpectedError);
+            };
+            let parent_type = match docblock_node.ir {
+                DocblockIr::Field(ResolverFieldDocblockIr::LegacyVerboseResolver(
+                    relay_resolver,
+                )) => match relay_resolver.on {
+                    On::Type(type_) => type_.value.item,
+                    On::Interface(interface) => interface.value.item,
+                },
+                DocblockIr::Field(ResolverFieldDocblockIr::TerseRelayResolver(terse_resolver)) => {
+                    terse_resolver.type_.item
+                }
+                DocblockIr::Type(_) => return Err(LSPRuntimeError::ExpectedError),
+            };
+            CallHierarchyNode::Resolver {
+                parent_type,
+                field_name,
+            }
+        }
+    };
+    let item = get_call_hierarchy_item(&program, node).ok_or(LSPRuntimeError::ExpectedError)?;
+
+    Ok(Some(vec![to_call_hierarchy_item(
+        &state.root_dir(),
+        project_name,
+        item,
+    )?]))
+}
+
+pub fn on_call_hierarchy_incoming_calls(
+    state: &impl GlobalState,
+    params: <CallHierarchyIncomingCalls as Request>::Params,
+) -> LSPRuntimeResult<<CallHierarchyIncomingCalls as Request>::Result> {
+    let (project_name, node) = read_item_data(&params.item)?;
+    let program = state.get_program(&project_name)?;
+    let index = state.get_call_hierarchy_index(&project_name)?;
+    let root_dir = state.root_dir();
+
+    Ok(Some(
+        get_incoming_calls(&program, &index, node)
+            .into_iter()
+            .filter_map(|call| {
+                Some(CallHierarchyIncomingCall {
+                    from_ranges: to_ranges(&root_dir, call.locations).ok()?,
+                    from: to_call_hierarchy_item(&root_dir, project_name, call.item).ok()?,
+                })
+            })
+            .collect(),
+    ))
+}
+
+pub fn on_call_hierarchy_outgoing_calls(
+    state: &impl GlobalState,
+    params: <CallHierarchyOutgoingCalls as Request>::Params,
+) -> LSPRuntimeResult<<CallHierarchyOutgoingCalls as Request>::Result> {
+    let (project_name, node) = read_item_data(&params.item)?;
+    let program = state.get_program(&project_name)?;
+    let root_dir = state.root_dir();
+
+    Ok(Some(
+        get_outgoing_calls(&program, node)
+            .into_iter()
+            .filter_map(|call| {
+                Some(CallHierarchyOutgoingCall {
+                    from_ranges: to_ranges(&root_dir, call.locations).ok()?,
+                    to: to_call_hierarchy_item(&root_dir, project_name, call.item).ok()?,
+                })
+            })
+            .collect(),
+    ))
+}
+
+fn read_item_data(item: &CallHierarchyItem) -> LSPRuntimeResult<(StringKey, CallHierarchyNode)> {
+    let data = item.data.clone().ok_or_else(|| {
+        LSPRuntimeError::UnexpectedError("Expected the call hierarchy item to have data".into())
+    })?;
+    let data: CallHierarchyItemData = serde_json::from_value(data).map_err(|err| {
+        LSPRuntimeError::UnexpectedError(format!(
+            "Unable to read the call hierarchy item data: {}",
+            err
+        ))
+    })?;
+    Ok((data.project_name, data.node))
+}
+
+fn to_call_hierarchy_item(
+    root_dir: &Path,
+    project_name: StringKey,
+    item: GraphQLCallHierarchyItem,
+) -> LSPRuntimeResult<CallHierarchyItem> {
+    let location = transform_relay_location_on_disk_to_lsp_location(root_dir, item.location)?;
+    let data = serde_json::to_value(CallHierarchyItemData {
+        project_name,
+        node: item.node,
+    })
+    .map_err(|err| LSPRuntimeError::UnexpectedError(err.to_string()))?;
+
+    Ok(CallHierarchyItem {
+        name: item.name,
+        kind: item.kind,
+        tags: None,
+        detail: item.detail,
+        uri: location.uri,
+        range: location.range,
+        selection_range: location.range,
+        data: Some(data),
+    })
+}
+
+fn to_ranges(root_dir: &Path, locations: Vec<Location>) -> LSPRuntimeResult<Vec<Range>> {
+    locations
+        .into_iter()
+        .map(|location| {
+            transform_relay_location_on_disk_to_lsp_location(root_dir, location)
+                .map(|location| location.range)
+        })
+        .collect()
+}
+
+/// Returns the item of an operation, a fragment or a Relay Resolver with a
+/// root fragment, if it exists in the program.
+pub fn get_call_hierarchy_item(
+    program: &Program,
+    node: CallHierarchyNode,
+) -> Option<GraphQLCallHierarchyItem> {
+    match node {
+        CallHierarchyNode::Operation { name } => {
+            let operation = program.operation(OperationDefinitionName(name))?;
+            Some(GraphQLCallHierarchyItem {
+                node,
+                name: name.to_string(),
+                detail: Some(operation.kind.to_string()),
+                kind: match operation.kind {
+                    OperationKind::Query => SymbolKind::FUNCTION,
+                    OperationKind::Mutation | OperationKind::Subscription => SymbolKind::METHOD,
+                },
+                location: operation.name.location,
+            })
+        }
+        CallHierarchyNode::Fragment { name } => {
+            let fragment = program.fragment(FragmentDefinitionName(name))?;
+            Some(GraphQLCallHierarchyItem {
+                node,
+                name: name.to_string(),
+                detail: Some(
+                    program
+                        .schema
+                        .get_type_name(fragment.type_condition)
+                        .to_string(),
+                ),
+                kind: SymbolKind::CLASS,
+                location: fragment.name.location,
+            })
+        }
+        CallHierarchyNode::Resolver {
+            parent_type,
+            field_name,
+        } => {
+            let field = get_resolver_field(&program.schema, parent_type, field_name)?;
+            Some(GraphQLCallHierarchyItem {
+                node,
+                name: format!("{}.{}", parent_type, field_name),
+                detail: Some("Relay Resolver".to_string()),
+                kind: SymbolKind::FIELD,
+                location: field.name.location,
+            })
+        }
+    }
+}
+
+/// Returns the operations and fragments that spread a fragment, and the
+/// Relay Resolvers that use it as their root fragment. For a Relay Resolver,
+/// returns the operations and fragments that select it. Only the selections
+/// of the callers of the index are walked.
+pub fn get_incoming_calls(
+    program: &Program,
+    index: &CallHierarchyIndex,
+    node: CallHierarchyNode,
+) -> Vec<GraphQLCallHierarchyCall> {
+    let fragment_name = match node {
+        CallHierarchyNode::Operation { .. } => return vec![],
+        CallHierarchyNode::Fragment { name } => FragmentDefinitionName(name),
+        CallHierarchyNode::Resolver {
+            parent_type,
+            field_name,
+        } => match get_resolver_field(&program.schema, parent_type, field_name)
+            .and_then(get_resolver_fragment_dependency_name)
+        {
+            Some(fragment_name) => fragment_name,
+            None => return vec![],
+        },
+    };
+
+    let mut calls = vec![];
+    for referrer in index.referrers.get(&fragment_name).into_iter().flatten() {
+        let (caller, selections) = match referrer {
+            ExecutableDefinitionName::OperationDefinitionName(name) => {
+                let Some(operation) = program.operation(*name) else {
+                    continue;
+                };
+                (
+                    CallHierarchyNode::Operation { name: name.0 },
+                    &operation.selections,
+                )
+            }
+            ExecutableDefinitionName::FragmentDefinitionName(name) => {
+                let Some(fragment) = program.fragment(*name) else {
+                    continue;
+                };
+                (
+                    CallHierarchyNode::Fragment { name: name.0 },
+                    &fragment.selections,
+                )
+            }
+        };
+        // A referrer of the root fragment of a resolver may spread the
+        // fragment, select the resolver, or both.
+        let Some((_, locations)) = get_calls(&program.schema, selections)
+            .into_iter()
+            .find(|(callee, _)| *callee == node)
+        else {
+            continue;
+        };
+        if let Some(item) = get_call_hierarchy_item(program, caller) {
+            calls.push(GraphQLCallHierarchyCall { item, locations });
+        }
+    }
+    if let CallHierarchyNode::Fragment { .. } = node {
+        for resolver in index.resolvers.get(&fragment_name).into_iter().flatten() {
+            if let Some(item) = get_call_hierarchy_item(program, *resolver) {
+                calls.push(GraphQLCallHierarchyCall {
+                    locations: vec![item.location],
+                    item,
+                });
+            }
+        }
+    }
+    calls.sort_by(|a, b| a.item.name.cmp(&b.item.name));
+    calls
+}
+
+/// Returns the fragments spread by an operation or a fragment and the Relay
+/// Resolvers with a root fragment that it selects, in the order of their first
+/// use. For a Relay Resolver, returns its root fragment.
+pub fn get_outgoing_calls(
+    program: &Program,
+    node: CallHierarchyNode,
+) -> Vec<GraphQLCallHierarchyCall> {
+    let calls = match node {
+        CallHierarchyNode::Operation { name } => program
+            .operation(OperationDefinitionName(name))
+            .map(|operation| get_calls(&program.schema, &operation.selections)),
+        CallHierarchyNode::Fragment { name } => program
+            .fragment(FragmentDefinitionName(name))
+            .map(|fragment| get_calls(&program.schema, &fragment.selections)),
+        CallHierarchyNode::Resolver {
+            parent_type,
+            field_name,
+        } => get_resolver_field(&program.schema, parent_type, field_name).and_then(|field| {
+            let fragment_name = get_resolver_fragment_dependency_name(field)?;
+            Some(vec![(
+                CallHierarchyNode::Fragment {
+                    name: fragment_name.0,
+                },
+                vec![field.name.location],
+            )])
+        }),
+    };
+
+    calls
+        .unwrap_or_default()
+        .into_iter()
+        .filter_map(|(callee, locations)| {
+            Some(GraphQLCallHierarchyCall {
+                item: get_call_hierarchy_item(program, callee)?,
+                locations,
+            })
+        })
+        .collect()
+}
+
+/// Returns the fragments spread and the Relay Resolvers with a root fragment
+/// selected in the selections, with the locations of each use, in the order of
+/// their first use. Unlike the dependency graph of the compiler, where a
+/// definition references the root fragment of a resolver it selects, the
+/// resolver is a node of its own, between the definition and the fragment.
+fn get_calls(
+    schema: &SDLSchema,
+    selections: &[Selection],
+) -> Vec<(CallHierarchyNode, Vec<Location>)> {
+    let mut calls: Vec<(CallHierarchyNode, Vec<Location>)> = vec![];
+    let mut add_call = |callee: CallHierarchyNode, location: Location| match calls
+        .iter_mut()
+        .find(|(node, _)| *node == callee)
+    {
+        Some((_, locations)) => locations.push(location),
+        None => calls.push((callee, vec![location])),
+    };
+    let mut selections: Vec<_> = selections.iter().rev().collect();
+    while let Some(selection) = selections.pop() {
+        let field = match selection {
+            Selection::FragmentSpread(spread) => {
+                add_call(
+                    CallHierarchyNode::Fragment {
+                        name: spread.fragment.item.0,
+                    },
+                    spread.fragment.location,
+                );
+                None
+            }
+            Selection::LinkedField(field) => {
+                selections.extend(field.selections.iter().rev());
+                Some(field.definition)
+            }
+            Selection::ScalarField(field) => Some(field.definition),
+            Selection::InlineFragment(fragment) => {
+                selections.extend(fragment.selections.iter().rev());
+                None
+            }
+            Selection::Condition(condition) => {
+                selections.extend(condition.selections.iter().rev());
+                None
+            }
+        };
+        if let Some(definition) = field {
+            let field = schema.field(definition.item);
+            if get_resolver_fragment_dependency_name(field).is_some() {
+                if let Some(resolver) = get_resolver_node(schema, field) {
+                    add_call(resolver, definition.location);
+                }
+            }
+        }
+    }
+    calls
+}
+
+fn get_resolver_field(
+    schema: &SDLSchema,
+    parent_type: StringKey,
+    field_name: StringKey,
+) -> Option<&Field> {
+    let field = schema.field(schema.named_field(schema.get_type(parent_type)?, field_name)?);
+    get_resolver_fragment_dependency_name(field).map(|_| field)
+}
+
+fn get_resolver_node(schema: &SDLSchema, field: &Field) -> Option<CallHierarchyNode> {
+    Some(CallHierarchyNode::Resolver {
+        parent_type: schema.get_type_name(field.parent_type?),
+        field_name: field.name.item,
+    })
+}
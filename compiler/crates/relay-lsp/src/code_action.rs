@@ -6,6 +6,9 @@
  */
 
 mod create_name_suggestion;
+mod extract_fragment;
+mod inline_fragment;
+mod validation_fix;
 
 use std::collections::HashMap;
 use std::collections::HashSet;
@@ -39,6 +42,14 @@ use serde_json::Value;
 
 use self::create_name_suggestion::create_default_fragment_name;
 use self::create_name_suggestion::create_default_fragment_name_with_index;
+use self::extract_fragment::get_extract_fragment_code_actions;
+pub use self::extract_fragment::get_extracted_fragment;
+pub use self::extract_fragment::ExtractedFragment;
+pub use self::inline_fragment::get_definition_removal_location;
+use self::inline_fragment::get_inline_fragment_code_actions;
+pub use self::inline_fragment::get_inlined_fragment_spread;
+use self::validation_fix::get_validation_fix_code_actions;
+pub use self::validation_fix::get_validation_fix_edits;
 use crate::lsp_runtime_error::LSPRuntimeError;
 use crate::lsp_runtime_error::LSPRuntimeResult;
 use crate::server::GlobalState;
@@ -55,9 +66,12 @@ pub(crate) fn on_code_action(
     }
 
     if let Some(diagnostic) = state.get_diagnostic_for_range(&uri, params.range) {
-        let code_actions = get_code_actions_from_diagnostics(&uri, diagnostic);
-        if code_actions.is_some() {
-            return Ok(code_actions);
+        let validation_fix_code_actions = get_validation_fix_code_actions(state, &uri, &diagnostic);
+        let mut code_actions =
+            get_code_actions_from_diagnostics(&uri, diagnostic).unwrap_or_default();
+        code_actions.extend(validation_fix_code_actions);
+        if !code_actions.is_empty() {
+            return Ok(Some(code_actions));
         }
     }
 
@@ -70,12 +84,29 @@ pub(crate) fn on_code_action(
     let (document, position_span) =
         state.extract_executable_document_from_text(&text_document_position_params, 1)?;
 
+    let inline_fragment_code_actions = get_inline_fragment_code_actions(
+        state,
+        &text_document_position_params,
+        &document,
+        position_span,
+    );
     let path = document.resolve((), position_span);
 
     let used_definition_names = get_definition_names(&definitions);
-    let result = get_code_actions(path, used_definition_names, uri, params.range)
-        .ok_or(LSPRuntimeError::ExpectedError)?;
-    Ok(Some(result))
+    let extract_fragment_code_actions = get_extract_fragment_code_actions(
+        state,
+        &uri,
+        params.range,
+        &used_definition_names.fragment_names,
+    );
+    let mut code_actions =
+        get_code_actions(path, used_definition_names, uri, params.range).unwrap_or_default();
+    code_actions.extend(extract_fragment_code_actions);
+    code_actions.extend(inline_fragment_code_actions);
+    if code_actions.is_empty() {
+        return Err(LSPRuntimeError::ExpectedError);
+    }
+    Ok(Some(code_actions))
 }
 
 fn get_code_actions_from_diagnostics(
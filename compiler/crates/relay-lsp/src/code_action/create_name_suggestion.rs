@@ -89,6 +89,31 @@ pub fn create_default_fragment_name_with_index(
     }
 }
 
+/// This function will create a name suggestion for a fragment extracted
+/// from the selections on `prop_name` in a file.
+/// Suggested name is {prefix}_{prop_name}, followed by an incremental index
+/// while `is_used` returns true for the suggested name.
+pub fn create_extracted_fragment_name(
+    file_name: &str,
+    prop_name: &str,
+    is_used: impl Fn(&str) -> bool,
+) -> Option<String> {
+    let module_name = extract_module_name(file_name)?;
+    let name = format!("{}_{}", module_name, prop_name);
+    if !is_used(&name) {
+        return Some(name);
+    }
+    let mut index = 1;
+    loop {
+        let new_name = format!("{}{}", name, index);
+        if is_used(&new_name) {
+            index += 1;
+        } else {
+            return Some(new_name);
+        }
+    }
+}
+
 /// If you already have a name for your fragment/operation,
 /// it doesn't start/end with correct prefixes/suffixes -
 /// this function will return a correctly wrapped name.
@@ -0,0 +1,520 @@
+/*
+ * Copyright (c) Meta Platforms, Inc. and affiliates.
+ *
+ * This source code is licensed under the MIT license found in the
+ * LICENSE file in the root directory of this source tree.
+ */
+
+use std::collections::HashMap;
+use std::collections::HashSet;
+
+use common::Span;
+use graphql_ir::FragmentDefinitionName;
+use graphql_ir::ARGUMENT_DEFINITION;
+use graphql_syntax::Argument;
+use graphql_syntax::ConstantValue;
+use graphql_syntax::ExecutableDefinition;
+use graphql_syntax::List;
+use graphql_syntax::Selection;
+use graphql_syntax::Value;
+use graphql_syntax::VariableIdentifier;
+use intern::string_key::Intern;
+use intern::string_key::StringKey;
+use intern::Lookup;
+use lsp_types::CodeAction;
+use lsp_types::CodeActionOrCommand;
+use lsp_types::Range;
+use lsp_types::TextDocumentIdentifier;
+use lsp_types::TextDocumentPositionParams;
+use lsp_types::TextEdit;
+use lsp_types::Url;
+use lsp_types::WorkspaceEdit;
+use schema::SDLSchema;
+use schema::Schema;
+
+use super::create_name_suggestion::create_extracted_fragment_name;
+use crate::node_resolution_info::TypePath;
+use crate::node_resolution_info::TypePathItem;
+use crate::server::GlobalState;
+use crate::Feature;
+
+/// A new fragment with the selections of a definition, and the edits that
+/// replace the selections with a spread of it.
+#[derive(Debug)]
+pub struct ExtractedFragment {
+    pub name: String,
+    pub edits: Vec<(Span, String)>,
+}
+
+/// Returns the code action that extracts the selections in the range into a
+/// new fragment, added after the enclosing definition in the same `graphql`
+/// tag. The name of the fragment is not used by the fragments of the file, or
+/// of the project.
+pub(crate) fn get_extract_fragment_code_actions(
+    state: &impl GlobalState,
+    url: &Url,
+    range: Range,
+    used_names: &HashSet<String>,
+) -> Vec<CodeActionOrCommand> {
+    if range.start == range.end {
+        return vec![];
+    }
+    let text_document_position = |position| TextDocumentPositionParams {
+        text_document: TextDocumentIdentifier { uri: url.clone() },
+        position,
+    };
+    let (
+        Ok((Feature::ExecutableDocument(document), start)),
+        Ok((Feature::ExecutableDocument(_), end)),
+    ) = (
+        state.extract_feature_from_text(&text_document_position(range.start), 1),
+        state.extract_feature_from_text(&text_document_position(range.end), 1),
+    )
+    else {
+        return vec![];
+    };
+    if start.source_location() != end.source_location() {
+        return vec![];
+    }
+    let Ok(source_text) = state.extract_graphql_source_text(&text_document_position(range.start))
+    else {
+        return vec![];
+    };
+    let Ok(project_name) = state.extract_project_name_from_url(url) else {
+        return vec![];
+    };
+    let Ok(schema) = state.get_schema(&project_name) else {
+        return vec![];
+    };
+    // The fragments of the file that are not in the program yet are in the
+    // used names
+    let program = state.get_program(&project_name).ok();
+
+    let selection_span = Span::new(start.span().start, end.span().end);
+    let Some(definition) = document
+        .definitions
+        .iter()
+        .find(|definition| definition.location().span().contains(selection_span))
+    else {
+        return vec![];
+    };
+    let Some(extracted_fragment) = get_extracted_fragment(
+        &schema,
+        &source_text,
+        definition,
+        selection_span,
+        |prop_name| {
+            create_extracted_fragment_name(url.path(), prop_name, |name| {
+                used_names.contains(name)
+                    || program.as_ref().is_some_and(|program| {
+                        program
+                            .fragment(FragmentDefinitionName(name.intern()))
+                            .is_some()
+                    })
+            })
+        },
+    ) else {
+        return vec![];
+    };
+
+    let Ok(text_edits) = extracted_fragment
+        .edits
+        .into_iter()
+        .map(|(span, new_text)| {
+            state
+                .transform_relay_location_in_editor_to_lsp_location(
+                    definition.location().with_span(span),
+                )
+                .map(|location| TextEdit {
+                    range: location.range,
+                    new_text,
+                })
+        })
+        .collect::<Result<Vec<_>, _>>()
+    else {
+        return vec![];
+    };
+    let mut changes = HashMap::new();
+    changes.insert(url.clone(), text_edits);
+
+    vec![CodeActionOrCommand::CodeAction(CodeAction {
+        title: format!(
+            "Extract selections into fragment '{}'",
+            extracted_fragment.name
+        ),
+        kind: Some(lsp_types::CodeActionKind::REFACTOR_EXTRACT),
+        edit: Some(WorkspaceEdit {
+            changes: Some(changes),
+            ..Default::default()
+        }),
+        ..Default::default()
+    })]
+}
+
+/// Extracts the selections of the definition that overlap the span into a
+/// new fragment on their parent type. The variables they use are declared as
+/// arguments of the new fragment, with fragment variables if the definition
+/// declares its arguments with them, and with `@argumentDefinitions`
+/// otherwise, with the types and default values of their declarations.
+/// `create_name` creates the name of the fragment from the name of the field
+/// the selections are on.
+pub fn get_extracted_fragment(
+    schema: &SDLSchema,
+    source_text: &str,
+    definition: &ExecutableDefinition,
+    span: Span,
+    create_name: impl Fn(&str) -> Option<String>,
+) -> Option<ExtractedFragment> {
+    let (root_path_item, root_selections) = match definition {
+        ExecutableDefinition::Operation(operation) => (
+            TypePathItem::Operation(operation.operation_kind()),
+            &operation.selections,
+        ),
+        ExecutableDefinition::Fragment(fragment) => (
+            TypePathItem::FragmentDefinition {
+                type_name: fragment.type_condition.type_.value,
+            },
+            &fragment.selections,
+        ),
+    };
+    let mut type_path = TypePath::default();
+    type_path.add_type(root_path_item);
+    let mut prop_name = None;
+    let selections = find_selections(root_selections, span, &mut type_path, &mut prop_name)?;
+    let parent_type = type_path.resolve_leaf_type(schema)?;
+    let type_name = schema.get_type_name(parent_type);
+    let prop_name = match prop_name {
+        Some(prop_name) => prop_name.lookup().to_string(),
+        None => lower_first_character(type_name.lookup()),
+    };
+    let name = create_name(&prop_name)?;
+
+    let mut variables = vec![];
+    collect_selection_variables(selections, &mut variables);
+    let arguments = variables
+        .into_iter()
+        .filter_map(|variable| {
+            get_variable_declaration(definition, source_text, variable.name)
+                .map(|declaration| (variable.name, declaration))
+        })
+        .collect::<Vec<_>>();
+    let uses_fragment_variables = matches!(
+        definition,
+        ExecutableDefinition::Fragment(fragment) if fragment.variable_definitions.is_some()
+    );
+
+    let (spread_arguments, fragment_arguments) = if arguments.is_empty() {
+        (String::new(), String::new())
+    } else if uses_fragment_variables {
+        (
+            format!(
+                "({})",
+                join(&arguments, |(name, _)| format!("{}: ${}", name, name))
+            ),
+            format!(
+                "({})",
+                join(&arguments, |(name, declaration)| {
+                    let default_value = declaration
+                        .default_value
+                        .as_ref()
+                        .map_or(String::new(), |value| format!(" = {}", value));
+                    format!("${}: {}{}", name, declaration.type_, default_value)
+                })
+            ),
+        )
+    } else {
+        (
+            format!(
+                " @arguments({})",
+                join(&arguments, |(name, _)| format!("{}: ${}", name, name))
+            ),
+            format!(
+                " @{}({})",
+                ARGUMENT_DEFINITION.0,
+                join(&arguments, |(name, declaration)| {
+                    let default_value = declaration
+                        .default_value
+                        .as_ref()
+                        .map_or(String::new(), |value| format!(", defaultValue: {}", value));
+                    format!(
+                        "{}: {{type: \"{}\"{}}}",
+                        name, declaration.type_, default_value
+                    )
+                })
+            ),
+        )
+    };
+    let (fragment_variables, argument_definitions) = if uses_fragment_variables {
+        (fragment_arguments, String::new())
+    } else {
+        (String::new(), fragment_arguments)
+    };
+
+    let selections_span = Span::new(
+        selections.first()?.span().start,
+        selections.last()?.span().end,
+    );
+    let (start, end) = selections_span.as_usize();
+    let selections_text = source_text.get(start..end)?;
+    let selections_indentation = get_indentation(source_text, selections_span.start);
+    let definition_span = definition.location().span();
+    let definition_indentation = get_indentation(source_text, definition_span.start);
+
+    let mut fragment_text = format!(
+        "\n\n{}fragment {}{} on {}{} {{",
+        definition_indentation, name, fragment_variables, type_name, argument_definitions
+    );
+    for (index, line) in selections_text.lines().enumerate() {
+        let line = if index == 0 {
+            line
+        } else {
+            strip_indentation(line, selections_indentation.len())
+        };
+        fragment_text.push('\n');
+        if !line.trim().is_empty() {
+            fragment_text.push_str(&definition_indentation);
+            fragment_text.push_str("  ");
+            fragment_text.push_str(line);
+        }
+    }
+    fragment_text.push('\n');
+    fragment_text.push_str(&definition_indentation);
+    fragment_text.push('}');
+
+    let spread_text = format!("...{}{}", name, spread_arguments);
+    Some(ExtractedFragment {
+        name,
+        edits: vec![
+            (selections_span, spread_text),
+            (
+                Span::new(definition_span.end, definition_span.end),
+                fragment_text,
+            ),
+        ],
+    })
+}
+
+/// Returns the selections that overlap the span, in the innermost selection
+/// set that has several of them or a selection that is entirely in the span.
+/// Adds the fields and inline fragments that enclose them to the type path,
+/// and sets the prop name to the alias or name of the innermost field.
+fn find_selections<'a>(
+    selections: &'a List<Selection>,
+    span: Span,
+    type_path: &mut TypePath,
+    prop_name: &mut Option<StringKey>,
+) -> Option<&'a [Selection]> {
+    let overlaps = |selection: &Selection| {
+        let selection_span = selection.span();
+        selection_span.start < span.end && span.start < selection_span.end
+    };
+    let first = selections.items.iter().position(overlaps)?;
+    let last = selections.items.iter().rposition(overlaps)?;
+    let selected = &selections.items[first..=last];
+
+    if let [selection] = selected {
+        if !span.contains(selection.span()) {
+            match selection {
+                Selection::LinkedField(field) if overlaps_list(&field.selections, span) => {
+                    type_path.add_type(TypePathItem::LinkedField {
+                        name: field.name.value,
+                    });
+                    *prop_name = Some(
+                        field
+                            .alias
+                            .as_ref()
+                            .map_or(field.name.value, |alias| alias.alias.value),
+                    );
+                    return find_selections(&field.selections, span, type_path, prop_name);
+                }
+                Selection::InlineFragment(inline_fragment)
+                    if overlaps_list(&inline_fragment.selections, span) =>
+                {
+                    if let Some(type_condition) = &inline_fragment.type_condition {
+                        type_path.add_type(TypePathItem::InlineFragment {
+                            type_name: type_condition.type_.value,
+                        });
+                    }
+                    return find_selections(
+                        &inline_fragment.selections,
+                        span,
+                        type_path,
+                        prop_name,
+                    );
+                }
+                _ => {}
+            }
+        }
+    }
+    Some(selected)
+}
+
+fn overlaps_list<T>(list: &List<T>, span: Span) -> bool {
+    list.start.span.end <= span.start && span.end <= list.end.span.start
+}
+
+fn collect_selection_variables<'a>(
+    selections: &'a [Selection],
+    variables: &mut Vec<&'a VariableIdentifier>,
+) {
+    for selection in selections {
+        let (arguments, selections) = match selection {
+            Selection::FragmentSpread(spread) => (spread.arguments.as_ref(), None),
+            Selection::InlineFragment(inline_fragment) => (None, Some(&inline_fragment.selections)),
+            Selection::LinkedField(field) => (field.arguments.as_ref(), Some(&field.selections)),
+            Selection::ScalarField(field) => (field.arguments.as_ref(), None),
+        };
+        let directive_arguments = selection
+            .directives()
+            .iter()
+            .filter_map(|directive| directive.arguments.as_ref());
+        for arguments in arguments.into_iter().chain(directive_arguments) {
+            collect_argument_variables(&arguments.items, variables);
+        }
+        if let Some(selections) = selections {
+            collect_selection_variables(&selections.items, variables);
+        }
+    }
+}
+
+fn collect_argument_variables<'a>(
+    arguments: &'a [Argument],
+    variables: &mut Vec<&'a VariableIdentifier>,
+) {
+    for argument in arguments {
+        collect_value_variables(&argument.value, variables);
+    }
+}
+
+fn collect_value_variables<'a>(value: &'a Value, variables: &mut Vec<&'a VariableIdentifier>) {
+    match value {
+        Value::Constant(_) => {}
+        Value::Variable(variable) => {
+            if !variables.iter().any(|item| item.name == variable.name) {
+                variables.push(variable);
+            }
+        }
+        Value::List(list) => {
+            for item in &list.items {
+                collect_value_variables(item, variables);
+            }
+        }
+        Value::Object(object) => collect_argument_variables(&object.items, variables),
+    }
+}
+
+/// A variable declared by a definition, with the text of its default value.
+struct VariableDeclaration {
+    type_: String,
+    default_value: Option<String>,
+}
+
+/// Returns the declaration of a variable by the definition, or `None` for the
+/// global variables of fragments.
+fn get_variable_declaration(
+    definition: &ExecutableDefinition,
+    source_text: &str,
+    variable_name: StringKey,
+) -> Option<VariableDeclaration> {
+    let (variable_definitions, directives) = match definition {
+        ExecutableDefinition::Operation(operation) => {
+            (&operation.variable_definitions, &operation.directives)
+        }
+        ExecutableDefinition::Fragment(fragment) => {
+            (&fragment.variable_definitions, &fragment.directives)
+        }
+    };
+    let get_text = |span: Span| {
+        let (start, end) = span.as_usize();
+        source_text.get(start..end).map(|text| text.to_string())
+    };
+    if let Some(variable_definition) = variable_definitions
+        .iter()
+        .flat_map(|variable_definitions| &variable_definitions.items)
+        .find(|variable_definition| variable_definition.name.name == variable_name)
+    {
+        return Some(VariableDeclaration {
+            type_: variable_definition.type_.to_string(),
+            default_value: variable_definition
+                .default_value
+                .as_ref()
+                .and_then(|default_value| get_text(default_value.value.span())),
+        });
+    }
+
+    let argument = directives
+        .iter()
+        .filter(|directive| directive.name.value == ARGUMENT_DEFINITION.0)
+        .filter_map(|directive| directive.arguments.as_ref())
+        .flat_map(|arguments| &arguments.items)
+        .find(|argument| argument.name.value == variable_name)?;
+    let (type_, default_value) = match &argument.value {
+        Value::Constant(ConstantValue::Object(object)) => {
+            let get_value = |key: &str| {
+                object
+                    .items
+                    .iter()
+                    .find(|item| item.name.value.lookup() == key)
+                    .map(|item| &item.value)
+            };
+            (
+                get_value("type").and_then(|value| value.get_string_literal()),
+                get_value("defaultValue").map(|value| value.span()),
+            )
+        }
+        Value::Object(object) => {
+            let get_value = |key: &str| {
+                object
+                    .items
+                    .iter()
+                    .find(|item| item.name.value.lookup() == key)
+                    .map(|item| &item.value)
+            };
+            (
+                get_value("type").and_then(|value| match value {
+                    Value::Constant(value) => value.get_string_literal(),
+                    _ => None,
+                }),
+                get_value("defaultValue").map(|value| value.span()),
+            )
+        }
+        _ => return None,
+    };
+    Some(VariableDeclaration {
+        type_: type_?.lookup().to_string(),
+        default_value: default_value.and_then(get_text),
+    })
+}
+
+/// Returns the whitespace before the offset on its line.
+pub(super) fn get_indentation(source_text: &str, offset: u32) -> String {
+    let line_start = source_text[..offset as usize]
+        .rfind('\n')
+        .map_or(0, |index| index + 1);
+    source_text[line_start..offset as usize]
+        .chars()
+        .take_while(|chr| chr.is_whitespace())
+        .collect()
+}
+
+fn strip_indentation(line: &str, indentation: usize) -> &str {
+    let whitespace = line
+        .char_indices()
+        .take(indentation)
+        .take_while(|(_, chr)| chr.is_whitespace())
+        .last()
+        .map_or(0, |(index, chr)| index + chr.len_utf8());
+    &line[whitespace..]
+}
+
+fn lower_first_character(name: &str) -> String {
+    let mut chars = name.chars();
+    match chars.next() {
+        Some(first) => first.to_lowercase().chain(chars).collect(),
+        None => String::new(),
+    }
+}
+
+fn join<T>(items: &[T], format_item: impl Fn(&T) -> String) -> String {
+    items.iter().map(format_item).collect::<Vec<_>>().join(", ")
+}
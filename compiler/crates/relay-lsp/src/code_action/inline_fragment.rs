@@ -0,0 +1,319 @@
+/*
+ * Copyright (c) Meta Platforms, Inc. and affiliates.
+ *
+ * This source code is licensed under the MIT license found in the
+ * LICENSE file in the root directory of this source tree.
+ */
+
+use std::collections::HashMap;
+use std::sync::Arc;
+
+use common::Location;
+use common::Span;
+use graphql_ir::Condition;
+use graphql_ir::FragmentDefinition;
+use graphql_ir::FragmentDefinitionName;
+use graphql_ir::FragmentSpread;
+use graphql_ir::InlineFragment;
+use graphql_ir::Selection;
+use graphql_syntax::DirectiveLocation;
+use graphql_syntax::ExecutableDefinition;
+use graphql_syntax::ExecutableDocument;
+use graphql_text_printer::print_selection;
+use intern::string_key::StringKey;
+use lsp_types::CodeAction;
+use lsp_types::CodeActionOrCommand;
+use lsp_types::TextDocumentPositionParams;
+use lsp_types::TextEdit;
+use lsp_types::Url;
+use lsp_types::WorkspaceEdit;
+use relay_transforms::apply_fragment_spread_arguments;
+use resolution_path::IdentParent;
+use resolution_path::IdentPath;
+use resolution_path::ResolutionPath;
+use resolution_path::ResolvePosition;
+use schema::SDLSchema;
+use schema::Schema;
+use schema::Type;
+
+use super::extract_fragment::get_indentation;
+use crate::lsp_runtime_error::LSPRuntimeError;
+use crate::lsp_runtime_error::LSPRuntimeResult;
+use crate::references::ReferenceFinder;
+use crate::server::build_ir_for_lsp_with_external_fragments;
+use crate::server::GlobalState;
+
+/// Returns the code actions that replace the fragment spread at the position
+/// with the selections of the fragment, in an inline fragment. When the
+/// spread is the last one of the fragment, also returns the code action that
+/// deletes the fragment as well.
+pub(crate) fn get_inline_fragment_code_actions(
+    state: &impl GlobalState,
+    text_document_position: &TextDocumentPositionParams,
+    document: &ExecutableDocument,
+    position_span: Span,
+) -> Vec<CodeActionOrCommand> {
+    let url = &text_document_position.text_document.uri;
+    let ResolutionPath::Ident(IdentPath {
+        inner: spread_name,
+        parent: IdentParent::FragmentSpreadName(spread_path),
+    }) = document.resolve((), position_span)
+    else {
+        return vec![];
+    };
+    let Some(definition) = document
+        .definitions
+        .iter()
+        .find(|definition| definition.location().span().contains(position_span))
+    else {
+        return vec![];
+    };
+    let Ok(project_name) = state.extract_project_name_from_url(url) else {
+        return vec![];
+    };
+    let (Ok(schema), Ok(program)) = (
+        state.get_schema(&project_name),
+        state.get_program(&project_name),
+    ) else {
+        return vec![];
+    };
+    let Some(fragment) = program.fragment(FragmentDefinitionName(spread_name.value)) else {
+        return vec![];
+    };
+    let Ok(source_text) = state.extract_graphql_source_text(text_document_position) else {
+        return vec![];
+    };
+    let Ok(ir_definitions) = build_ir_for_lsp_with_external_fragments(
+        &schema,
+        std::slice::from_ref(definition),
+        std::slice::from_ref(fragment.as_ref()),
+    ) else {
+        return vec![];
+    };
+    let Some(inlined_text) = ir_definitions.first().and_then(|ir_definition| {
+        get_inlined_fragment_spread(
+            &schema,
+            ir_definition,
+            fragment,
+            spread_path.inner.span,
+            &get_indentation(&source_text, spread_path.inner.span.start),
+        )
+    }) else {
+        return vec![];
+    };
+    let Ok((spread_url, spread_edit)) = to_text_edit(
+        state,
+        definition.location().with_span(spread_path.inner.span),
+        inlined_text,
+    ) else {
+        return vec![];
+    };
+
+    let mut changes = HashMap::new();
+    changes.insert(spread_url, vec![spread_edit]);
+    let mut code_actions = vec![create_inline_code_action(
+        format!("Inline fragment '{}'", spread_name.value),
+        changes.clone(),
+    )];
+
+    let references = ReferenceFinder::get_references_to_fragment(&program, spread_name.value);
+    // The spread being inlined is the only one. No references means the
+    // program is stale, so the fragment may still be used.
+    if references.len() == 1 {
+        if let Ok((fragment_url, removal_edit)) =
+            get_fragment_removal_edit(state, fragment.name.location, spread_name.value)
+        {
+            changes
+                .entry(fragment_url)
+                .or_insert_with(Vec::new)
+                .push(removal_edit);
+            code_actions.push(create_inline_code_action(
+                format!(
+                    "Inline fragment '{}' and delete the unused fragment",
+                    spread_name.value
+                ),
+                changes,
+            ));
+        }
+    }
+    code_actions
+}
+
+/// Returns the text of an inline fragment with the selections of the fragment
+/// spread at `spread_span` in the definition, with the values of the
+/// arguments of the spread substituted for the variables of the fragment.
+/// The inline fragment has the type condition of the fragment when it is not
+/// the type the spread is on, and the directives of the spread that are valid
+/// on inline fragments. Lines after
+/// the first one are indented with `indentation`.
+pub fn get_inlined_fragment_spread(
+    schema: &SDLSchema,
+    definition: &graphql_ir::ExecutableDefinition,
+    fragment: &FragmentDefinition,
+    spread_span: Span,
+    indentation: &str,
+) -> Option<String> {
+    let (parent_type, selections) = match definition {
+        graphql_ir::ExecutableDefinition::Operation(operation) => {
+            (operation.type_, &operation.selections)
+        }
+        graphql_ir::ExecutableDefinition::Fragment(fragment) => {
+            (fragment.type_condition, &fragment.selections)
+        }
+    };
+    let (spread, parent_type, conditions) =
+        find_spread(schema, selections, parent_type, spread_span)?;
+    let inline_fragment = InlineFragment {
+        type_condition: (fragment.type_condition != parent_type).then_some(fragment.type_condition),
+        directives: spread
+            .directives
+            .iter()
+            .filter(|directive| {
+                schema
+                    .get_directive(directive.name.item)
+                    .is_some_and(|definition| {
+                        definition
+                            .locations
+                            .contains(&DirectiveLocation::InlineFragment)
+                    })
+            })
+            .cloned()
+            .collect(),
+        selections: apply_fragment_spread_arguments(fragment, spread),
+        spread_location: spread.fragment.location,
+    };
+    if inline_fragment.selections.is_empty() {
+        return None;
+    }
+    let selection = conditions.into_iter().rev().fold(
+        Selection::InlineFragment(Arc::new(inline_fragment)),
+        |selection, condition| {
+            Selection::Condition(Arc::new(Condition {
+                selections: vec![selection],
+                ..condition.clone()
+            }))
+        },
+    );
+    let text = print_selection(schema, &selection);
+    Some(
+        text.lines()
+            .collect::<Vec<_>>()
+            .join(&format!("\n{}", indentation)),
+    )
+}
+
+/// Returns the spread of the fragment in the span, the type it is on, and the
+/// conditions of the directives of the spread, from the outermost one.
+fn find_spread<'a>(
+    schema: &SDLSchema,
+    selections: &'a [Selection],
+    parent_type: Type,
+    spread_span: Span,
+) -> Option<(&'a FragmentSpread, Type, Vec<&'a Condition>)> {
+    selections.iter().find_map(|selection| match selection {
+        Selection::FragmentSpread(spread) => spread_span
+            .contains(spread.fragment.location.span())
+            .then(|| (spread.as_ref(), parent_type, vec![])),
+        Selection::InlineFragment(inline_fragment) => find_spread(
+            schema,
+            &inline_fragment.selections,
+            inline_fragment.type_condition.unwrap_or(parent_type),
+            spread_span,
+        ),
+        Selection::LinkedField(field) => find_spread(
+            schema,
+            &field.selections,
+            schema.field(field.definition.item).type_.inner(),
+            spread_span,
+        ),
+        Selection::Condition(condition) => {
+            let (spread, parent_type, mut conditions) =
+                find_spread(schema, &condition.selections, parent_type, spread_span)?;
+            if spread_span.contains(condition.location.span()) {
+                conditions.insert(0, condition.as_ref());
+            }
+            Some((spread, parent_type, conditions))
+        }
+        Selection::ScalarField(_) => None,
+    })
+}
+
+/// Returns the edit that deletes the definition of the fragment from its
+/// `graphql` tag, in an open document. A fragment that is the only definition
+/// of its tag is not deleted, as that would leave an empty tag.
+fn get_fragment_removal_edit(
+    state: &impl GlobalState,
+    fragment_location: Location,
+    fragment_name: StringKey,
+) -> LSPRuntimeResult<(Url, TextEdit)> {
+    let fragment_url = Url::from_file_path(
+        state
+            .root_dir()
+            .join(fragment_location.source_location().path()),
+    )
+    .map_err(|_| LSPRuntimeError::ExpectedError)?;
+    let definitions = state.resolve_executable_definitions(&fragment_url)?;
+    let removal_location = get_definition_removal_location(&definitions, fragment_name)
+        .ok_or(LSPRuntimeError::ExpectedError)?;
+    to_text_edit(state, removal_location, String::new())
+}
+
+/// Returns the location of the text to delete to remove the fragment: the
+/// definition with the whitespace that separates it from the previous
+/// definition of its source, or from the next one for the first definition.
+pub fn get_definition_removal_location(
+    definitions: &[ExecutableDefinition],
+    fragment_name: StringKey,
+) -> Option<Location> {
+    let index = definitions.iter().position(|definition| match definition {
+        ExecutableDefinition::Fragment(fragment) => fragment.name.value == fragment_name,
+        ExecutableDefinition::Operation(_) => false,
+    })?;
+    let location = definitions[index].location();
+    let in_same_source = |other: &&ExecutableDefinition| {
+        other.location().source_location() == location.source_location()
+    };
+    let span = location.span();
+    if let Some(previous) = index
+        .checked_sub(1)
+        .and_then(|previous| definitions.get(previous))
+        .filter(in_same_source)
+    {
+        Some(location.with_span(Span::new(previous.location().span().end, span.end)))
+    } else {
+        definitions
+            .get(index + 1)
+            .filter(in_same_source)
+            .map(|next| location.with_span(Span::new(span.start, next.location().span().start)))
+    }
+}
+
+fn to_text_edit(
+    state: &impl GlobalState,
+    location: Location,
+    new_text: String,
+) -> LSPRuntimeResult<(Url, TextEdit)> {
+    let location = state.transform_relay_location_in_editor_to_lsp_location(location)?;
+    Ok((
+        location.uri,
+        TextEdit {
+            range: location.range,
+            new_text,
+        },
+    ))
+}
+
+fn create_inline_code_action(
+    title: String,
+    changes: HashMap<Url, Vec<TextEdit>>,
+) -> CodeActionOrCommand {
+    CodeActionOrCommand::CodeAction(CodeAction {
+        title,
+        kind: Some(lsp_types::CodeActionKind::REFACTOR_INLINE),
+        edit: Some(WorkspaceEdit {
+            changes: Some(changes),
+            ..Default::default()
+        }),
+        ..Default::default()
+    })
+}
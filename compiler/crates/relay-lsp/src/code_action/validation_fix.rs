@@ -0,0 +1,220 @@
+/*
+ * Copyright (c) Meta Platforms, Inc. and affiliates.
+ *
+ * This source code is licensed under the MIT license found in the
+ * LICENSE file in the root directory of this source tree.
+ */
+
+use std::collections::HashMap;
+
+use common::Span;
+use graphql_ir::ValidationFix;
+use graphql_ir::ARGUMENT_DEFINITION;
+use graphql_syntax::ExecutableDefinition;
+use graphql_syntax::List;
+use lsp_types::CodeAction;
+use lsp_types::CodeActionOrCommand;
+use lsp_types::Diagnostic;
+use lsp_types::TextDocumentIdentifier;
+use lsp_types::TextDocumentPositionParams;
+use lsp_types::TextEdit;
+use lsp_types::Url;
+use lsp_types::WorkspaceEdit;
+use serde_json::Value;
+
+use crate::server::GlobalState;
+
+/// Returns the quick fixes for the `ValidationFix`es in the data of the
+/// diagnostic, which edit the definition the diagnostic is reported in.
+pub(crate) fn get_validation_fix_code_actions(
+    state: &impl GlobalState,
+    url: &Url,
+    diagnostic: &Diagnostic,
+) -> Vec<CodeActionOrCommand> {
+    let fixes = match &diagnostic.data {
+        Some(Value::Array(data)) => data
+            .iter()
+            .filter(|item| item.is_object())
+            .filter_map(|item| serde_json::from_value::<ValidationFix>(item.clone()).ok())
+            .collect::<Vec<_>>(),
+        _ => vec![],
+    };
+    if fixes.is_empty() {
+        return vec![];
+    }
+
+    let text_document_position_params = TextDocumentPositionParams {
+        text_document: TextDocumentIdentifier { uri: url.clone() },
+        position: diagnostic.range.start,
+    };
+    let Ok((document, position_span)) =
+        state.extract_executable_document_from_text(&text_document_position_params, 1)
+    else {
+        return vec![];
+    };
+    let Some(definition) = document
+        .definitions
+        .iter()
+        .find(|definition| definition.location().span().contains(position_span))
+    else {
+        return vec![];
+    };
+
+    fixes
+        .iter()
+        .filter_map(|fix| {
+            let (title, edits) = get_validation_fix_edits(fix, definition)?;
+            let text_edits = edits
+                .into_iter()
+                .map(|(span, new_text)| {
+                    state
+                        .transform_relay_location_in_editor_to_lsp_location(
+                            definition.location().with_span(span),
+                        )
+                        .map(|location| TextEdit {
+                            range: location.range,
+                            new_text,
+                        })
+                })
+                .collect::<Result<Vec<_>, _>>()
+                .ok()?;
+            let mut changes = HashMap::new();
+            changes.insert(url.clone(), text_edits);
+
+            Some(CodeActionOrCommand::CodeAction(CodeAction {
+                title,
+                kind: Some(lsp_types::CodeActionKind::QUICKFIX),
+                diagnostics: Some(vec![diagnostic.clone()]),
+                edit: Some(WorkspaceEdit {
+                    changes: Some(changes),
+                    ..Default::default()
+                }),
+                is_preferred: Some(true),
+                ..Default::default()
+            }))
+        })
+        .collect()
+}
+
+/// Returns the title of the fix, and its edits as the spans of the text of the
+/// definition to replace.
+pub fn get_validation_fix_edits(
+    fix: &ValidationFix,
+    definition: &ExecutableDefinition,
+) -> Option<(String, Vec<(Span, String)>)> {
+    match fix {
+        ValidationFix::RemoveVariableDefinition { variable_name } => {
+            let title = format!("Remove unused variable `${}`", variable_name);
+            let variable_definitions = match definition {
+                ExecutableDefinition::Operation(operation) => &operation.variable_definitions,
+                ExecutableDefinition::Fragment(fragment) => &fragment.variable_definitions,
+            };
+            if let Some(variable_definitions) = variable_definitions {
+                if let Some(index) = variable_definitions
+                    .items
+                    .iter()
+                    .position(|item| item.name.name == variable_name.0)
+                {
+                    return Some((
+                        title,
+                        vec![(
+                            remove_list_item(variable_definitions, index, |item| item.span),
+                            String::new(),
+                        )],
+                    ));
+                }
+            }
+
+            let ExecutableDefinition::Fragment(fragment) = definition else {
+                return None;
+            };
+            let directive_index = fragment
+                .directives
+                .iter()
+                .position(|directive| directive.name.value == ARGUMENT_DEFINITION.0)?;
+            let arguments = fragment.directives[directive_index].arguments.as_ref()?;
+            let index = arguments
+                .items
+                .iter()
+                .position(|argument| argument.name.value == variable_name.0)?;
+            let span = if arguments.items.len() > 1 {
+                remove_list_item(arguments, index, |argument| argument.span)
+            } else {
+                // Remove the whole directive, with the whitespace before it
+                let start = match directive_index {
+                    0 => fragment.type_condition.span.end,
+                    _ => fragment.directives[directive_index - 1].span.end,
+                };
+                Span::new(start, fragment.directives[directive_index].span.end)
+            };
+            Some((title, vec![(span, String::new())]))
+        }
+        ValidationFix::DeclareFragmentArgument {
+            variable_name,
+            type_,
+        } => {
+            let ExecutableDefinition::Fragment(fragment) = definition else {
+                return None;
+            };
+            let title = format!(
+                "Declare `${}` as an argument of the fragment",
+                variable_name
+            );
+            if let Some(variable_definitions) = &fragment.variable_definitions {
+                let edit = match variable_definitions.items.last() {
+                    Some(last) => (
+                        Span::new(last.span.end, last.span.end),
+                        format!(", ${}: {}", variable_name, type_),
+                    ),
+                    None => (
+                        Span::new(
+                            variable_definitions.end.span.start,
+                            variable_definitions.end.span.start,
+                        ),
+                        format!("${}: {}", variable_name, type_),
+                    ),
+                };
+                return Some((title, vec![edit]));
+            }
+
+            let argument = format!("{}: {{type: \"{}\"}}", variable_name, type_);
+            let edit = match fragment
+                .directives
+                .iter()
+                .find(|directive| directive.name.value == ARGUMENT_DEFINITION.0)
+                .and_then(|directive| directive.arguments.as_ref())
+                .and_then(|arguments| arguments.items.last())
+            {
+                Some(last) => (
+                    Span::new(last.span.end, last.span.end),
+                    format!(", {}", argument),
+                ),
+                None => (
+                    Span::new(
+                        fragment.type_condition.span.end,
+                        fragment.type_condition.span.end,
+                    ),
+                    format!(" @{}({})", ARGUMENT_DEFINITION.0, argument),
+                ),
+            };
+            Some((title, vec![edit]))
+        }
+    }
+}
+
+/// Returns the span to remove for the item of the list at `index`: the item
+/// with the separator after it, or before it for the last item. Removes the
+/// whole list when it has a single item.
+fn remove_list_item<T>(list: &List<T>, index: usize, get_span: impl Fn(&T) -> Span) -> Span {
+    let items = &list.items;
+    if items.len() == 1 {
+        list.span
+    } else if index + 1 < items.len() {
+        Span::new(
+            get_span(&items[index]).start,
+            get_span(&items[index + 1]).start,
+        )
+    } else {
+        Span::new(get_span(&items[index - 1]).end, get_span(&items[index]).end)
+    }
+}
@@ -0,0 +1,213 @@
+/*
+ * Copyright (c) Meta Platforms, Inc. and affiliates.
+ *
+ * This source code is licensed under the MIT license found in the
+ * LICENSE file in the root directory of this source tree.
+ */
+
+//! Utilities for providing the code lens feature
+
+use std::path::Path;
+use std::path::PathBuf;
+
+use common::Location;
+use common::WithLocation;
+use graphql_ir::ExecutableDefinitionName;
+use graphql_ir::FragmentDefinitionName;
+use graphql_ir::OperationDefinitionName;
+use graphql_ir::Program;
+use graphql_syntax::ExecutableDefinition;
+use lsp_types::request::CodeLensRequest;
+use lsp_types::request::Request;
+use lsp_types::CodeLens;
+use lsp_types::Command;
+use lsp_types::Url;
+use relay_compiler::ArtifactRecord;
+use serde_json::json;
+
+use crate::location::transform_relay_location_on_disk_to_lsp_location;
+use crate::lsp_runtime_error::LSPRuntimeError;
+use crate::lsp_runtime_error::LSPRuntimeResult;
+use crate::references::ReferenceFinder;
+use crate::server::GlobalState;
+use crate::utils::is_file_uri_in_dir;
+
+/// Client command that shows the locations passed as the third argument, from
+/// the document and position passed as the first two.
+pub const SHOW_FRAGMENT_SPREADS_COMMAND: &str = "relay.showFragmentSpreads";
+/// Client command that opens the file passed as the argument.
+pub const OPEN_GENERATED_ARTIFACT_COMMAND: &str = "relay.openGeneratedArtifact";
+
+#[derive(Debug)]
+pub enum GraphQLCodeLensKind {
+    /// The locations of the spreads of a fragment, in the whole project.
+    FragmentSpreads(Vec<Location>),
+    /// The path of the generated artifact, relative to the root directory.
+    GeneratedArtifact(PathBuf),
+    PersistedOperationId(String),
+    OperationTextHash(String),
+}
+
+/// A code lens over the name of a definition, with Relay locations.
+#[derive(Debug)]
+pub struct GraphQLCodeLens {
+    pub location: Location,
+    pub kind: GraphQLCodeLensKind,
+}
+
+pub fn on_code_lens(
+    state: &impl GlobalState,
+    params: <CodeLensRequest as Request>::Params,
+) -> LSPRuntimeResult<<CodeLensRequest as Request>::Result> {
+    let uri = params.text_document.uri;
+    let root_dir = state.root_dir();
+    if !is_file_uri_in_dir(root_dir.clone(), &uri) {
+        return Err(LSPRuntimeError::ExpectedError);
+    }
+
+    let project_name = state.extract_project_name_from_url(&uri)?;
+    let program = state.get_program(&project_name)?;
+    let definitions = state.resolve_executable_definitions(&uri)?;
+    let code_lenses = get_code_lenses(&definitions, &program, |definition_name| {
+        state
+            .get_artifact_record(&project_name, definition_name)
+            .ok()
+    });
+    if code_lenses.is_empty() {
+        return Ok(None);
+    }
+
+    Ok(Some(
+        code_lenses
+            .into_iter()
+            .filter_map(|code_lens| to_code_lens(state, &uri, &root_dir, code_lens).ok())
+            .collect(),
+    ))
+}
+
+fn to_code_lens(
+    state: &impl GlobalState,
+    uri: &Url,
+    root_dir: &Path,
+    code_lens: GraphQLCodeLens,
+) -> LSPRuntimeResult<CodeLens> {
+    let range = state
+        .transform_relay_location_in_editor_to_lsp_location(code_lens.location)?
+        .range;
+    let command = match code_lens.kind {
+        GraphQLCodeLensKind::FragmentSpreads(locations) => {
+            // A spread in a file that cannot be read, e.g. one that was
+            // deleted since the last build, is left out.
+            let locations = locations
+                .into_iter()
+                .filter_map(|location| {
+                    transform_relay_location_on_disk_to_lsp_location(root_dir, location).ok()
+                })
+                .collect::<Vec<_>>();
+            Command::new(
+                match locations.len() {
+                    1 => "1 spread".to_string(),
+                    count => format!("{} spreads", count),
+                },
+                SHOW_FRAGMENT_SPREADS_COMMAND.to_string(),
+                Some(vec![json!(uri), json!(range.start), json!(locations)]),
+            )
+        }
+        GraphQLCodeLensKind::GeneratedArtifact(path) => {
+            let artifact_uri = Url::from_file_path(root_dir.join(path)).map_err(|_| {
+                LSPRuntimeError::UnexpectedError(
+                    "Unable to convert the artifact path to a URL".to_string(),
+                )
+            })?;
+            Command::new(
+                "Open generated artifact".to_string(),
+                OPEN_GENERATED_ARTIFACT_COMMAND.to_string(),
+                Some(vec![json!(artifact_uri)]),
+            )
+        }
+        // An empty command makes the code lens a plain label
+        GraphQLCodeLensKind::PersistedOperationId(id) => {
+            Command::new(format!("Persisted ID: {}", id), "".to_string(), None)
+        }
+        GraphQLCodeLensKind::OperationTextHash(text_hash) => {
+            Command::new(format!("Text hash: {}", text_hash), "".to_string(), None)
+        }
+    };
+
+    Ok(CodeLens {
+        range,
+        command: Some(command),
+        data: None,
+    })
+}
+
+/// Returns the code lenses of the named operations and fragments: the spreads
+/// of each fragment, the generated artifact of each definition, and the
+/// persisted id or text hash of each operation.
+pub fn get_code_lenses(
+    definitions: &[ExecutableDefinition],
+    program: &Program,
+    get_artifact_record: impl Fn(WithLocation<ExecutableDefinitionName>) -> Option<ArtifactRecord>,
+) -> Vec<GraphQLCodeLens> {
+    let mut spreads_by_fragment = ReferenceFinder::get_references_to_fragments(
+        program,
+        definitions
+            .iter()
+            .filter_map(|definition| match definition {
+                ExecutableDefinition::Fragment(fragment) => {
+                    Some(FragmentDefinitionName(fragment.name.value))
+                }
+                ExecutableDefinition::Operation(_) => None,
+            }),
+    );
+    let mut code_lenses = vec![];
+    for definition in definitions {
+        match definition {
+            ExecutableDefinition::Operation(operation) => {
+                let Some(name) = &operation.name else {
+                    continue;
+                };
+                let location = operation.location.with_span(name.span);
+                let Some(record) = get_artifact_record(WithLocation::new(
+                    location,
+                    OperationDefinitionName(name.value).into(),
+                )) else {
+                    continue;
+                };
+                code_lenses.push(GraphQLCodeLens {
+                    location,
+                    kind: GraphQLCodeLensKind::GeneratedArtifact(record.path),
+                });
+                if let Some(id) = record.persisted_operation_id {
+                    code_lenses.push(GraphQLCodeLens {
+                        location,
+                        kind: GraphQLCodeLensKind::PersistedOperationId(id),
+                    });
+                } else if let Some(text_hash) = record.text_hash {
+                    code_lenses.push(GraphQLCodeLens {
+                        location,
+                        kind: GraphQLCodeLensKind::OperationTextHash(text_hash),
+                    });
+                }
+            }
+            ExecutableDefinition::Fragment(fragment) => {
+                let location = fragment.location.with_span(fragment.name.span);
+                let name = FragmentDefinitionName(fragment.name.value);
+                let mut spreads = spreads_by_fragment.remove(&name).unwrap_or_default();
+                spreads.sort_by_key(|location| (location.source_location(), location.span()));
+                code_lenses.push(GraphQLCodeLens {
+                    location,
+                    kind: GraphQLCodeLensKind::FragmentSpreads(spreads),
+                });
+                if let Some(record) = get_artifact_record(WithLocation::new(location, name.into()))
+                {
+                    code_lenses.push(GraphQLCodeLens {
+                        location,
+                        kind: GraphQLCodeLensKind::GeneratedArtifact(record.path),
+                    });
+                }
+            }
+        }
+    }
+    code_lenses
+}
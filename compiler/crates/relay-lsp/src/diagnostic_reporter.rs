@@ -8,6 +8,9 @@
 //! Utilities for reporting errors to an LSP client
 use std::path::Path;
 use std::path::PathBuf;
+use std::sync::atomic::AtomicBool;
+use std::sync::atomic::AtomicU64;
+use std::sync::atomic::Ordering;
 
 use common::get_diagnostics_data;
 use common::Diagnostic as CompilerDiagnostic;
@@ -19,24 +22,40 @@ use dashmap::mapref::entry::Entry;
 use dashmap::DashMap;
 use dunce::canonicalize;
 use extract_graphql::JavaScriptSourceFeature;
+use graphql_ir::ValidationFix;
 use lsp_server::Message;
 use lsp_server::Notification as ServerNotification;
+use lsp_server::Request as ServerRequest;
+use lsp_server::RequestId;
 use lsp_types::notification::Notification;
 use lsp_types::notification::PublishDiagnostics;
+use lsp_types::request::Request;
+use lsp_types::request::WorkspaceDiagnosticRefresh;
 use lsp_types::Diagnostic;
 use lsp_types::DiagnosticRelatedInformation as LspDiagnosticRelatedInformation;
 use lsp_types::DiagnosticSeverity;
 use lsp_types::DiagnosticTag;
+use lsp_types::DocumentDiagnosticReport;
+use lsp_types::FullDocumentDiagnosticReport;
 use lsp_types::Location as LspLocation;
 use lsp_types::Position;
+use lsp_types::PreviousResultId;
 use lsp_types::PublishDiagnosticsParams;
 use lsp_types::Range;
+use lsp_types::RelatedFullDocumentDiagnosticReport;
+use lsp_types::RelatedUnchangedDocumentDiagnosticReport;
+use lsp_types::UnchangedDocumentDiagnosticReport;
 use lsp_types::Url;
+use lsp_types::WorkspaceDiagnosticReport;
+use lsp_types::WorkspaceDocumentDiagnosticReport;
+use lsp_types::WorkspaceFullDocumentDiagnosticReport;
+use lsp_types::WorkspaceUnchangedDocumentDiagnosticReport;
 use relay_compiler::errors::BuildProjectError;
 use relay_compiler::errors::Error;
 use relay_compiler::source_for_location;
 use relay_compiler::FsSourceReader;
 use relay_compiler::SourceReader;
+use serde_json::Value;
 
 use crate::lsp_process_error::LSPProcessResult;
 
@@ -56,11 +75,37 @@ struct DiagnosticSet {
     regular_diagnostics: Vec<Diagnostic>,
 }
 
+impl DiagnosticSet {
+    /// The quick diagnostics, followed by the regular diagnostics that are not
+    /// already reported as quick diagnostics.
+    fn merged_diagnostics(&self) -> Vec<Diagnostic> {
+        let mut next_diagnostics = self.quick_diagnostics.clone();
+        for diagnostic in &self.regular_diagnostics {
+            if !next_diagnostics
+                .iter()
+                .any(|prev_diag| prev_diag.eq(diagnostic))
+            {
+                next_diagnostics.push(diagnostic.clone());
+            }
+        }
+        next_diagnostics
+    }
+}
+
 pub struct DiagnosticReporter {
     active_diagnostics: DashMap<Url, DiagnosticSet>,
     sender: Sender<Message>,
     root_dir: PathBuf,
     source_reader: Box<dyn SourceReader + Send + Sync>,
+    /// When the client pulls the diagnostics (LSP 3.17), changes bump the
+    /// result id of the document instead of being published.
+    pull_diagnostics: AtomicBool,
+    /// Whether the client should be asked to pull the diagnostics again
+    /// when they change.
+    refresh_support: AtomicBool,
+    has_pending_refresh: AtomicBool,
+    result_ids: DashMap<Url, u64>,
+    next_result_id: AtomicU64,
 }
 
 impl DiagnosticReporter {
@@ -70,9 +115,22 @@ impl DiagnosticReporter {
             sender,
             root_dir,
             source_reader: Box::new(FsSourceReader),
+            pull_diagnostics: AtomicBool::new(false),
+            refresh_support: AtomicBool::new(false),
+            has_pending_refresh: AtomicBool::new(false),
+            result_ids: Default::default(),
+            next_result_id: AtomicU64::new(1),
         }
     }
 
+    /// Stops publishing the diagnostics, for clients that pull them with the
+    /// `textDocument/diagnostic` and `workspace/diagnostic` requests.
+    pub fn enable_pull_diagnostics(&self, refresh_support: bool) {
+        self.pull_diagnostics.store(true, Ordering::Relaxed);
+        self.refresh_support
+            .store(refresh_support, Ordering::Relaxed);
+    }
+
     pub fn clear_regular_diagnostics(&self) {
         for mut r in self.active_diagnostics.iter_mut() {
             let (url, diagnostics) = r.pair_mut();
@@ -81,7 +139,8 @@ impl DiagnosticReporter {
         }
         self.active_diagnostics.retain(|_, diagnostics| {
             !diagnostics.regular_diagnostics.is_empty() || !diagnostics.quick_diagnostics.is_empty()
-        })
+        });
+        self.refresh_pulled_diagnostics();
     }
 
     pub fn report_error(&self, error: &Error) {
@@ -116,6 +175,7 @@ impl DiagnosticReporter {
             let (url, diagnostics) = r.pair();
             self.publish_diagnostics_set(url, diagnostics)
         }
+        self.refresh_pulled_diagnostics();
     }
 
     fn add_diagnostic(&self, url: Url, diagnostic: Diagnostic) {
@@ -146,6 +206,7 @@ impl DiagnosticReporter {
                 }
             }
         }
+        self.refresh_pulled_diagnostics();
     }
 
     pub fn clear_quick_diagnostics_for_url(&self, url: &Url) {
@@ -155,26 +216,134 @@ impl DiagnosticReporter {
                 self.publish_diagnostics_set(url, &diagnostics)
             }
         }
+        self.refresh_pulled_diagnostics();
     }
 
     fn publish_diagnostics_set(&self, url: &Url, diagnostics: &DiagnosticSet) {
-        let mut next_diagnostics = diagnostics.quick_diagnostics.clone();
-        for diagnostic in &diagnostics.regular_diagnostics {
-            if !next_diagnostics
-                .iter()
-                .any(|prev_diag| prev_diag.eq(diagnostic))
-            {
-                next_diagnostics.push(diagnostic.clone());
-            }
+        if self.pull_diagnostics.load(Ordering::Relaxed) {
+            self.result_ids.insert(
+                url.clone(),
+                self.next_result_id.fetch_add(1, Ordering::Relaxed),
+            );
+            self.has_pending_refresh.store(true, Ordering::Relaxed);
+            return;
         }
         let params = PublishDiagnosticsParams {
-            diagnostics: next_diagnostics,
+            diagnostics: diagnostics.merged_diagnostics(),
             uri: url.clone(),
             version: None,
         };
         publish_diagnostic(params, &self.sender).ok();
     }
 
+    /// Asks the client to pull the diagnostics again, once per batch of
+    /// changes.
+    fn refresh_pulled_diagnostics(&self) {
+        if !self.refresh_support.load(Ordering::Relaxed)
+            || !self.has_pending_refresh.swap(false, Ordering::Relaxed)
+        {
+            return;
+        }
+        let request = ServerRequest::new(
+            RequestId::from(format!(
+                "relay-diagnostic-refresh-{}",
+                self.next_result_id.load(Ordering::Relaxed)
+            )),
+            WorkspaceDiagnosticRefresh::METHOD.to_string(),
+            (),
+        );
+        self.sender.send(Message::Request(request)).unwrap_or(());
+    }
+
+    fn get_result_id(&self, url: &Url) -> String {
+        self.result_ids
+            .get(url)
+            .map_or(0, |result_id| *result_id)
+            .to_string()
+    }
+
+    /// Returns the diagnostics of a document, or an unchanged report if the
+    /// client already has the latest ones.
+    pub fn get_document_diagnostic_report(
+        &self,
+        url: &Url,
+        previous_result_id: Option<&str>,
+    ) -> DocumentDiagnosticReport {
+        let result_id = self.get_result_id(url);
+        if previous_result_id == Some(result_id.as_str()) {
+            return DocumentDiagnosticReport::Unchanged(RelatedUnchangedDocumentDiagnosticReport {
+                related_documents: None,
+                unchanged_document_diagnostic_report: UnchangedDocumentDiagnosticReport {
+                    result_id,
+                },
+            });
+        }
+        DocumentDiagnosticReport::Full(RelatedFullDocumentDiagnosticReport {
+            related_documents: None,
+            full_document_diagnostic_report: FullDocumentDiagnosticReport {
+                result_id: Some(result_id),
+                items: self
+                    .active_diagnostics
+                    .get(url)
+                    .map_or_else(Vec::new, |diagnostics| diagnostics.merged_diagnostics()),
+            },
+        })
+    }
+
+    /// Returns the diagnostics of every document that has some, and clears the
+    /// diagnostics the client has for documents that no longer have any.
+    pub fn get_workspace_diagnostic_report(
+        &self,
+        previous_result_ids: &[PreviousResultId],
+    ) -> WorkspaceDiagnosticReport {
+        let mut items = vec![];
+        for entry in self.active_diagnostics.iter() {
+            let (url, diagnostics) = entry.pair();
+            let result_id = self.get_result_id(url);
+            let is_unchanged = previous_result_ids
+                .iter()
+                .any(|previous| &previous.uri == url && previous.value == result_id);
+            items.push(if is_unchanged {
+                WorkspaceDocumentDiagnosticReport::Unchanged(
+                    WorkspaceUnchangedDocumentDiagnosticReport {
+                        uri: url.clone(),
+                        version: None,
+                        unchanged_document_diagnostic_report: UnchangedDocumentDiagnosticReport {
+                            result_id,
+                        },
+                    },
+                )
+            } else {
+                WorkspaceDocumentDiagnosticReport::Full(WorkspaceFullDocumentDiagnosticReport {
+                    uri: url.clone(),
+                    version: None,
+                    full_document_diagnostic_report: FullDocumentDiagnosticReport {
+                        result_id: Some(result_id),
+                        items: diagnostics.merged_diagnostics(),
+                    },
+                })
+            });
+        }
+        for previous in previous_result_ids {
+            if !self.active_diagnostics.contains_key(&previous.uri) {
+                let result_id = self.get_result_id(&previous.uri);
+                if previous.value != result_id {
+                    items.push(WorkspaceDocumentDiagnosticReport::Full(
+                        WorkspaceFullDocumentDiagnosticReport {
+                            uri: previous.uri.clone(),
+                            version: None,
+                            full_document_diagnostic_report: FullDocumentDiagnosticReport {
+                                result_id: Some(result_id),
+                                items: vec![],
+                            },
+                        },
+                    ));
+                }
+            }
+        }
+        WorkspaceDiagnosticReport { items }
+    }
+
     #[cfg(test)]
     fn set_source_reader(&mut self, source_reader: Box<dyn SourceReader + Send + Sync>) {
         self.source_reader = source_reader;
@@ -211,7 +380,7 @@ impl DiagnosticReporter {
 
         Diagnostic {
             code: None,
-            data: get_diagnostics_data(diagnostic),
+            data: get_lsp_diagnostic_data(diagnostic),
             message: diagnostic.message().to_string(),
             range: text_source.to_span_range(diagnostic.location().span()),
             related_information: if related_information.is_empty() {
@@ -318,6 +487,24 @@ fn is_sub_range(inner: Range, outer: Range) -> bool {
         && (outer.end.character >= inner.end.character && outer.end.line >= inner.end.line)
 }
 
+/// The data of a diagnostic: the replacements of the text in its range,
+/// followed by the fix of the validation error, if any.
+fn get_lsp_diagnostic_data(diagnostic: &CompilerDiagnostic) -> Option<Value> {
+    let data = get_diagnostics_data(diagnostic);
+    let Some(fix) =
+        ValidationFix::from_diagnostic(diagnostic).and_then(|fix| serde_json::to_value(fix).ok())
+    else {
+        return data;
+    };
+    match data {
+        Some(Value::Array(mut items)) => {
+            items.push(fix);
+            Some(Value::Array(items))
+        }
+        _ => Some(Value::Array(vec![fix])),
+    }
+}
+
 /// Publish diagnostics to the client
 pub fn publish_diagnostic(
     diagnostic_params: PublishDiagnosticsParams,
@@ -338,10 +525,18 @@ mod tests {
     use common::Location;
     use common::SourceLocationKey;
     use common::Span;
+    use common::TextSource;
+    use graphql_ir::ValidationFix;
+    use graphql_ir::VariableName;
     use intern::string_key::Intern;
+    use lsp_server::Message;
+    use lsp_types::Diagnostic as LspDiagnostic;
+    use lsp_types::DocumentDiagnosticReport;
     use lsp_types::Position;
     use lsp_types::Range;
+    use lsp_types::Url;
     use relay_compiler::SourceReader;
+    use serde_json::json;
 
     use super::is_sub_range;
     use super::DiagnosticReporter;
@@ -372,6 +567,29 @@ mod tests {
         assert_eq!(reporter.active_diagnostics.len(), 1);
     }
 
+    #[test]
+    fn validation_fix_data_test() {
+        let (sender, _) = crossbeam::channel::unbounded();
+        let reporter = DiagnosticReporter::new(PathBuf::from("/tmp"), sender);
+        let diagnostic = ValidationFix::RemoveVariableDefinition {
+            variable_name: VariableName("id".intern()),
+        }
+        .attach_to(Diagnostic::error(
+            "test message",
+            Location::new(
+                SourceLocationKey::standalone("foo.graphql"),
+                Span { start: 0, end: 1 },
+            ),
+        ));
+
+        let diagnostic =
+            reporter.convert_diagnostic(&TextSource::from_whole_document("a"), &diagnostic);
+        assert_eq!(
+            diagnostic.data,
+            Some(json!([{"fix": "RemoveVariableDefinition", "variable_name": "id"}]))
+        );
+    }
+
     /// This test will assert that the message without URL (with generated source) won't be reported by LSPStatusReporter
     /// I'm not sure if this is the right behavior, but lets capture it here.
     #[test]
@@ -386,6 +604,45 @@ mod tests {
         assert_eq!(reporter.active_diagnostics.len(), 0);
     }
 
+    #[test]
+    fn pull_diagnostics_report_test() {
+        let root_dir = PathBuf::from("/tmp");
+        let (sender, receiver) = crossbeam::channel::unbounded();
+        let reporter = DiagnosticReporter::new(root_dir, sender);
+        reporter.enable_pull_diagnostics(true);
+        let url = Url::parse("file:///tmp/foo.js").unwrap();
+
+        reporter.update_quick_diagnostics_for_url(
+            &url,
+            vec![LspDiagnostic {
+                message: "test message".to_string(),
+                ..Default::default()
+            }],
+        );
+        assert_eq!(receiver.len(), 1);
+        assert!(matches!(receiver.recv().unwrap(), Message::Request(_)));
+
+        let result_id = match reporter.get_document_diagnostic_report(&url, None) {
+            DocumentDiagnosticReport::Full(report) => {
+                assert_eq!(report.full_document_diagnostic_report.items.len(), 1);
+                report.full_document_diagnostic_report.result_id.unwrap()
+            }
+            DocumentDiagnosticReport::Unchanged(_) => panic!("Expected a full report"),
+        };
+        assert!(matches!(
+            reporter.get_document_diagnostic_report(&url, Some(&result_id)),
+            DocumentDiagnosticReport::Unchanged(_)
+        ));
+
+        reporter.clear_quick_diagnostics_for_url(&url);
+        match reporter.get_document_diagnostic_report(&url, Some(&result_id)) {
+            DocumentDiagnosticReport::Full(report) => {
+                assert!(report.full_document_diagnostic_report.items.is_empty());
+            }
+            DocumentDiagnosticReport::Unchanged(_) => panic!("Expected a full report"),
+        }
+    }
+
     #[test]
     fn sub_range_inner_directly_below() {
         let cursor = Range::new(Position::new(106, 12), Position::new(106, 12));
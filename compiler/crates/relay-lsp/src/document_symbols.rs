@@ -0,0 +1,358 @@
+/*
+ * Copyright (c) Meta Platforms, Inc. and affiliates.
+ *
+ * This source code is licensed under the MIT license found in the
+ * LICENSE file in the root directory of this source tree.
+ */
+
+//! Utilities for providing the document symbol feature
+
+use common::Location;
+use graphql_syntax::Alias;
+use graphql_syntax::EnumValueDefinition;
+use graphql_syntax::ExecutableDefinition;
+use graphql_syntax::FieldDefinition;
+use graphql_syntax::Identifier;
+use graphql_syntax::InputValueDefinition;
+use graphql_syntax::List;
+use graphql_syntax::OperationKind;
+use graphql_syntax::SchemaDocument;
+use graphql_syntax::Selection;
+use graphql_syntax::TypeSystemDefinition;
+use lsp_types::request::DocumentSymbolRequest;
+use lsp_types::request::Request;
+use lsp_types::DocumentSymbol;
+use lsp_types::DocumentSymbolResponse;
+use lsp_types::SymbolKind;
+
+use crate::lsp_runtime_error::LSPRuntimeError;
+use crate::lsp_runtime_error::LSPRuntimeResult;
+use crate::server::GlobalState;
+use crate::utils::is_file_uri_in_dir;
+
+/// A symbol of the outline of a GraphQL document, with Relay locations.
+#[derive(Debug)]
+pub struct GraphQLSymbol {
+    pub name: String,
+    pub detail: Option<String>,
+    pub kind: SymbolKind,
+    /// The location of the whole definition or selection.
+    pub location: Location,
+    /// The location of the name of the definition or selection.
+    pub name_location: Location,
+    pub children: Vec<GraphQLSymbol>,
+}
+
+pub fn on_document_symbol(
+    state: &impl GlobalState,
+    params: <DocumentSymbolRequest as Request>::Params,
+) -> LSPRuntimeResult<<DocumentSymbolRequest as Request>::Result> {
+    let uri = params.text_document.uri;
+    if !is_file_uri_in_dir(state.root_dir(), &uri) {
+        return Err(LSPRuntimeError::ExpectedError);
+    }
+
+    let symbols = match state.resolve_schema_document(&uri)? {
+        Some(document) => get_schema_document_symbols(&document),
+        None => get_executable_document_symbols(&state.resolve_executable_definitions(&uri)?),
+    };
+    let symbols = symbols
+        .into_iter()
+        .map(|symbol| to_document_symbol(state, symbol))
+        .collect::<LSPRuntimeResult<Vec<_>>>()?;
+
+    Ok(Some(DocumentSymbolResponse::Nested(symbols)))
+}
+
+fn to_document_symbol(
+    state: &impl GlobalState,
+    symbol: GraphQLSymbol,
+) -> LSPRuntimeResult<DocumentSymbol> {
+    let range = state
+        .transform_relay_location_in_editor_to_lsp_location(symbol.location)?
+        .range;
+    let selection_range = state
+        .transform_relay_location_in_editor_to_lsp_location(symbol.name_location)?
+        .range;
+    let children = symbol
+        .children
+        .into_iter()
+        .map(|child| to_document_symbol(state, child))
+        .collect::<LSPRuntimeResult<Vec<_>>>()?;
+    #[allow(deprecated)]
+    Ok(DocumentSymbol {
+        name: symbol.name,
+        detail: symbol.detail,
+        kind: symbol.kind,
+        tags: None,
+        deprecated: None,
+        range,
+        selection_range,
+        children: if children.is_empty() {
+            None
+        } else {
+            Some(children)
+        },
+    })
+}
+
+/// Returns the outline of the operations and fragments, with their selections.
+pub fn get_executable_document_symbols(definitions: &[ExecutableDefinition]) -> Vec<GraphQLSymbol> {
+    definitions
+        .iter()
+        .map(|definition| match definition {
+            ExecutableDefinition::Operation(operation) => {
+                let kind = operation.operation_kind();
+                let (name, name_span) = match (&operation.name, &operation.operation) {
+                    (Some(name), _) => (name.value.to_string(), name.span),
+                    (None, Some((token, _))) => (format!("{} (anonymous)", kind), token.span),
+                    (None, None) => (
+                        format!("{} (anonymous)", kind),
+                        operation.selections.start.span,
+                    ),
+                };
+                GraphQLSymbol {
+                    name,
+                    detail: Some(kind.to_string()),
+                    kind: match kind {
+                        OperationKind::Query => SymbolKind::FUNCTION,
+                        OperationKind::Mutation | OperationKind::Subscription => SymbolKind::METHOD,
+                    },
+                    location: operation.location,
+                    name_location: operation.location.with_span(name_span),
+                    children: get_selection_symbols(&operation.location, &operation.selections),
+                }
+            }
+            ExecutableDefinition::Fragment(fragment) => GraphQLSymbol {
+                name: fragment.name.value.to_string(),
+                detail: Some(fragment.type_condition.to_string()),
+                kind: SymbolKind::CLASS,
+                location: fragment.location,
+                name_location: fragment.location.with_span(fragment.name.span),
+                children: get_selection_symbols(&fragment.location, &fragment.selections),
+            },
+        })
+        .collect()
+}
+
+fn get_selection_symbols(
+    definition_location: &Location,
+    selections: &List<Selection>,
+) -> Vec<GraphQLSymbol> {
+    selections
+        .items
+        .iter()
+        .map(|selection| {
+            let (name, kind, name_span, children) = match selection {
+                Selection::ScalarField(field) => (
+                    get_field_symbol_name(field.alias.as_ref(), &field.name),
+                    SymbolKind::FIELD,
+                    field.name.span,
+                    vec![],
+                ),
+                Selection::LinkedField(field) => (
+                    get_field_symbol_name(field.alias.as_ref(), &field.name),
+                    SymbolKind::FIELD,
+                    field.name.span,
+                    get_selection_symbols(definition_location, &field.selections),
+                ),
+                Selection::FragmentSpread(spread) => (
+                    format!("...{}", spread.name.value),
+                    SymbolKind::OBJECT,
+                    spread.name.span,
+                    vec![],
+                ),
+                Selection::InlineFragment(fragment) => (
+                    match &fragment.type_condition {
+                        Some(type_condition) => format!("... {}", type_condition),
+                        None => "...".to_string(),
+                    },
+                    SymbolKind::NAMESPACE,
+                    fragment
+                        .type_condition
+                        .as_ref()
+                        .map_or(fragment.spread.span, |type_condition| type_condition.span),
+                    get_selection_symbols(definition_location, &fragment.selections),
+                ),
+            };
+            GraphQLSymbol {
+                name,
+                detail: None,
+                kind,
+                location: definition_location.with_span(selection.span()),
+                name_location: definition_location.with_span(name_span),
+                children,
+            }
+        })
+        .collect()
+}
+
+fn get_field_symbol_name(alias: Option<&Alias>, name: &Identifier) -> String {
+    match alias {
+        Some(alias) => format!("{}: {}", alias.alias.value, name.value),
+        None => name.value.to_string(),
+    }
+}
+
+/// Returns the outline of the types and directives of a schema document, with
+/// their fields and values.
+pub fn get_schema_document_symbols(document: &SchemaDocument) -> Vec<GraphQLSymbol> {
+    let location = &document.location;
+    document
+        .definitions
+        .iter()
+        .filter_map(|definition| {
+            let (name, kind, span, children) = match definition {
+                TypeSystemDefinition::SchemaDefinition(_)
+                | TypeSystemDefinition::SchemaExtension(_) => return None,
+                TypeSystemDefinition::ObjectTypeDefinition(definition) => (
+                    &definition.name,
+                    SymbolKind::CLASS,
+                    definition.span,
+                    get_field_symbols(location, &definition.fields),
+                ),
+                TypeSystemDefinition::ObjectTypeExtension(extension) => (
+                    &extension.name,
+                    SymbolKind::CLASS,
+                    extension.span,
+                    get_field_symbols(location, &extension.fields),
+                ),
+                TypeSystemDefinition::InterfaceTypeDefinition(definition) => (
+                    &definition.name,
+                    SymbolKind::INTERFACE,
+                    definition.span,
+                    get_field_symbols(location, &definition.fields),
+                ),
+                TypeSystemDefinition::InterfaceTypeExtension(extension) => (
+                    &extension.name,
+                    SymbolKind::INTERFACE,
+                    extension.span,
+                    get_field_symbols(location, &extension.fields),
+                ),
+                TypeSystemDefinition::UnionTypeDefinition(definition) => (
+                    &definition.name,
+                    SymbolKind::OBJECT,
+                    definition.span,
+                    vec![],
+                ),
+                TypeSystemDefinition::UnionTypeExtension(extension) => {
+                    (&extension.name, SymbolKind::OBJECT, extension.span, vec![])
+                }
+                TypeSystemDefinition::InputObjectTypeDefinition(definition) => (
+                    &definition.name,
+                    SymbolKind::STRUCT,
+                    definition.span,
+                    get_input_value_symbols(location, &definition.fields),
+                ),
+                TypeSystemDefinition::InputObjectTypeExtension(extension) => (
+                    &extension.name,
+                    SymbolKind::STRUCT,
+                    extension.span,
+                    get_input_value_symbols(location, &extension.fields),
+                ),
+                TypeSystemDefinition::EnumTypeDefinition(definition) => (
+                    &definition.name,
+                    SymbolKind::ENUM,
+                    definition.span,
+                    get_enum_value_symbols(location, &definition.values),
+                ),
+                TypeSystemDefinition::EnumTypeExtension(extension) => (
+                    &extension.name,
+                    SymbolKind::ENUM,
+                    extension.span,
+                    get_enum_value_symbols(location, &extension.values),
+                ),
+                TypeSystemDefinition::ScalarTypeDefinition(definition) => (
+                    &definition.name,
+                    SymbolKind::TYPE_PARAMETER,
+                    definition.span,
+                    vec![],
+                ),
+                TypeSystemDefinition::ScalarTypeExtension(extension) => (
+                    &extension.name,
+                    SymbolKind::TYPE_PARAMETER,
+                    extension.span,
+                    vec![],
+                ),
+                TypeSystemDefinition::DirectiveDefinition(definition) => {
+                    return Some(GraphQLSymbol {
+                        name: format!("@{}", definition.name.value),
+                        detail: None,
+                        kind: SymbolKind::OPERATOR,
+                        location: location.with_span(definition.span),
+                        name_location: location.with_span(definition.name.span),
+                        children: get_input_value_symbols(location, &definition.arguments),
+                    });
+                }
+            };
+            Some(GraphQLSymbol {
+                name: name.value.to_string(),
+                detail: None,
+                kind,
+                location: location.with_span(span),
+                name_location: location.with_span(name.span),
+                children,
+            })
+        })
+        .collect()
+}
+
+fn get_field_symbols(
+    location: &Location,
+    fields: &Option<List<FieldDefinition>>,
+) -> Vec<GraphQLSymbol> {
+    fields.as_ref().map_or(vec![], |fields| {
+        fields
+            .items
+            .iter()
+            .map(|field| GraphQLSymbol {
+                name: field.name.value.to_string(),
+                detail: Some(field.type_.to_string()),
+                kind: SymbolKind::FIELD,
+                location: location.with_span(field.span),
+                name_location: location.with_span(field.name.span),
+                children: get_input_value_symbols(location, &field.arguments),
+            })
+            .collect()
+    })
+}
+
+fn get_input_value_symbols(
+    location: &Location,
+    values: &Option<List<InputValueDefinition>>,
+) -> Vec<GraphQLSymbol> {
+    values.as_ref().map_or(vec![], |values| {
+        values
+            .items
+            .iter()
+            .map(|value| GraphQLSymbol {
+                name: value.name.value.to_string(),
+                detail: Some(value.type_.to_string()),
+                kind: SymbolKind::PROPERTY,
+                location: location.with_span(value.span),
+                name_location: location.with_span(value.name.span),
+                children: vec![],
+            })
+            .collect()
+    })
+}
+
+fn get_enum_value_symbols(
+    location: &Location,
+    values: &Option<List<EnumValueDefinition>>,
+) -> Vec<GraphQLSymbol> {
+    values.as_ref().map_or(vec![], |values| {
+        values
+            .items
+            .iter()
+            .map(|value| GraphQLSymbol {
+                name: value.name.value.to_string(),
+                detail: None,
+                kind: SymbolKind::ENUM_MEMBER,
+                location: location.with_span(value.span),
+                name_location: location.with_span(value.name.span),
+                children: vec![],
+            })
+            .collect()
+    })
+}
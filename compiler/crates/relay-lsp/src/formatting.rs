@@ -0,0 +1,66 @@
+/*
+ * Copyright (c) Meta Platforms, Inc. and affiliates.
+ *
+ * This source code is licensed under the MIT license found in the
+ * LICENSE file in the root directory of this source tree.
+ */
+
+//! Utilities for providing the formatting feature
+
+use lsp_types::request::Formatting;
+use lsp_types::request::RangeFormatting;
+use lsp_types::request::Request;
+use lsp_types::TextEdit;
+use relay_compiler::FormattedGraphQLTag;
+
+use crate::lsp_runtime_error::LSPRuntimeError;
+use crate::lsp_runtime_error::LSPRuntimeResult;
+use crate::server::GlobalState;
+use crate::utils::is_file_uri_in_dir;
+
+pub fn on_formatting(
+    state: &impl GlobalState,
+    params: <Formatting as Request>::Params,
+) -> LSPRuntimeResult<<Formatting as Request>::Result> {
+    let uri = params.text_document.uri;
+    if !is_file_uri_in_dir(state.root_dir(), &uri) {
+        return Err(LSPRuntimeError::ExpectedError);
+    }
+
+    let edits = state
+        .format_graphql_sources(&uri)?
+        .into_iter()
+        .map(to_text_edit)
+        .collect();
+
+    Ok(Some(edits))
+}
+
+/// Formats the `graphql` tags that overlap the range, or the whole document
+/// of a schema or extension file.
+pub fn on_range_formatting(
+    state: &impl GlobalState,
+    params: <RangeFormatting as Request>::Params,
+) -> LSPRuntimeResult<<RangeFormatting as Request>::Result> {
+    let uri = params.text_document.uri;
+    if !is_file_uri_in_dir(state.root_dir(), &uri) {
+        return Err(LSPRuntimeError::ExpectedError);
+    }
+
+    let range = params.range;
+    let edits = state
+        .format_graphql_sources(&uri)?
+        .into_iter()
+        .map(to_text_edit)
+        .filter(|edit| edit.range.start <= range.end && range.start <= edit.range.end)
+        .collect();
+
+    Ok(Some(edits))
+}
+
+fn to_text_edit(formatted_tag: FormattedGraphQLTag) -> TextEdit {
+    TextEdit {
+        range: formatted_tag.graphql_source.text_source().to_range(),
+        new_text: formatted_tag.formatted_text,
+    }
+}
@@ -7,13 +7,17 @@
 
 #![deny(clippy::all)]
 
+pub mod call_hierarchy;
 mod client;
-mod code_action;
+pub mod code_action;
+pub mod code_lens;
 pub mod completion;
 pub mod diagnostic_reporter;
 mod docblock_resolution_info;
+pub mod document_symbols;
 mod explore_schema_for_type;
 pub mod find_field_usages;
+mod formatting;
 pub mod goto_definition;
 mod graphql_tools;
 pub mod hover;
@@ -23,16 +27,20 @@ mod lsp_extra_data_provider;
 pub mod lsp_process_error;
 pub mod lsp_runtime_error;
 pub mod node_resolution_info;
+pub mod pull_diagnostics;
 pub mod references;
 pub mod rename;
 mod resolved_types_at_location;
 mod search_schema_items;
+pub mod semantic_tokens;
 mod server;
 mod shutdown;
+pub mod signature_help;
 mod status_reporter;
 pub mod status_updater;
 pub mod text_documents;
 pub mod utils;
+pub mod workspace_symbols;
 use std::sync::Arc;
 
 use common::PerfLogger;
@@ -55,6 +63,7 @@ use relay_compiler::config::Config;
 use relay_docblock::DocblockIr;
 use schema_documentation::SchemaDocumentation;
 use schema_documentation::SchemaDocumentationLoader;
+pub use server::build_ir_for_lsp;
 pub use server::GlobalState;
 pub use server::LSPNotificationDispatch;
 pub use server::LSPRequestDispatch;
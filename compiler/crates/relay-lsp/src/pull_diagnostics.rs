@@ -0,0 +1,38 @@
+/*
+ * Copyright (c) Meta Platforms, Inc. and affiliates.
+ *
+ * This source code is licensed under the MIT license found in the
+ * LICENSE file in the root directory of this source tree.
+ */
+
+//! Utilities for providing the pull diagnostics (LSP 3.17) feature
+
+use lsp_types::request::DocumentDiagnosticRequest;
+use lsp_types::request::Request;
+use lsp_types::request::WorkspaceDiagnosticRequest;
+use lsp_types::DocumentDiagnosticReportResult;
+use lsp_types::WorkspaceDiagnosticReportResult;
+
+use crate::lsp_runtime_error::LSPRuntimeResult;
+use crate::server::GlobalState;
+
+pub fn on_document_diagnostic(
+    state: &impl GlobalState,
+    params: <DocumentDiagnosticRequest as Request>::Params,
+) -> LSPRuntimeResult<<DocumentDiagnosticRequest as Request>::Result> {
+    Ok(DocumentDiagnosticReportResult::Report(
+        state.get_document_diagnostic_report(
+            &params.text_document.uri,
+            params.previous_result_id.as_deref(),
+        ),
+    ))
+}
+
+pub fn on_workspace_diagnostic(
+    state: &impl GlobalState,
+    params: <WorkspaceDiagnosticRequest as Request>::Params,
+) -> LSPRuntimeResult<<WorkspaceDiagnosticRequest as Request>::Result> {
+    Ok(WorkspaceDiagnosticReportResult::Report(
+        state.get_workspace_diagnostic_report(&params.previous_result_ids),
+    ))
+}
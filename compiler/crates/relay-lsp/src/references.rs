@@ -10,6 +10,7 @@
 use std::path::Path;
 
 use common::Location as IRLocation;
+use fnv::FnvHashMap;
 use graphql_ir::FragmentDefinitionName;
 use graphql_ir::FragmentSpread;
 use graphql_ir::Program;
@@ -113,18 +114,33 @@ fn get_references_response(
 }
 
 #[derive(Debug, Clone)]
-struct ReferenceFinder {
-    references: Vec<IRLocation>,
-    name: StringKey,
+pub(crate) struct ReferenceFinder {
+    references: FnvHashMap<FragmentDefinitionName, Vec<IRLocation>>,
 }
 
 impl ReferenceFinder {
-    fn get_references_to_fragment(program: &Program, name: StringKey) -> Vec<IRLocation> {
+    pub(crate) fn get_references_to_fragment(
+        program: &Program,
+        name: StringKey,
+    ) -> Vec<IRLocation> {
+        let name = FragmentDefinitionName(name);
+        Self::get_references_to_fragments(program, [name])
+            .remove(&name)
+            .unwrap_or_default()
+    }
+
+    /// Finds the references to several fragments with a single walk of the
+    /// program.
+    pub(crate) fn get_references_to_fragments(
+        program: &Program,
+        names: impl IntoIterator<Item = FragmentDefinitionName>,
+    ) -> FnvHashMap<FragmentDefinitionName, Vec<IRLocation>> {
         let mut reference_finder = ReferenceFinder {
-            references: vec![],
-            name,
+            references: names.into_iter().map(|name| (name, vec![])).collect(),
         };
-        reference_finder.visit_program(program);
+        if !reference_finder.references.is_empty() {
+            reference_finder.visit_program(program);
+        }
         reference_finder.references
     }
 }
@@ -135,8 +151,8 @@ impl Visitor for ReferenceFinder {
     const VISIT_DIRECTIVES: bool = false;
 
     fn visit_fragment_spread(&mut self, spread: &FragmentSpread) {
-        if spread.fragment.item == FragmentDefinitionName(self.name) {
-            self.references.push(spread.fragment.location);
+        if let Some(references) = self.references.get_mut(&spread.fragment.item) {
+            references.push(spread.fragment.location);
         }
     }
 }
@@ -0,0 +1,639 @@
+/*
+ * Copyright (c) Meta Platforms, Inc. and affiliates.
+ *
+ * This source code is licensed under the MIT license found in the
+ * LICENSE file in the root directory of this source tree.
+ */
+
+//! Utilities for providing the semantic tokens feature
+
+use common::Location;
+use common::NamedItem;
+use common::SourceLocationKey;
+use common::Span;
+use common::TextSource;
+use docblock_shared::RELAY_RESOLVER_DIRECTIVE_NAME;
+use graphql_ir::ARGUMENT_DEFINITION;
+use graphql_syntax::Argument;
+use graphql_syntax::ConstantValue;
+use graphql_syntax::Directive;
+use graphql_syntax::ExecutableDefinition;
+use graphql_syntax::ExecutableDocument;
+use graphql_syntax::List;
+use graphql_syntax::Selection;
+use graphql_syntax::TypeAnnotation;
+use graphql_syntax::Value;
+use graphql_syntax::VariableDefinition;
+use intern::string_key::StringKey;
+use lsp_types::request::Request;
+use lsp_types::request::SemanticTokensFullRequest;
+use lsp_types::request::SemanticTokensRangeRequest;
+use lsp_types::Position;
+use lsp_types::Range;
+use lsp_types::SemanticToken;
+use lsp_types::SemanticTokenModifier;
+use lsp_types::SemanticTokenType;
+use lsp_types::SemanticTokens;
+use lsp_types::SemanticTokensLegend;
+use lsp_types::SemanticTokensRangeResult;
+use lsp_types::SemanticTokensResult;
+use lsp_types::Url;
+use resolution_path::ArgumentParent;
+use resolution_path::ArgumentPath;
+use resolution_path::DirectivePath;
+use resolution_path::IdentParent;
+use resolution_path::IdentPath;
+use resolution_path::LinkedFieldPath;
+use resolution_path::ResolutionPath;
+use resolution_path::ResolvePosition;
+use resolution_path::ScalarFieldPath;
+use resolution_path::SelectionParent;
+use resolution_path::VariableIdentifierParent;
+use resolution_path::VariableIdentifierPath;
+use schema::SDLSchema;
+use schema::Schema;
+use schema::Type;
+
+use crate::lsp_runtime_error::LSPRuntimeError;
+use crate::lsp_runtime_error::LSPRuntimeResult;
+use crate::server::GlobalState;
+use crate::utils::is_file_uri_in_dir;
+
+/// The field or type is only defined in a client schema extension.
+pub const CLIENT_ONLY: SemanticTokenModifier = SemanticTokenModifier::new("clientOnly");
+/// The field is backed by a Relay Resolver.
+pub const RESOLVER: SemanticTokenModifier = SemanticTokenModifier::new("resolver");
+/// The variable is defined by the enclosing fragment, with fragment variable
+/// definitions or `@argumentDefinitions`, instead of the operation.
+pub const FRAGMENT_VARIABLE: SemanticTokenModifier = SemanticTokenModifier::new("fragmentVariable");
+
+const TOKEN_TYPES: [SemanticTokenType; 10] = [
+    SemanticTokenType::FUNCTION,
+    SemanticTokenType::CLASS,
+    SemanticTokenType::PROPERTY,
+    SemanticTokenType::TYPE,
+    SemanticTokenType::INTERFACE,
+    SemanticTokenType::ENUM,
+    SemanticTokenType::ENUM_MEMBER,
+    SemanticTokenType::DECORATOR,
+    SemanticTokenType::PARAMETER,
+    SemanticTokenType::VARIABLE,
+];
+
+const TOKEN_MODIFIERS: [SemanticTokenModifier; 5] = [
+    SemanticTokenModifier::DECLARATION,
+    SemanticTokenModifier::DEPRECATED,
+    CLIENT_ONLY,
+    RESOLVER,
+    FRAGMENT_VARIABLE,
+];
+
+/// A semantic token of a GraphQL document, with a Relay location.
+#[derive(Debug)]
+pub struct GraphQLSemanticToken {
+    pub location: Location,
+    pub token_type: SemanticTokenType,
+    pub modifiers: Vec<SemanticTokenModifier>,
+}
+
+pub fn get_semantic_tokens_legend() -> SemanticTokensLegend {
+    SemanticTokensLegend {
+        token_types: TOKEN_TYPES.to_vec(),
+        token_modifiers: TOKEN_MODIFIERS.to_vec(),
+    }
+}
+
+pub fn on_semantic_tokens_full(
+    state: &impl GlobalState,
+    params: <SemanticTokensFullRequest as Request>::Params,
+) -> LSPRuntimeResult<<SemanticTokensFullRequest as Request>::Result> {
+    let data = get_encoded_semantic_tokens(state, &params.text_document.uri, None)?;
+
+    Ok(Some(SemanticTokensResult::Tokens(SemanticTokens {
+        result_id: None,
+        data,
+    })))
+}
+
+pub fn on_semantic_tokens_range(
+    state: &impl GlobalState,
+    params: <SemanticTokensRangeRequest as Request>::Params,
+) -> LSPRuntimeResult<<SemanticTokensRangeRequest as Request>::Result> {
+    let data = get_encoded_semantic_tokens(state, &params.text_document.uri, Some(params.range))?;
+
+    Ok(Some(SemanticTokensRangeResult::Tokens(SemanticTokens {
+        result_id: None,
+        data,
+    })))
+}
+
+/// Returns the tokens of the `graphql` tags of a file, in the relative format
+/// of the protocol. With a range, only the tokens inside of it are returned.
+fn get_encoded_semantic_tokens(
+    state: &impl GlobalState,
+    uri: &Url,
+    range: Option<Range>,
+) -> LSPRuntimeResult<Vec<SemanticToken>> {
+    if !is_file_uri_in_dir(state.root_dir(), uri) {
+        return Err(LSPRuntimeError::ExpectedError);
+    }
+
+    let project_name = state.extract_project_name_from_url(uri)?;
+    let schema = state.get_schema(&project_name)?;
+    let definitions = state.resolve_executable_definitions(uri)?;
+    let sources = state.get_javascript_sources(uri)?;
+
+    let mut tokens = vec![];
+    for document in group_definitions_by_source(definitions) {
+        let SourceLocationKey::Embedded { index, .. } =
+            document.definitions[0].location().source_location()
+        else {
+            continue;
+        };
+        let Some(source) = sources.get(index as usize) else {
+            continue;
+        };
+        let line_index = LineIndex::new(source.text_source());
+        for token in get_semantic_tokens(&schema, &document) {
+            let (Some(token_type), Some(token_modifiers_bitset)) = (
+                get_token_type_index(&token.token_type),
+                get_token_modifiers_bitset(&token.modifiers),
+            ) else {
+                continue;
+            };
+            // Clients are not expected to support tokens that span several
+            // lines, so a token is split into one token per line.
+            for token_range in line_index.to_line_ranges(token.location.span()) {
+                if range.is_some_and(|range| {
+                    token_range.end < range.start || range.end < token_range.start
+                }) {
+                    continue;
+                }
+                tokens.push((token_range, token_type, token_modifiers_bitset));
+            }
+        }
+    }
+    tokens.sort_by_key(|(range, _, _)| range.start);
+
+    let mut data = Vec::with_capacity(tokens.len());
+    let mut last_line = 0;
+    let mut last_start = 0;
+    for (range, token_type, token_modifiers_bitset) in tokens {
+        let delta_line = range.start.line - last_line;
+        let delta_start = if delta_line == 0 {
+            range.start.character - last_start
+        } else {
+            range.start.character
+        };
+        data.push(SemanticToken {
+            delta_line,
+            delta_start,
+            length: range.end.character - range.start.character,
+            token_type,
+            token_modifiers_bitset,
+        });
+        last_line = range.start.line;
+        last_start = range.start.character;
+    }
+    Ok(data)
+}
+
+/// The index of the token type in the legend.
+fn get_token_type_index(token_type: &SemanticTokenType) -> Option<u32> {
+    TOKEN_TYPES
+        .iter()
+        .position(|legend_token_type| legend_token_type == token_type)
+        .map(|index| index as u32)
+}
+
+/// The bitset of the indices of the modifiers in the legend.
+fn get_token_modifiers_bitset(modifiers: &[SemanticTokenModifier]) -> Option<u32> {
+    modifiers.iter().try_fold(0, |bitset, modifier| {
+        TOKEN_MODIFIERS
+            .iter()
+            .position(|legend_modifier| legend_modifier == modifier)
+            .map(|index| bitset | 1 << index)
+    })
+}
+
+/// The offsets of the lines of the text of a `graphql` tag, to convert the
+/// spans of its tokens to positions in the file.
+struct LineIndex {
+    line_index: u32,
+    column_index: u32,
+    /// The character offsets of the starts and the ends of the lines,
+    /// without their line terminators.
+    lines: Vec<(u32, u32)>,
+}
+
+impl LineIndex {
+    fn new(text_source: &TextSource) -> Self {
+        let mut lines = vec![];
+        let mut line_start = 0;
+        let mut chars = text_source.text.chars().enumerate().peekable();
+        while let Some((index, chr)) = chars.next() {
+            // The line terminators of `TextSource::to_span_range`.
+            let is_newline = match chr {
+                '\u{000A}' | '\u{000D}' | '\u{2028}' | '\u{2029}' => {
+                    !matches!((chr, chars.peek()), ('\u{000D}', Some((_, '\u{000D}'))))
+                }
+                _ => false,
+            };
+            if is_newline {
+                lines.push((line_start, index as u32));
+                line_start = index as u32 + 1;
+            }
+        }
+        lines.push((line_start, text_source.text.chars().count() as u32));
+        Self {
+            line_index: text_source.line_index as u32,
+            column_index: text_source.column_index as u32,
+            lines,
+        }
+    }
+
+    fn get_line(&self, offset: u32) -> usize {
+        self.lines
+            .partition_point(|(line_start, _)| *line_start <= offset)
+            .saturating_sub(1)
+    }
+
+    fn to_position(&self, line: usize, offset: u32) -> Position {
+        let (line_start, _) = self.lines[line];
+        let character = offset.saturating_sub(line_start);
+        if line == 0 {
+            Position::new(self.line_index, self.column_index + character)
+        } else {
+            Position::new(self.line_index + line as u32, character)
+        }
+    }
+
+    /// The ranges of the parts of the span on each of its lines.
+    fn to_line_ranges(&self, span: Span) -> Vec<Range> {
+        let start_line = self.get_line(span.start);
+        let end_line = self.get_line(span.end);
+        (start_line..=end_line)
+            .filter_map(|line| {
+                let (line_start, line_end) = self.lines[line];
+                let start = span.start.max(line_start);
+                let end = span.end.min(line_end);
+                (start < end)
+                    .then(|| Range::new(self.to_position(line, start), self.to_position(line, end)))
+            })
+            .collect()
+    }
+}
+
+/// The definitions of a JavaScript file come from several `graphql` tags,
+/// whose spans overlap, so each tag is resolved as its own document.
+fn group_definitions_by_source(definitions: Vec<ExecutableDefinition>) -> Vec<ExecutableDocument> {
+    let mut documents: Vec<ExecutableDocument> = vec![];
+    for definition in definitions {
+        let location = definition.location();
+        match documents.last_mut() {
+            Some(document)
+                if document.definitions[0].location().source_location()
+                    == location.source_location() =>
+            {
+                document.span = Span::new(document.span.start, location.span().end);
+                document.definitions.push(definition);
+            }
+            _ => documents.push(ExecutableDocument {
+                span: location.span(),
+                definitions: vec![definition],
+            }),
+        }
+    }
+    documents
+}
+
+/// Returns the tokens of the names, variables and enum values of a document,
+/// classified with their resolution path and the schema.
+pub fn get_semantic_tokens(
+    schema: &SDLSchema,
+    document: &ExecutableDocument,
+) -> Vec<GraphQLSemanticToken> {
+    let mut tokens = vec![];
+    for definition in &document.definitions {
+        let fragment_variables = get_fragment_variables(definition);
+        let mut spans = vec![];
+        collect_definition_spans(definition, &mut spans);
+        for span in spans {
+            let path = definition.resolve(document.path(()), span);
+            if let Some((token_type, modifiers)) =
+                classify_resolution_path(schema, path, &fragment_variables)
+            {
+                tokens.push(GraphQLSemanticToken {
+                    location: Location::new(definition.location().source_location(), span),
+                    token_type,
+                    modifiers,
+                });
+            }
+        }
+    }
+    tokens
+}
+
+fn classify_resolution_path(
+    schema: &SDLSchema,
+    path: ResolutionPath<'_>,
+    fragment_variables: &[StringKey],
+) -> Option<(SemanticTokenType, Vec<SemanticTokenModifier>)> {
+    match path {
+        ResolutionPath::Ident(IdentPath {
+            inner: identifier,
+            parent,
+        }) => match parent {
+            IdentParent::OperationDefinitionName(_) => Some((
+                SemanticTokenType::FUNCTION,
+                vec![SemanticTokenModifier::DECLARATION],
+            )),
+            IdentParent::FragmentDefinitionName(_) => Some((
+                SemanticTokenType::CLASS,
+                vec![SemanticTokenModifier::DECLARATION],
+            )),
+            IdentParent::FragmentSpreadName(_) => Some((SemanticTokenType::CLASS, vec![])),
+            IdentParent::ScalarFieldAlias(_) | IdentParent::LinkedFieldAlias(_) => Some((
+                SemanticTokenType::PROPERTY,
+                vec![SemanticTokenModifier::DECLARATION],
+            )),
+            IdentParent::ScalarFieldName(ScalarFieldPath {
+                inner: _,
+                parent: selection_path,
+            }) => Some((
+                SemanticTokenType::PROPERTY,
+                get_field_modifiers(schema, &selection_path.parent, identifier.value),
+            )),
+            IdentParent::LinkedFieldName(LinkedFieldPath {
+                inner: _,
+                parent: selection_path,
+            }) => Some((
+                SemanticTokenType::PROPERTY,
+                get_field_modifiers(schema, &selection_path.parent, identifier.value),
+            )),
+            IdentParent::TypeConditionType(_) | IdentParent::NamedTypeAnnotation(_) => {
+                Some(classify_type(schema, identifier.value))
+            }
+            IdentParent::DirectiveName(_) => Some((SemanticTokenType::DECORATOR, vec![])),
+            IdentParent::ArgumentName(ArgumentPath {
+                inner: _,
+                parent: ArgumentParent::ConstantObject(_),
+            })
+            | IdentParent::ConstantArgumentKey(_) => Some((SemanticTokenType::PROPERTY, vec![])),
+            IdentParent::ArgumentName(ArgumentPath {
+                inner: _,
+                parent:
+                    ArgumentParent::Directive(DirectivePath {
+                        inner: directive,
+                        parent: _,
+                    }),
+            }) if directive.name.value == ARGUMENT_DEFINITION.0 => Some((
+                SemanticTokenType::VARIABLE,
+                vec![SemanticTokenModifier::DECLARATION, FRAGMENT_VARIABLE],
+            )),
+            IdentParent::ArgumentName(_) => Some((SemanticTokenType::PARAMETER, vec![])),
+            _ => None,
+        },
+        ResolutionPath::VariableIdentifier(VariableIdentifierPath {
+            inner: variable,
+            parent,
+        }) => {
+            let mut modifiers = vec![];
+            if matches!(parent, VariableIdentifierParent::VariableDefinition(_)) {
+                modifiers.push(SemanticTokenModifier::DECLARATION);
+            }
+            if fragment_variables.contains(&variable.name) {
+                modifiers.push(FRAGMENT_VARIABLE);
+            }
+            Some((SemanticTokenType::VARIABLE, modifiers))
+        }
+        ResolutionPath::ConstantEnum(_) => Some((SemanticTokenType::ENUM_MEMBER, vec![])),
+        _ => None,
+    }
+}
+
+fn get_field_modifiers(
+    schema: &SDLSchema,
+    selection_parent: &SelectionParent<'_>,
+    field_name: StringKey,
+) -> Vec<SemanticTokenModifier> {
+    let Some(field) = selection_parent
+        .find_parent_type(schema)
+        .and_then(|parent_type| schema.named_field(parent_type, field_name))
+        .map(|field_id| schema.field(field_id))
+    else {
+        return vec![];
+    };
+
+    let mut modifiers = vec![];
+    if field.deprecated().is_some() {
+        modifiers.push(SemanticTokenModifier::DEPRECATED);
+    }
+    if field.is_extension {
+        modifiers.push(CLIENT_ONLY);
+    }
+    if field
+        .directives
+        .named(*RELAY_RESOLVER_DIRECTIVE_NAME)
+        .is_some()
+    {
+        modifiers.push(RESOLVER);
+    }
+    modifiers
+}
+
+fn classify_type(
+    schema: &SDLSchema,
+    type_name: StringKey,
+) -> (SemanticTokenType, Vec<SemanticTokenModifier>) {
+    let Some(type_) = schema.get_type(type_name) else {
+        return (SemanticTokenType::TYPE, vec![]);
+    };
+    let token_type = match type_ {
+        Type::Interface(_) => SemanticTokenType::INTERFACE,
+        Type::Enum(_) => SemanticTokenType::ENUM,
+        _ => SemanticTokenType::TYPE,
+    };
+    let modifiers = if schema.is_extension_type(type_) {
+        vec![CLIENT_ONLY]
+    } else {
+        vec![]
+    };
+    (token_type, modifiers)
+}
+
+/// Returns the variables that a fragment defines itself.
+fn get_fragment_variables(definition: &ExecutableDefinition) -> Vec<StringKey> {
+    let ExecutableDefinition::Fragment(fragment) = definition else {
+        return vec![];
+    };
+    let mut variables = fragment
+        .variable_definitions
+        .iter()
+        .flat_map(|list| list.items.iter())
+        .map(|variable_definition| variable_definition.name.name)
+        .collect::<Vec<_>>();
+    for directive in &fragment.directives {
+        if directive.name.value == ARGUMENT_DEFINITION.0 {
+            if let Some(arguments) = &directive.arguments {
+                variables.extend(arguments.items.iter().map(|argument| argument.name.value));
+            }
+        }
+    }
+    variables
+}
+
+fn collect_definition_spans(definition: &ExecutableDefinition, spans: &mut Vec<Span>) {
+    match definition {
+        ExecutableDefinition::Operation(operation) => {
+            if let Some(name) = &operation.name {
+                spans.push(name.span);
+            }
+            collect_variable_definition_spans(operation.variable_definitions.as_ref(), spans);
+            collect_directive_spans(&operation.directives, spans);
+            collect_selection_spans(&operation.selections, spans);
+        }
+        ExecutableDefinition::Fragment(fragment) => {
+            spans.push(fragment.name.span);
+            collect_variable_definition_spans(fragment.variable_definitions.as_ref(), spans);
+            spans.push(fragment.type_condition.type_.span);
+            collect_directive_spans(&fragment.directives, spans);
+            collect_selection_spans(&fragment.selections, spans);
+        }
+    }
+}
+
+fn collect_variable_definition_spans(
+    variable_definitions: Option<&List<VariableDefinition>>,
+    spans: &mut Vec<Span>,
+) {
+    for variable_definition in variable_definitions.iter().flat_map(|list| &list.items) {
+        spans.push(variable_definition.name.span);
+        collect_type_annotation_spans(&variable_definition.type_, spans);
+        if let Some(default_value) = &variable_definition.default_value {
+            collect_constant_value_spans(&default_value.value, spans);
+        }
+        collect_directive_spans(&variable_definition.directives, spans);
+    }
+}
+
+fn collect_type_annotation_spans(type_annotation: &TypeAnnotation, spans: &mut Vec<Span>) {
+    match type_annotation {
+        TypeAnnotation::Named(named) => spans.push(named.name.span),
+        TypeAnnotation::List(list) => collect_type_annotation_spans(&list.type_, spans),
+        TypeAnnotation::NonNull(non_null) => collect_type_annotation_spans(&non_null.type_, spans),
+    }
+}
+
+fn collect_selection_spans(selections: &List<Selection>, spans: &mut Vec<Span>) {
+    for selection in &selections.items {
+        match selection {
+            Selection::ScalarField(field) => {
+                if let Some(alias) = &field.alias {
+                    spans.push(alias.alias.span);
+                }
+                spans.push(field.name.span);
+                collect_argument_spans(field.arguments.as_ref(), spans);
+                collect_directive_spans(&field.directives, spans);
+            }
+            Selection::LinkedField(field) => {
+                if let Some(alias) = &field.alias {
+                    spans.push(alias.alias.span);
+                }
+                spans.push(field.name.span);
+                collect_argument_spans(field.arguments.as_ref(), spans);
+                collect_directive_spans(&field.directives, spans);
+                collect_selection_spans(&field.selections, spans);
+            }
+            Selection::FragmentSpread(spread) => {
+                spans.push(spread.name.span);
+                collect_argument_spans(spread.arguments.as_ref(), spans);
+                collect_directive_spans(&spread.directives, spans);
+            }
+            Selection::InlineFragment(fragment) => {
+                if let Some(type_condition) = &fragment.type_condition {
+                    spans.push(type_condition.type_.span);
+                }
+                collect_directive_spans(&fragment.directives, spans);
+                collect_selection_spans(&fragment.selections, spans);
+            }
+        }
+    }
+}
+
+fn collect_directive_spans(directives: &[Directive], spans: &mut Vec<Span>) {
+    for directive in directives {
+        spans.push(directive.name.span);
+        collect_argument_spans(directive.arguments.as_ref(), spans);
+    }
+}
+
+fn collect_argument_spans(arguments: Option<&List<Argument>>, spans: &mut Vec<Span>) {
+    for argument in arguments.iter().flat_map(|list| &list.items) {
+        spans.push(argument.name.span);
+        collect_value_spans(&argument.value, spans);
+    }
+}
+
+fn collect_value_spans(value: &Value, spans: &mut Vec<Span>) {
+    match value {
+        Value::Constant(constant) => collect_constant_value_spans(constant, spans),
+        Value::Variable(variable) => spans.push(variable.span),
+        Value::List(list) => {
+            for item in &list.items {
+                collect_value_spans(item, spans);
+            }
+        }
+        Value::Object(object) => collect_argument_spans(Some(object), spans),
+    }
+}
+
+fn collect_constant_value_spans(value: &ConstantValue, spans: &mut Vec<Span>) {
+    match value {
+        ConstantValue::Enum(node) => spans.push(node.token.span),
+        ConstantValue::List(list) => {
+            for item in &list.items {
+                collect_constant_value_spans(item, spans);
+            }
+        }
+        ConstantValue::Object(object) => {
+            for argument in &object.items {
+                spans.push(argument.name.span);
+                collect_constant_value_spans(&argument.value, spans);
+            }
+        }
+        _ => {}
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_line_ranges_of_single_line_span() {
+        let line_index = LineIndex::new(&TextSource::new("\n  fragment Foo on User", 3, 10));
+        assert_eq!(
+            line_index.to_line_ranges(Span::new(12, 15)),
+            vec![Range::new(Position::new(4, 11), Position::new(4, 14))]
+        );
+
+        let line_index = LineIndex::new(&TextSource::new("fragment Foo on User", 3, 10));
+        assert_eq!(
+            line_index.to_line_ranges(Span::new(9, 12)),
+            vec![Range::new(Position::new(3, 19), Position::new(3, 22))]
+        );
+    }
+
+    #[test]
+    fn test_line_ranges_of_multi_line_span() {
+        let line_index = LineIndex::new(&TextSource::new("  id(arg: $\n    var)", 3, 10));
+        assert_eq!(
+            line_index.to_line_ranges(Span::new(10, 19)),
+            vec![
+                Range::new(Position::new(3, 20), Position::new(3, 21)),
+                Range::new(Position::new(4, 0), Position::new(4, 7)),
+            ]
+        );
+    }
+}
@@ -31,6 +31,7 @@ use lsp_server::Notification;
 use lsp_server::Response as ServerResponse;
 use lsp_server::ResponseError;
 pub use lsp_state::build_ir_for_lsp;
+pub use lsp_state::build_ir_for_lsp_with_external_fragments;
 pub use lsp_state::GlobalState;
 pub use lsp_state::LSPState;
 pub use lsp_state::Schemas;
@@ -40,21 +41,42 @@ use lsp_types::notification::DidCloseTextDocument;
 use lsp_types::notification::DidOpenTextDocument;
 use lsp_types::notification::DidSaveTextDocument;
 use lsp_types::notification::Exit;
+use lsp_types::request::CallHierarchyIncomingCalls;
+use lsp_types::request::CallHierarchyOutgoingCalls;
+use lsp_types::request::CallHierarchyPrepare;
 use lsp_types::request::CodeActionRequest;
+use lsp_types::request::CodeLensRequest;
 use lsp_types::request::Completion;
+use lsp_types::request::DocumentDiagnosticRequest;
+use lsp_types::request::DocumentSymbolRequest;
+use lsp_types::request::Formatting;
 use lsp_types::request::GotoDefinition;
 use lsp_types::request::HoverRequest;
 use lsp_types::request::InlayHintRequest;
 use lsp_types::request::PrepareRenameRequest;
+use lsp_types::request::RangeFormatting;
 use lsp_types::request::References;
 use lsp_types::request::Rename;
 use lsp_types::request::ResolveCompletionItem;
+use lsp_types::request::SemanticTokensFullRequest;
+use lsp_types::request::SemanticTokensRangeRequest;
 use lsp_types::request::Shutdown;
+use lsp_types::request::SignatureHelpRequest;
+use lsp_types::request::WorkspaceDiagnosticRequest;
+use lsp_types::request::WorkspaceSymbolRequest;
+use lsp_types::CallHierarchyServerCapability;
 use lsp_types::CodeActionProviderCapability;
+use lsp_types::CodeLensOptions;
 use lsp_types::CompletionOptions;
+use lsp_types::DiagnosticOptions;
+use lsp_types::DiagnosticServerCapabilities;
 use lsp_types::InitializeParams;
 use lsp_types::RenameOptions;
+use lsp_types::SemanticTokensFullOptions;
+use lsp_types::SemanticTokensOptions;
+use lsp_types::SemanticTokensServerCapabilities;
 use lsp_types::ServerCapabilities;
+use lsp_types::SignatureHelpOptions;
 use lsp_types::TextDocumentSyncCapability;
 use lsp_types::TextDocumentSyncKind;
 use lsp_types::WorkDoneProgressOptions;
@@ -64,13 +86,20 @@ use schema_documentation::SchemaDocumentation;
 use schema_documentation::SchemaDocumentationLoader;
 
 use self::task_queue::TaskProcessor;
+use crate::call_hierarchy::on_call_hierarchy_incoming_calls;
+use crate::call_hierarchy::on_call_hierarchy_outgoing_calls;
+use crate::call_hierarchy::on_prepare_call_hierarchy;
 use crate::code_action::on_code_action;
+use crate::code_lens::on_code_lens;
 use crate::completion::on_completion;
 use crate::completion::on_resolve_completion_item;
+use crate::document_symbols::on_document_symbol;
 use crate::explore_schema_for_type::on_explore_schema_for_type;
 use crate::explore_schema_for_type::ExploreSchemaForType;
 use crate::find_field_usages::on_find_field_usages;
 use crate::find_field_usages::FindFieldUsages;
+use crate::formatting::on_formatting;
+use crate::formatting::on_range_formatting;
 use crate::goto_definition::on_get_source_location_of_type_definition;
 use crate::goto_definition::on_goto_definition;
 use crate::goto_definition::GetSourceLocationOfTypeDefinition;
@@ -80,6 +109,8 @@ use crate::hover::on_hover;
 use crate::inlay_hints::on_inlay_hint_request;
 use crate::lsp_process_error::LSPProcessResult;
 use crate::lsp_runtime_error::LSPRuntimeError;
+use crate::pull_diagnostics::on_document_diagnostic;
+use crate::pull_diagnostics::on_workspace_diagnostic;
 use crate::references::on_references;
 use crate::rename::on_prepare_rename;
 use crate::rename::on_rename;
@@ -87,17 +118,22 @@ use crate::resolved_types_at_location::on_get_resolved_types_at_location;
 use crate::resolved_types_at_location::ResolvedTypesAtLocation;
 use crate::search_schema_items::on_search_schema_items;
 use crate::search_schema_items::SearchSchemaItems;
+use crate::semantic_tokens::get_semantic_tokens_legend;
+use crate::semantic_tokens::on_semantic_tokens_full;
+use crate::semantic_tokens::on_semantic_tokens_range;
 use crate::server::lsp_state::handle_lsp_state_tasks;
 use crate::server::lsp_state_resources::LSPStateResources;
 use crate::server::task_queue::TaskQueue;
 use crate::shutdown::on_exit;
 use crate::shutdown::on_shutdown;
+use crate::signature_help::on_signature_help;
 use crate::status_reporter::LSPStatusReporter;
 use crate::text_documents::on_cancel;
 use crate::text_documents::on_did_change_text_document;
 use crate::text_documents::on_did_close_text_document;
 use crate::text_documents::on_did_open_text_document;
 use crate::text_documents::on_did_save_text_document;
+use crate::workspace_symbols::on_workspace_symbol;
 pub use crate::LSPExtraDataProvider;
 
 /// Initializes an LSP connection, handling the `initialize` message and `initialized` notification
@@ -125,7 +161,41 @@ pub fn initialize(connection: &Connection) -> LSPProcessResult<InitializeParams>
         definition_provider: Some(lsp_types::OneOf::Left(true)),
         references_provider: Some(lsp_types::OneOf::Left(true)),
         code_action_provider: Some(CodeActionProviderCapability::Simple(true)),
+        code_lens_provider: Some(CodeLensOptions {
+            resolve_provider: Some(false),
+        }),
+        call_hierarchy_provider: Some(CallHierarchyServerCapability::Simple(true)),
         inlay_hint_provider: Some(lsp_types::OneOf::Left(true)),
+        document_symbol_provider: Some(lsp_types::OneOf::Left(true)),
+        workspace_symbol_provider: Some(lsp_types::OneOf::Left(true)),
+        document_formatting_provider: Some(lsp_types::OneOf::Left(true)),
+        document_range_formatting_provider: Some(lsp_types::OneOf::Left(true)),
+        semantic_tokens_provider: Some(SemanticTokensServerCapabilities::SemanticTokensOptions(
+            SemanticTokensOptions {
+                work_done_progress_options: WorkDoneProgressOptions {
+                    work_done_progress: None,
+                },
+                legend: get_semantic_tokens_legend(),
+                range: Some(true),
+                full: Some(SemanticTokensFullOptions::Bool(true)),
+            },
+        )),
+        diagnostic_provider: Some(DiagnosticServerCapabilities::Options(DiagnosticOptions {
+            identifier: Some("relay".to_string()),
+            // Spreads are validated against the fragments of other documents
+            inter_file_dependencies: true,
+            workspace_diagnostics: true,
+            work_done_progress_options: WorkDoneProgressOptions {
+                work_done_progress: None,
+            },
+        })),
+        signature_help_provider: Some(SignatureHelpOptions {
+            trigger_characters: Some(vec!["(".into(), ",".into()]),
+            retrigger_characters: None,
+            work_done_progress_options: WorkDoneProgressOptions {
+                work_done_progress: None,
+            },
+        }),
         ..Default::default()
     };
 
@@ -148,7 +218,7 @@ pub async fn run<
 >(
     connection: Connection,
     mut config: Config,
-    _params: InitializeParams,
+    params: InitializeParams,
     perf_logger: Arc<TPerfLogger>,
     extra_data_provider: Box<dyn LSPExtraDataProvider + Send + Sync>,
     schema_documentation_loader: Option<Box<dyn SchemaDocumentationLoader<TSchemaDocumentation>>>,
@@ -177,6 +247,25 @@ pub async fn run<
         schema_documentation_loader,
     ));
 
+    if params
+        .capabilities
+        .text_document
+        .as_ref()
+        .and_then(|text_document| text_document.diagnostic.as_ref())
+        .is_some()
+    {
+        let refresh_support = params
+            .capabilities
+            .workspace
+            .as_ref()
+            .and_then(|workspace| workspace.diagnostic.as_ref())
+            .and_then(|diagnostic| diagnostic.refresh_support)
+            .unwrap_or(false);
+        lsp_state
+            .diagnostic_reporter
+            .enable_pull_diagnostics(refresh_support);
+    }
+
     LSPStateResources::new(Arc::clone(&lsp_state)).watch();
 
     while let Some(task) = next_task(&connection.receiver, &task_queue.receiver) {
@@ -264,6 +353,7 @@ fn dispatch_request(request: lsp_server::Request, lsp_state: &impl GlobalState)
             .on_request_sync::<Completion>(on_completion)?
             .on_request_sync::<ResolveCompletionItem>(on_resolve_completion_item)?
             .on_request_sync::<CodeActionRequest>(on_code_action)?
+            .on_request_sync::<CodeLensRequest>(on_code_lens)?
             .on_request_sync::<Shutdown>(on_shutdown)?
             .on_request_sync::<GraphQLExecuteQuery>(on_graphql_execute_query)?
             .on_request_sync::<HeartbeatRequest>(on_heartbeat)?
@@ -271,6 +361,18 @@ fn dispatch_request(request: lsp_server::Request, lsp_state: &impl GlobalState)
             .on_request_sync::<InlayHintRequest>(on_inlay_hint_request)?
             .on_request_sync::<Rename>(on_rename)?
             .on_request_sync::<PrepareRenameRequest>(on_prepare_rename)?
+            .on_request_sync::<DocumentSymbolRequest>(on_document_symbol)?
+            .on_request_sync::<WorkspaceSymbolRequest>(on_workspace_symbol)?
+            .on_request_sync::<Formatting>(on_formatting)?
+            .on_request_sync::<RangeFormatting>(on_range_formatting)?
+            .on_request_sync::<SemanticTokensFullRequest>(on_semantic_tokens_full)?
+            .on_request_sync::<SemanticTokensRangeRequest>(on_semantic_tokens_range)?
+            .on_request_sync::<SignatureHelpRequest>(on_signature_help)?
+            .on_request_sync::<DocumentDiagnosticRequest>(on_document_diagnostic)?
+            .on_request_sync::<WorkspaceDiagnosticRequest>(on_workspace_diagnostic)?
+            .on_request_sync::<CallHierarchyPrepare>(on_prepare_call_hierarchy)?
+            .on_request_sync::<CallHierarchyIncomingCalls>(on_call_hierarchy_incoming_calls)?
+            .on_request_sync::<CallHierarchyOutgoingCalls>(on_call_hierarchy_outgoing_calls)?
             .request();
 
         // If we have gotten here, we have not handled the request
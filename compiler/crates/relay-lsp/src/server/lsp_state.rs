@@ -13,6 +13,7 @@ use common::Location;
 use common::PerfLogger;
 use common::SourceLocationKey;
 use common::Span;
+use common::WithLocation;
 use crossbeam::channel::SendError;
 use crossbeam::channel::Sender;
 use dashmap::mapref::entry::Entry;
@@ -20,27 +21,42 @@ use dashmap::DashMap;
 use docblock_syntax::parse_docblock;
 use extract_graphql::JavaScriptSourceFeature;
 use fnv::FnvBuildHasher;
-use graphql_ir::build_ir_with_extra_features;
+use graphql_ir::build_ir_with_external_fragments;
 use graphql_ir::BuilderOptions;
+use graphql_ir::ExecutableDefinitionName;
+use graphql_ir::FragmentDefinition;
+use graphql_ir::FragmentDefinitionNameSet;
+use graphql_ir::FragmentSpread;
 use graphql_ir::FragmentVariablesSemantic;
 use graphql_ir::Program;
 use graphql_ir::RelayMode;
+use graphql_ir::Visitor;
 use graphql_syntax::parse_executable_with_error_recovery_and_parser_features;
 use graphql_syntax::ExecutableDefinition;
 use graphql_syntax::ExecutableDocument;
 use graphql_syntax::GraphQLSource;
+use graphql_syntax::SchemaDocument;
+use graphql_text_printer::format_schema_document;
 use intern::string_key::Intern;
 use intern::string_key::StringKey;
 use log::debug;
 use lsp_server::Message;
 use lsp_types::Diagnostic;
+use lsp_types::DocumentDiagnosticReport;
+use lsp_types::PreviousResultId;
 use lsp_types::Range;
 use lsp_types::TextDocumentPositionParams;
 use lsp_types::Url;
+use lsp_types::WorkspaceDiagnosticReport;
+use relay_compiler::compiler_state::ArtifactMapKind;
 use relay_compiler::config::Config;
+use relay_compiler::format_graphql_tags;
 use relay_compiler::get_parser_features;
+use relay_compiler::ArtifactRecord;
+use relay_compiler::ArtifactSourceKey;
 use relay_compiler::FileCategorizer;
 use relay_compiler::FileGroup;
+use relay_compiler::FormattedGraphQLTag;
 use relay_compiler::ProjectName;
 use relay_docblock::parse_docblock_ast;
 use relay_docblock::ParseOptions;
@@ -52,6 +68,7 @@ use schema_documentation::SchemaDocumentationLoader;
 use tokio::sync::Notify;
 
 use super::task_queue::TaskScheduler;
+use crate::call_hierarchy::CallHierarchyIndex;
 use crate::diagnostic_reporter::DiagnosticReporter;
 use crate::docblock_resolution_info::create_docblock_resolution_info;
 use crate::graphql_tools::get_query_text;
@@ -71,6 +88,8 @@ use crate::LSPRuntimeError;
 
 pub type Schemas = Arc<DashMap<StringKey, Arc<SDLSchema>, FnvBuildHasher>>;
 pub type SourcePrograms = Arc<DashMap<StringKey, Program, FnvBuildHasher>>;
+pub type ProjectArtifacts = Arc<DashMap<StringKey, Arc<ArtifactMapKind>, FnvBuildHasher>>;
+pub type CallHierarchyIndexes = Arc<DashMap<StringKey, Arc<CallHierarchyIndex>, FnvBuildHasher>>;
 pub type ProjectStatusMap = Arc<DashMap<StringKey, ProjectStatus, FnvBuildHasher>>;
 
 #[derive(Eq, PartialEq)]
@@ -86,6 +105,9 @@ pub trait GlobalState {
 
     fn get_program(&self, project_name: &StringKey) -> LSPRuntimeResult<Program>;
 
+    /// The names of the projects whose program is available.
+    fn get_project_names(&self) -> Vec<StringKey>;
+
     fn resolve_node(
         &self,
         text_document_position: &TextDocumentPositionParams,
@@ -105,6 +127,15 @@ pub trait GlobalState {
         index_offset: usize,
     ) -> LSPRuntimeResult<(Feature, Location)>;
 
+    /// The text of the GraphQL document of an open file at the position of
+    /// this LSP request, which the spans of its parsed document index into:
+    /// the `graphql` tag at the position in a JavaScript file, or the whole
+    /// text of a GraphQL file.
+    fn extract_graphql_source_text(
+        &self,
+        position: &TextDocumentPositionParams,
+    ) -> LSPRuntimeResult<String>;
+
     fn get_schema_documentation(&self, schema_name: &str) -> Self::TSchemaDocumentation;
 
     fn get_extra_data_provider(&self) -> &dyn LSPExtraDataProvider;
@@ -114,8 +145,57 @@ pub trait GlobalState {
         text_document_uri: &Url,
     ) -> LSPRuntimeResult<Vec<ExecutableDefinition>>;
 
+    /// The sources of an open JavaScript file, in the order of the indices of
+    /// their embedded source locations.
+    fn get_javascript_sources(
+        &self,
+        text_document_uri: &Url,
+    ) -> LSPRuntimeResult<Vec<JavaScriptSourceFeature>>;
+
+    /// Parses the text document, if it is an open schema or schema extension
+    /// file.
+    fn resolve_schema_document(
+        &self,
+        text_document_uri: &Url,
+    ) -> LSPRuntimeResult<Option<SchemaDocument>>;
+
+    /// Formats the `graphql` tags of an open JavaScript file, or the document
+    /// of an open schema or extension file, and returns the ones that changed.
+    fn format_graphql_sources(
+        &self,
+        text_document_uri: &Url,
+    ) -> LSPRuntimeResult<Vec<FormattedGraphQLTag>>;
+
+    /// The record of the main artifact generated for an executable definition,
+    /// if it exists. The persisted operation id and the text hash are only
+    /// known when the compiler state the project was built from tracks its
+    /// generated artifacts.
+    fn get_artifact_record(
+        &self,
+        project_name: &StringKey,
+        definition_name: WithLocation<ExecutableDefinitionName>,
+    ) -> LSPRuntimeResult<ArtifactRecord>;
+
+    /// The index of the callers of the fragments of the program of a project,
+    /// for the incoming calls of the call hierarchy.
+    fn get_call_hierarchy_index(
+        &self,
+        project_name: &StringKey,
+    ) -> LSPRuntimeResult<Arc<CallHierarchyIndex>>;
+
     fn get_diagnostic_for_range(&self, url: &Url, range: Range) -> Option<Diagnostic>;
 
+    fn get_document_diagnostic_report(
+        &self,
+        url: &Url,
+        previous_result_id: Option<&str>,
+    ) -> DocumentDiagnosticReport;
+
+    fn get_workspace_diagnostic_report(
+        &self,
+        previous_result_ids: &[PreviousResultId],
+    ) -> WorkspaceDiagnosticReport;
+
     /// For Relay - project_name is an human-readable identifier of a set of configurations,
     /// source files, schema extensions, etc, that are compiled together using a single GraphQL
     /// Schema. project_name typically the same as the schema name: facebook, intern, etc.
@@ -167,7 +247,15 @@ pub struct LSPState<
     pub(crate) schemas: Schemas,
     schema_documentation_loader: Option<Box<dyn SchemaDocumentationLoader<TSchemaDocumentation>>>,
     pub(crate) source_programs: SourcePrograms,
+    pub(crate) artifacts: ProjectArtifacts,
+    /// The call hierarchy index of each program of `source_programs`, built on
+    /// the first use, and removed when the program changes.
+    pub(crate) call_hierarchy_indexes: CallHierarchyIndexes,
     synced_javascript_sources: DashMap<Url, Vec<JavaScriptSourceFeature>>,
+    synced_javascript_texts: DashMap<Url, String>,
+    /// The fragments of the open documents, without their selections, to
+    /// validate the spreads of other documents against unsaved changes.
+    synced_fragments: DashMap<Url, SyncedFragments>,
     synced_schema_sources: DashMap<Url, GraphQLSource>,
     pub(crate) perf_logger: Arc<TPerfLogger>,
     pub(crate) diagnostic_reporter: Arc<DiagnosticReporter>,
@@ -211,7 +299,11 @@ impl<TPerfLogger: PerfLogger + 'static, TSchemaDocumentation: SchemaDocumentatio
             schemas: Arc::new(DashMap::with_hasher(FnvBuildHasher::default())),
             schema_documentation_loader,
             source_programs: Arc::new(DashMap::with_hasher(FnvBuildHasher::default())),
+            artifacts: Arc::new(DashMap::with_hasher(FnvBuildHasher::default())),
+            call_hierarchy_indexes: Arc::new(DashMap::with_hasher(FnvBuildHasher::default())),
             synced_javascript_sources: Default::default(),
+            synced_javascript_texts: Default::default(),
+            synced_fragments: Default::default(),
             synced_schema_sources: Default::default(),
         };
 
@@ -223,8 +315,15 @@ impl<TPerfLogger: PerfLogger + 'static, TSchemaDocumentation: SchemaDocumentatio
         lsp_state
     }
 
-    fn insert_synced_js_sources(&self, url: &Url, sources: Vec<JavaScriptSourceFeature>) {
+    fn insert_synced_js_sources(
+        &self,
+        url: &Url,
+        text: &str,
+        sources: Vec<JavaScriptSourceFeature>,
+    ) {
         self.synced_javascript_sources.insert(url.clone(), sources);
+        self.synced_javascript_texts
+            .insert(url.clone(), text.to_string());
     }
 
     fn validate_synced_js_sources(&self, url: &Url) -> LSPRuntimeResult<()> {
@@ -245,6 +344,7 @@ impl<TPerfLogger: PerfLogger + 'static, TSchemaDocumentation: SchemaDocumentatio
 
         let mut executable_definitions = vec![];
         let mut docblock_sources = vec![];
+        let mut synced_fragments = Some(vec![]);
 
         for (index, feature) in javascript_features.iter().enumerate() {
             match feature {
@@ -271,13 +371,18 @@ impl<TPerfLogger: PerfLogger + 'static, TSchemaDocumentation: SchemaDocumentatio
                         }
                         Ok(warnings)
                     };
-                    let compiler_diagnostics =
-                        match build_ir_for_lsp(&schema, &result.item.definitions)
-                            .and_then(get_errors_or_warnings)
-                        {
-                            Ok(warnings) => warnings,
-                            Err(errors) => errors,
-                        };
+                    let compiler_diagnostics = match self
+                        .build_ir_with_synced_fragments(
+                            &project_name,
+                            &schema,
+                            &result.item.definitions,
+                            &mut synced_fragments,
+                        )
+                        .and_then(get_errors_or_warnings)
+                    {
+                        Ok(warnings) => warnings,
+                        Err(errors) => errors,
+                    };
 
                     diagnostics.extend(compiler_diagnostics.iter().map(|diagnostic| {
                         self.diagnostic_reporter
@@ -322,9 +427,103 @@ impl<TPerfLogger: PerfLogger + 'static, TSchemaDocumentation: SchemaDocumentatio
         self.diagnostic_reporter
             .update_quick_diagnostics_for_url(url, diagnostics);
 
+        // Documents with errors keep the fragments of their last valid version
+        if let Some(synced_fragments) = synced_fragments {
+            let has_changed_signatures = self
+                .synced_fragments
+                .get(url)
+                .map_or(!synced_fragments.is_empty(), |previous| {
+                    !have_same_signatures(&previous.fragments, &synced_fragments)
+                });
+            self.synced_fragments.insert(
+                url.clone(),
+                SyncedFragments {
+                    project_name,
+                    fragments: synced_fragments,
+                },
+            );
+            if has_changed_signatures {
+                // Validate the spreads of these fragments in the open documents
+                self.schedule_task(Task::SyncedDocuments);
+            }
+        }
+
         Ok(())
     }
 
+    /// Builds the IR of the definitions of an open document, and validates
+    /// their spreads of fragments defined in other documents, against the
+    /// unsaved changes of the open documents or the project. Collects the
+    /// fragments of the document in `synced_fragments`, or resets it if the
+    /// definitions are invalid.
+    fn build_ir_with_synced_fragments(
+        &self,
+        project_name: &StringKey,
+        schema: &SDLSchema,
+        definitions: &[ExecutableDefinition],
+        synced_fragments: &mut Option<Vec<FragmentDefinition>>,
+    ) -> DiagnosticsResult<Vec<graphql_ir::ExecutableDefinition>> {
+        let ir = match build_ir_for_lsp(schema, definitions) {
+            Ok(ir) => ir,
+            Err(errors) => {
+                *synced_fragments = None;
+                return Err(errors);
+            }
+        };
+
+        let mut spread_collector = FragmentSpreadCollector::default();
+        for definition in &ir {
+            match definition {
+                graphql_ir::ExecutableDefinition::Fragment(fragment) => {
+                    spread_collector
+                        .defined_fragments
+                        .insert(fragment.name.item);
+                    if let Some(synced_fragments) = synced_fragments {
+                        synced_fragments.push(FragmentDefinition {
+                            selections: vec![],
+                            ..fragment.clone()
+                        });
+                    }
+                }
+                graphql_ir::ExecutableDefinition::Operation(_) => {}
+            }
+            spread_collector.visit_executable_definition(definition);
+        }
+
+        let program = self.source_programs.get(project_name);
+        let external_fragments = spread_collector
+            .spread_fragments
+            .into_iter()
+            .filter(|name| !spread_collector.defined_fragments.contains(name))
+            .filter_map(|name| {
+                self.synced_fragments
+                    .iter()
+                    .filter(|entry| entry.project_name == *project_name)
+                    .find_map(|entry| {
+                        entry
+                            .fragments
+                            .iter()
+                            .find(|fragment| fragment.name.item == name)
+                            .cloned()
+                    })
+                    .or_else(|| {
+                        program
+                            .as_ref()
+                            .and_then(|program| program.fragment(name))
+                            .map(|fragment| FragmentDefinition {
+                                selections: vec![],
+                                ..fragment.as_ref().clone()
+                            })
+                    })
+            })
+            .collect::<Vec<_>>();
+        if external_fragments.is_empty() {
+            return Ok(ir);
+        }
+
+        build_ir_for_lsp_with_external_fragments(schema, definitions, &external_fragments)
+    }
+
     fn validate_synced_schema_source(&self, url: &Url) -> LSPRuntimeResult<()> {
         let schema_source = self.synced_schema_sources.get(url).ok_or_else(|| {
             LSPRuntimeError::UnexpectedError(format!("Expected schema source for URL {}", url))
@@ -378,13 +577,23 @@ impl<TPerfLogger: PerfLogger + 'static, TSchemaDocumentation: SchemaDocumentatio
         self.task_scheduler.schedule(super::Task::LSPState(task));
     }
 
-    fn process_synced_js_sources(&self, uri: &Url, sources: Vec<JavaScriptSourceFeature>) {
-        self.insert_synced_js_sources(uri, sources);
+    fn process_synced_js_sources(
+        &self,
+        uri: &Url,
+        text: &str,
+        sources: Vec<JavaScriptSourceFeature>,
+    ) {
+        self.insert_synced_js_sources(uri, text, sources);
         self.schedule_task(Task::SyncedSource(uri.clone()));
     }
 
     fn remove_synced_js_sources(&self, url: &Url) {
         self.synced_javascript_sources.remove(url);
+        self.synced_javascript_texts.remove(url);
+        if self.synced_fragments.remove(url).is_some() {
+            // Validate the spreads of these fragments against the project again
+            self.schedule_task(Task::SyncedDocuments);
+        }
         self.diagnostic_reporter
             .clear_quick_diagnostics_for_url(url);
     }
@@ -448,6 +657,13 @@ impl<TPerfLogger: PerfLogger + 'static, TSchemaDocumentation: SchemaDocumentatio
             })
     }
 
+    fn get_project_names(&self) -> Vec<StringKey> {
+        self.source_programs
+            .iter()
+            .map(|entry| *entry.key())
+            .collect()
+    }
+
     fn resolve_node(
         &self,
         text_document_position: &TextDocumentPositionParams,
@@ -517,6 +733,31 @@ impl<TPerfLogger: PerfLogger + 'static, TSchemaDocumentation: SchemaDocumentatio
         )
     }
 
+    fn extract_graphql_source_text(
+        &self,
+        position: &TextDocumentPositionParams,
+    ) -> LSPRuntimeResult<String> {
+        if let Some(schema_source) = self.synced_schema_sources.get(&position.text_document.uri) {
+            return Ok(schema_source.text_source().text.clone());
+        }
+        let source_features = self
+            .synced_javascript_sources
+            .get(&position.text_document.uri)
+            .ok_or(LSPRuntimeError::ExpectedError)?;
+
+        source_features
+            .iter()
+            .find_map(|source_feature| match source_feature {
+                JavaScriptSourceFeature::GraphQL(graphql_source) => {
+                    let range = graphql_source.text_source().to_range();
+                    (position.position >= range.start && position.position <= range.end)
+                        .then(|| graphql_source.text_source().text.clone())
+                }
+                JavaScriptSourceFeature::Docblock(_) => None,
+            })
+            .ok_or(LSPRuntimeError::ExpectedError)
+    }
+
     fn get_schema_documentation(&self, schema_name: &str) -> Self::TSchemaDocumentation {
         let primary = self
             .schemas
@@ -562,11 +803,146 @@ impl<TPerfLogger: PerfLogger + 'static, TSchemaDocumentation: SchemaDocumentatio
         )
     }
 
+    fn get_javascript_sources(
+        &self,
+        text_document_uri: &Url,
+    ) -> LSPRuntimeResult<Vec<JavaScriptSourceFeature>> {
+        self.synced_javascript_sources
+            .get(text_document_uri)
+            .map(|sources| sources.clone())
+            .ok_or(LSPRuntimeError::ExpectedError)
+    }
+
+    fn resolve_schema_document(
+        &self,
+        text_document_uri: &Url,
+    ) -> LSPRuntimeResult<Option<SchemaDocument>> {
+        let Some(schema_source) = self.synced_schema_sources.get(text_document_uri) else {
+            return Ok(None);
+        };
+        let path = text_document_uri
+            .to_file_path()
+            .map_err(|_| LSPRuntimeError::ExpectedError)?;
+        let path = path
+            .strip_prefix(&self.root_dir)
+            .map_err(|_| LSPRuntimeError::ExpectedError)?;
+        // Standalone locations are relative to the root directory.
+        let source_location_key = SourceLocationKey::standalone(&path.to_string_lossy());
+        graphql_syntax::parse_schema_document(
+            &schema_source.text_source().text,
+            source_location_key,
+        )
+        .map(Some)
+        .map_err(|_| LSPRuntimeError::ExpectedError)
+    }
+
+    fn format_graphql_sources(
+        &self,
+        text_document_uri: &Url,
+    ) -> LSPRuntimeResult<Vec<FormattedGraphQLTag>> {
+        if let Some(schema_source) = self.synced_schema_sources.get(text_document_uri) {
+            let text = &schema_source.text_source().text;
+            let formatted_document =
+                format_schema_document(text, SourceLocationKey::generated()).ok();
+            return Ok(formatted_document
+                .filter(|formatted_text| formatted_text != text)
+                .map(|formatted_text| FormattedGraphQLTag {
+                    graphql_source: schema_source.clone(),
+                    range: 0..text.len(),
+                    formatted_text,
+                })
+                .into_iter()
+                .collect());
+        }
+        let project_name: ProjectName = self
+            .extract_project_name_from_url(text_document_uri)?
+            .into();
+        let project_config = self.config.projects.get(&project_name).unwrap();
+        let text = self
+            .synced_javascript_texts
+            .get(text_document_uri)
+            .ok_or(LSPRuntimeError::ExpectedError)?;
+
+        Ok(format_graphql_tags(
+            &text,
+            get_parser_features(project_config),
+        ))
+    }
+
+    fn get_artifact_record(
+        &self,
+        project_name: &StringKey,
+        definition_name: WithLocation<ExecutableDefinitionName>,
+    ) -> LSPRuntimeResult<ArtifactRecord> {
+        let project_config = self
+            .config
+            .projects
+            .get(&ProjectName::from(*project_name))
+            .ok_or(LSPRuntimeError::ExpectedError)?;
+        let path = project_config.artifact_path_for_definition(definition_name);
+        if !self.root_dir.join(&path).exists() {
+            return Err(LSPRuntimeError::ExpectedError);
+        }
+        let record = self.artifacts.get(project_name).and_then(|artifacts| {
+            match artifacts.value().as_ref() {
+                ArtifactMapKind::Mapping(artifact_map) => artifact_map
+                    .0
+                    .get(&ArtifactSourceKey::ExecutableDefinition(
+                        definition_name.item,
+                    ))
+                    .and_then(|records| records.iter().find(|record| record.path == path).cloned()),
+                ArtifactMapKind::Unconnected(_) => None,
+            }
+        });
+
+        Ok(record.unwrap_or(ArtifactRecord {
+            path,
+            persisted_operation_id: None,
+            text_hash: None,
+        }))
+    }
+
+    fn get_call_hierarchy_index(
+        &self,
+        project_name: &StringKey,
+    ) -> LSPRuntimeResult<Arc<CallHierarchyIndex>> {
+        // The program is borrowed until the index is stored, so that a new
+        // program, which removes the index, can't replace it in between.
+        let program = self.source_programs.get(project_name).ok_or_else(|| {
+            LSPRuntimeError::UnexpectedError(format!(
+                "get_call_hierarchy_index: program is missing (or not ready, yet) for the `{}` project.",
+                project_name
+            ))
+        })?;
+        let index = self
+            .call_hierarchy_indexes
+            .entry(*project_name)
+            .or_insert_with(|| Arc::new(CallHierarchyIndex::new(program.value())));
+        Ok(Arc::clone(index.value()))
+    }
+
     fn get_diagnostic_for_range(&self, url: &Url, range: Range) -> Option<Diagnostic> {
         self.diagnostic_reporter
             .get_diagnostics_for_range(url, range)
     }
 
+    fn get_document_diagnostic_report(
+        &self,
+        url: &Url,
+        previous_result_id: Option<&str>,
+    ) -> DocumentDiagnosticReport {
+        self.diagnostic_reporter
+            .get_document_diagnostic_report(url, previous_result_id)
+    }
+
+    fn get_workspace_diagnostic_report(
+        &self,
+        previous_result_ids: &[PreviousResultId],
+    ) -> WorkspaceDiagnosticReport {
+        self.diagnostic_reporter
+            .get_workspace_diagnostic_report(previous_result_ids)
+    }
+
     fn get_full_query_text(
         &self,
         query_text: String,
@@ -601,7 +977,7 @@ impl<TPerfLogger: PerfLogger + 'static, TSchemaDocumentation: SchemaDocumentatio
 
                 if !embedded_sources.is_empty() {
                     self.initialize_lsp_state_resources(project_name);
-                    self.process_synced_js_sources(uri, embedded_sources);
+                    self.process_synced_js_sources(uri, text, embedded_sources);
                 }
 
                 Ok(())
@@ -629,7 +1005,7 @@ impl<TPerfLogger: PerfLogger + 'static, TSchemaDocumentation: SchemaDocumentatio
                 if embedded_sources.is_empty() {
                     self.remove_synced_js_sources(uri);
                 } else {
-                    self.process_synced_js_sources(uri, embedded_sources);
+                    self.process_synced_js_sources(uri, text, embedded_sources);
                 }
 
                 Ok(())
@@ -665,9 +1041,20 @@ pub fn build_ir_for_lsp(
     schema: &SDLSchema,
     definitions: &[ExecutableDefinition],
 ) -> DiagnosticsResult<Vec<graphql_ir::ExecutableDefinition>> {
-    build_ir_with_extra_features(
+    build_ir_for_lsp_with_external_fragments(schema, definitions, &[])
+}
+
+/// Builds the IR of definitions that spread fragments of other documents,
+/// checking those spreads against `external_fragments`.
+pub fn build_ir_for_lsp_with_external_fragments(
+    schema: &SDLSchema,
+    definitions: &[ExecutableDefinition],
+    external_fragments: &[FragmentDefinition],
+) -> DiagnosticsResult<Vec<graphql_ir::ExecutableDefinition>> {
+    build_ir_with_external_fragments(
         schema,
         definitions,
+        external_fragments,
         &BuilderOptions {
             allow_undefined_fragment_spreads: true,
             fragment_variables_semantic: FragmentVariablesSemantic::PassedValue,
@@ -678,6 +1065,65 @@ pub fn build_ir_for_lsp(
     )
 }
 
+/// The fragments of an open document, without their selections.
+struct SyncedFragments {
+    /// The project of the document. Fragments are only visible to the
+    /// documents of the same project, as their types belong to its schema.
+    project_name: StringKey,
+    fragments: Vec<FragmentDefinition>,
+}
+
+/// Whether the fragments have the same names, type conditions and arguments,
+/// regardless of their locations.
+fn have_same_signatures(
+    previous_fragments: &[FragmentDefinition],
+    fragments: &[FragmentDefinition],
+) -> bool {
+    previous_fragments.len() == fragments.len()
+        && previous_fragments
+            .iter()
+            .zip(fragments)
+            .all(|(previous_fragment, fragment)| {
+                previous_fragment.name.item == fragment.name.item
+                    && previous_fragment.type_condition == fragment.type_condition
+                    && previous_fragment.variable_definitions.len()
+                        == fragment.variable_definitions.len()
+                    && previous_fragment
+                        .variable_definitions
+                        .iter()
+                        .zip(&fragment.variable_definitions)
+                        .all(|(previous_variable, variable)| {
+                            previous_variable.name.item == variable.name.item
+                                && previous_variable.type_ == variable.type_
+                                && previous_variable
+                                    .default_value
+                                    .as_ref()
+                                    .map(|default_value| &default_value.item)
+                                    == variable
+                                        .default_value
+                                        .as_ref()
+                                        .map(|default_value| &default_value.item)
+                        })
+            })
+}
+
+/// Collects the names of the fragments spread by definitions.
+#[derive(Default)]
+struct FragmentSpreadCollector {
+    defined_fragments: FragmentDefinitionNameSet,
+    spread_fragments: FragmentDefinitionNameSet,
+}
+
+impl Visitor for FragmentSpreadCollector {
+    const NAME: &'static str = "FragmentSpreadCollector";
+    const VISIT_ARGUMENTS: bool = false;
+    const VISIT_DIRECTIVES: bool = false;
+
+    fn visit_fragment_spread(&mut self, spread: &FragmentSpread) {
+        self.spread_fragments.insert(spread.fragment.item);
+    }
+}
+
 #[derive(Debug)]
 pub enum Task {
     SyncedDocuments,
@@ -710,3 +1156,149 @@ pub(crate) fn handle_lsp_state_tasks<
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use std::path::PathBuf;
+    use std::sync::Arc;
+
+    use common::NoopPerfLogger;
+    use intern::string_key::Intern;
+    use lsp_types::DocumentDiagnosticReport;
+    use lsp_types::Url;
+    use relay_compiler::compiler_state::ProjectSet;
+    use relay_compiler::config::Config;
+    use relay_compiler::config::SingleProjectConfigFile;
+    use relay_compiler::ProjectConfig;
+    use relay_compiler::ProjectName;
+    use relay_test_schema::get_test_schema;
+    use schema::SDLSchema;
+
+    use super::handle_lsp_state_tasks;
+    use super::LSPState;
+    use crate::lsp_extra_data_provider::DummyExtraDataProvider;
+    use crate::server::task_queue::TaskQueue;
+    use crate::server::LSPTaskProcessor;
+    use crate::server::Task;
+    use crate::GlobalState;
+
+    type TestLSPState = LSPState<NoopPerfLogger, SDLSchema>;
+
+    /// Runs the state tasks scheduled so far, and the tasks they schedule.
+    fn run_scheduled_tasks(state: &Arc<TestLSPState>, task_queue: &TaskQueue<TestLSPState, Task>) {
+        while let Ok(task) = task_queue.receiver.try_recv() {
+            if let Task::LSPState(task) = task {
+                handle_lsp_state_tasks(Arc::clone(state), task);
+            }
+        }
+    }
+
+    fn diagnostic_messages(state: &TestLSPState, url: &Url) -> Vec<String> {
+        match state
+            .diagnostic_reporter
+            .get_document_diagnostic_report(url, None)
+        {
+            DocumentDiagnosticReport::Full(report) => report
+                .full_document_diagnostic_report
+                .items
+                .into_iter()
+                .map(|diagnostic| diagnostic.message)
+                .collect(),
+            DocumentDiagnosticReport::Unchanged(_) => panic!("Expected a full report"),
+        }
+    }
+
+    fn create_state() -> (Arc<TestLSPState>, TaskQueue<TestLSPState, Task>) {
+        let mut config = Config::from(SingleProjectConfigFile::default());
+        config.root_dir = PathBuf::from("/virtual");
+        config.sources.clear();
+        config.projects.clear();
+        for name in ["first", "second"] {
+            let project_name = ProjectName::from(name.intern());
+            config
+                .sources
+                .insert(PathBuf::from(name), ProjectSet::of(project_name));
+            config.projects.insert(
+                project_name,
+                ProjectConfig {
+                    name: project_name,
+                    ..Default::default()
+                },
+            );
+        }
+
+        let task_queue = TaskQueue::new(Arc::new(LSPTaskProcessor));
+        let (sender, _receiver) = crossbeam::channel::unbounded();
+        let state = Arc::new(LSPState::new(
+            Arc::new(config),
+            sender,
+            task_queue.get_scheduler(),
+            Arc::new(NoopPerfLogger),
+            Box::new(DummyExtraDataProvider::new()),
+            None,
+        ));
+        state.diagnostic_reporter.enable_pull_diagnostics(false);
+        for name in ["first", "second"] {
+            state.schemas.insert(name.intern(), get_test_schema());
+        }
+        (state, task_queue)
+    }
+
+    #[test]
+    fn revalidates_spreads_of_changed_fragments() {
+        let (state, task_queue) = create_state();
+        let fragment_url = Url::parse("file:///virtual/first/Fragment.js").unwrap();
+        let query_url = Url::parse("file:///virtual/first/Query.js").unwrap();
+
+        state
+            .document_opened(
+                &fragment_url,
+                "graphql`fragment Fragment_user on User { name }`",
+            )
+            .unwrap();
+        state
+            .document_opened(
+                &query_url,
+                "graphql`query QueryQuery { me { ...Fragment_user } }`",
+            )
+            .unwrap();
+        run_scheduled_tasks(&state, &task_queue);
+        assert!(diagnostic_messages(&state, &query_url).is_empty());
+
+        state
+            .document_changed(
+                &fragment_url,
+                "graphql`fragment Fragment_user on Comment { id }`",
+            )
+            .unwrap();
+        run_scheduled_tasks(&state, &task_queue);
+        assert_eq!(diagnostic_messages(&state, &query_url).len(), 1);
+
+        state.document_closed(&fragment_url).unwrap();
+        run_scheduled_tasks(&state, &task_queue);
+        assert!(diagnostic_messages(&state, &query_url).is_empty());
+    }
+
+    #[test]
+    fn ignores_fragments_of_other_projects() {
+        let (state, task_queue) = create_state();
+        let fragment_url = Url::parse("file:///virtual/second/Fragment.js").unwrap();
+        let query_url = Url::parse("file:///virtual/first/Query.js").unwrap();
+
+        state
+            .document_opened(
+                &fragment_url,
+                "graphql`fragment Fragment_user on Comment { id }`",
+            )
+            .unwrap();
+        state
+            .document_opened(
+                &query_url,
+                "graphql`query QueryQuery { me { ...Fragment_user } }`",
+            )
+            .unwrap();
+        run_scheduled_tasks(&state, &task_queue);
+        assert!(diagnostic_messages(&state, &fragment_url).is_empty());
+        assert!(diagnostic_messages(&state, &query_url).is_empty());
+    }
+}
@@ -336,6 +336,12 @@ impl<TPerfLogger: PerfLogger + 'static, TSchemaDocumentation: SchemaDocumentatio
             base_fragment_names,
         } = get_project_asts(&schema, graphql_asts_map, project_config)?;
 
+        if let Some(artifacts) = compiler_state.artifacts.get(&project_name) {
+            self.lsp_state
+                .artifacts
+                .insert(project_name.into(), Arc::clone(artifacts));
+        }
+
         // This will kick-off the validation of all synced documents
         self.lsp_state.schedule_task(Task::SyncedDocuments);
 
@@ -507,6 +513,9 @@ impl<TPerfLogger: PerfLogger + 'static, TSchemaDocumentation: SchemaDocumentatio
                 e.insert(base_program.clone());
             }
             Entry::Occupied(mut e) => {
+                self.lsp_state
+                    .call_hierarchy_indexes
+                    .remove(&project_config.name.into());
                 let program = e.get_mut();
                 let removed_definition_names = graphql_asts.get(&project_config.name).map(|ast| {
                     ast.removed_definition_names
@@ -582,6 +591,13 @@ impl<TPerfLogger: PerfLogger + 'static, TSchemaDocumentation: SchemaDocumentatio
                             }
                         }
                     }
+                    Ok(FileSourceSubscriptionNextChange::Notify(file_source_changes)) => {
+                        pending_file_source_changes
+                            .write()
+                            .expect("LSPState::watch_and_update_schemas: expect to acquire write lock on pending_file_source_changes")
+                            .push(FileSourceResult::WalkDir(file_source_changes));
+                        notify_sender.notify_one();
+                    }
                 }
             }
         })
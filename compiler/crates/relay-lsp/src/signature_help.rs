@@ -0,0 +1,451 @@
+/*
+ * Copyright (c) Meta Platforms, Inc. and affiliates.
+ *
+ * This source code is licensed under the MIT license found in the
+ * LICENSE file in the root directory of this source tree.
+ */
+
+//! Utilities for providing the signature help feature
+
+use common::Span;
+use graphql_ir::FragmentDefinitionName;
+use graphql_ir::Program;
+use graphql_ir::Value;
+use graphql_ir::DIRECTIVE_ARGUMENTS;
+use graphql_syntax::Argument;
+use graphql_syntax::FragmentSpread;
+use graphql_syntax::List;
+use graphql_text_printer::print_value;
+use graphql_text_printer::PrinterOptions;
+use intern::string_key::StringKey;
+use intern::Lookup;
+use lsp_types::request::Request;
+use lsp_types::request::SignatureHelpRequest;
+use lsp_types::Documentation;
+use lsp_types::MarkupContent;
+use lsp_types::MarkupKind;
+use lsp_types::ParameterInformation;
+use lsp_types::ParameterLabel;
+use lsp_types::SignatureHelp;
+use lsp_types::SignatureInformation;
+use resolution_path::ArgumentParent;
+use resolution_path::ArgumentPath;
+use resolution_path::ArgumentRoot;
+use resolution_path::ConstantValuePath;
+use resolution_path::ConstantValueRoot;
+use resolution_path::DirectiveParent;
+use resolution_path::DirectivePath;
+use resolution_path::FragmentSpreadPath;
+use resolution_path::IdentParent;
+use resolution_path::IdentPath;
+use resolution_path::LinkedFieldPath;
+use resolution_path::Path;
+use resolution_path::ResolutionPath;
+use resolution_path::ResolvePosition;
+use resolution_path::ScalarFieldPath;
+use resolution_path::SelectionParent;
+use resolution_path::ValuePath;
+use resolution_path::VariableIdentifierParent;
+use resolution_path::VariableIdentifierPath;
+use schema::SDLSchema;
+use schema::Schema;
+use schema_documentation::SchemaDocumentation;
+
+use crate::lsp_runtime_error::LSPRuntimeResult;
+use crate::server::GlobalState;
+
+pub fn on_signature_help(
+    state: &impl GlobalState,
+    params: <SignatureHelpRequest as Request>::Params,
+) -> LSPRuntimeResult<<SignatureHelpRequest as Request>::Result> {
+    let (document, position_span) =
+        state.extract_executable_document_from_text(&params.text_document_position_params, 0)?;
+
+    let resolution_path = document.resolve((), position_span);
+
+    let project_name = state
+        .extract_project_name_from_url(&params.text_document_position_params.text_document.uri)?;
+    let schema = state.get_schema(&project_name)?;
+    let schema_documentation = state.get_schema_documentation(project_name.lookup());
+
+    Ok(get_signature_help(
+        &resolution_path,
+        position_span,
+        &schema,
+        &schema_documentation,
+        &state.get_program(&project_name)?,
+    ))
+}
+
+/// The field or fragment spread whose argument list contains the position.
+enum ArgumentListOwner<'a> {
+    Field {
+        name: StringKey,
+        selection_parent: &'a SelectionParent<'a>,
+        arguments: &'a List<Argument>,
+    },
+    FragmentSpread {
+        spread: &'a FragmentSpread,
+        arguments: &'a List<Argument>,
+    },
+}
+
+/// A parameter of a signature, before its offsets in the label are known.
+struct Parameter {
+    name: StringKey,
+    label: String,
+    description: Option<String>,
+}
+
+/// Returns the arguments of the field or the fragment spread whose argument
+/// list contains the position, with the parameter at the position as the
+/// active one. Fragment arguments come from the variable definitions of the
+/// fragment, whether they use `@argumentDefinitions` or fragment variables.
+pub fn get_signature_help(
+    path: &ResolutionPath<'_>,
+    position: Span,
+    schema: &SDLSchema,
+    schema_documentation: &impl SchemaDocumentation,
+    program: &Program,
+) -> Option<SignatureHelp> {
+    let (label, documentation, parameters, arguments) =
+        match find_argument_list_owner(path, position)? {
+            ArgumentListOwner::Field {
+                name,
+                selection_parent,
+                arguments,
+            } => {
+                let parent_type = selection_parent.find_parent_type(schema)?;
+                let field = schema.field(schema.named_field(parent_type, name)?);
+                let parent_type_name = schema.get_type_name(parent_type).lookup();
+                let parameters = field
+                    .arguments
+                    .iter()
+                    .map(|argument| Parameter {
+                        name: argument.name.item.0,
+                        label: format!(
+                            "{}: {}{}",
+                            argument.name.item,
+                            schema.get_type_string(&argument.type_),
+                            argument.default_value.as_ref().map_or(
+                                String::new(),
+                                |default_value| format!(" = {}", default_value)
+                            )
+                        ),
+                        description: schema_documentation
+                            .get_field_argument_description(
+                                parent_type_name,
+                                field.name.item.lookup(),
+                                argument.name.item.0.lookup(),
+                            )
+                            .map(|description| description.to_string())
+                            .or_else(|| {
+                                argument
+                                    .description
+                                    .map(|description| description.to_string())
+                            }),
+                    })
+                    .collect::<Vec<_>>();
+                let documentation = schema_documentation
+                    .get_field_description(parent_type_name, field.name.item.lookup())
+                    .map(|description| description.to_string())
+                    .or_else(|| field.description.map(|description| description.to_string()));
+                let label = format!(
+                    "{}({}): {}",
+                    field.name.item,
+                    join_parameter_labels(&parameters),
+                    schema.get_type_string(&field.type_)
+                );
+                (label, documentation, parameters, arguments)
+            }
+            ArgumentListOwner::FragmentSpread { spread, arguments } => {
+                let fragment = program.fragment(FragmentDefinitionName(spread.name.value))?;
+                let parameters = fragment
+                    .variable_definitions
+                    .iter()
+                    .map(|variable_definition| Parameter {
+                        name: variable_definition.name.item.0,
+                        label: format!(
+                            "{}: {}{}",
+                            variable_definition.name.item.0,
+                            schema.get_type_string(&variable_definition.type_),
+                            variable_definition.default_value.as_ref().map_or(
+                                String::new(),
+                                |default_value| format!(
+                                    " = {}",
+                                    print_value(
+                                        schema,
+                                        &Value::Constant(default_value.item.clone()),
+                                        PrinterOptions::default()
+                                    )
+                                )
+                            )
+                        ),
+                        description: None,
+                    })
+                    .collect::<Vec<_>>();
+                let documentation = Some(format!(
+                    "Arguments of the fragment `{}` on `{}`.",
+                    fragment.name.item,
+                    schema.get_type_name(fragment.type_condition)
+                ));
+                let label = format!(
+                    "{}({})",
+                    fragment.name.item,
+                    join_parameter_labels(&parameters)
+                );
+                (label, documentation, parameters, arguments)
+            }
+        };
+    if parameters.is_empty() {
+        return None;
+    }
+
+    let active_parameter = get_active_parameter(&parameters, arguments, position);
+    let signature = SignatureInformation {
+        parameters: Some(get_parameter_information(&label, parameters)),
+        label,
+        documentation: documentation.map(to_markdown),
+        active_parameter,
+    };
+    Some(SignatureHelp {
+        signatures: vec![signature],
+        active_signature: Some(0),
+        active_parameter,
+    })
+}
+
+fn find_argument_list_owner<'a>(
+    path: &'a ResolutionPath<'a>,
+    position: Span,
+) -> Option<ArgumentListOwner<'a>> {
+    match path {
+        ResolutionPath::ScalarField(scalar_field_path) => {
+            get_scalar_field_owner(scalar_field_path, position)
+        }
+        ResolutionPath::LinkedField(linked_field_path) => {
+            get_linked_field_owner(linked_field_path, position)
+        }
+        ResolutionPath::FragmentSpread(fragment_spread_path) => {
+            get_fragment_spread_owner(fragment_spread_path, position)
+        }
+        ResolutionPath::Directive(directive_path) => get_directive_owner(directive_path, position),
+        ResolutionPath::Argument(ArgumentPath { inner: _, parent })
+        | ResolutionPath::Ident(IdentPath {
+            inner: _,
+            parent: IdentParent::ArgumentName(ArgumentPath { inner: _, parent }),
+        }) => get_argument_owner(parent, position),
+        ResolutionPath::VariableIdentifier(VariableIdentifierPath {
+            inner: _,
+            parent: VariableIdentifierParent::Value(ValuePath { inner: _, parent }),
+        }) => get_argument_owner(&parent.find_enclosing_argument_path().parent, position),
+        ResolutionPath::ValueList(Path {
+            inner: _,
+            parent: value_path,
+        })
+        | ResolutionPath::ConstantObject(Path {
+            inner: _,
+            parent: value_path,
+        }) => get_argument_owner(
+            &value_path.parent.find_enclosing_argument_path().parent,
+            position,
+        ),
+        ResolutionPath::ConstantInt(Path {
+            inner: _,
+            parent: constant_value_path,
+        })
+        | ResolutionPath::ConstantFloat(Path {
+            inner: _,
+            parent: constant_value_path,
+        })
+        | ResolutionPath::ConstantString(Path {
+            inner: _,
+            parent: constant_value_path,
+        })
+        | ResolutionPath::ConstantBoolean(Path {
+            inner: _,
+            parent: constant_value_path,
+        })
+        | ResolutionPath::ConstantNull(Path {
+            inner: _,
+            parent: constant_value_path,
+        })
+        | ResolutionPath::ConstantEnum(Path {
+            inner: _,
+            parent: constant_value_path,
+        }) => get_constant_value_owner(constant_value_path, position),
+        ResolutionPath::ConstantList(Path {
+            inner: _,
+            parent: constant_value_path,
+        }) => get_constant_value_owner(constant_value_path, position),
+        ResolutionPath::ConstantObj(Path {
+            inner: _,
+            parent: constant_value_path,
+        }) => get_constant_value_owner(constant_value_path, position),
+        _ => None,
+    }
+}
+
+fn get_constant_value_owner<'a>(
+    constant_value_path: &'a ConstantValuePath<'a>,
+    position: Span,
+) -> Option<ArgumentListOwner<'a>> {
+    match constant_value_path.parent.find_constant_value_root() {
+        ConstantValueRoot::Argument(argument_path) => {
+            get_argument_owner(&argument_path.parent, position)
+        }
+        _ => None,
+    }
+}
+
+fn get_argument_owner<'a>(
+    argument_parent: &'a ArgumentParent<'a>,
+    position: Span,
+) -> Option<ArgumentListOwner<'a>> {
+    match argument_parent.find_argument_root() {
+        ArgumentRoot::ScalarField(scalar_field_path) => {
+            get_scalar_field_owner(scalar_field_path, position)
+        }
+        ArgumentRoot::LinkedField(linked_field_path) => {
+            get_linked_field_owner(linked_field_path, position)
+        }
+        ArgumentRoot::FragmentSpread(fragment_spread_path) => {
+            get_fragment_spread_owner(fragment_spread_path, position)
+        }
+        ArgumentRoot::Directive(directive_path) => get_directive_owner(directive_path, position),
+    }
+}
+
+fn get_scalar_field_owner<'a>(
+    scalar_field_path: &'a ScalarFieldPath<'a>,
+    position: Span,
+) -> Option<ArgumentListOwner<'a>> {
+    let field = scalar_field_path.inner;
+    let arguments = field.arguments.as_ref()?;
+    arguments
+        .span
+        .contains(position)
+        .then_some(ArgumentListOwner::Field {
+            name: field.name.value,
+            selection_parent: &scalar_field_path.parent.parent,
+            arguments,
+        })
+}
+
+fn get_linked_field_owner<'a>(
+    linked_field_path: &'a LinkedFieldPath<'a>,
+    position: Span,
+) -> Option<ArgumentListOwner<'a>> {
+    let field = linked_field_path.inner;
+    let arguments = field.arguments.as_ref()?;
+    arguments
+        .span
+        .contains(position)
+        .then_some(ArgumentListOwner::Field {
+            name: field.name.value,
+            selection_parent: &linked_field_path.parent.parent,
+            arguments,
+        })
+}
+
+fn get_fragment_spread_owner<'a>(
+    fragment_spread_path: &'a FragmentSpreadPath<'a>,
+    position: Span,
+) -> Option<ArgumentListOwner<'a>> {
+    let spread = fragment_spread_path.inner;
+    let arguments = spread.arguments.as_ref()?;
+    arguments
+        .span
+        .contains(position)
+        .then_some(ArgumentListOwner::FragmentSpread { spread, arguments })
+}
+
+/// Only the `@arguments` of a fragment spread have a signature.
+fn get_directive_owner<'a>(
+    directive_path: &'a DirectivePath<'a>,
+    position: Span,
+) -> Option<ArgumentListOwner<'a>> {
+    let DirectivePath {
+        inner: directive,
+        parent: DirectiveParent::FragmentSpread(fragment_spread_path),
+    } = directive_path
+    else {
+        return None;
+    };
+    if directive.name.value != *DIRECTIVE_ARGUMENTS {
+        return None;
+    }
+    let arguments = directive.arguments.as_ref()?;
+    arguments
+        .span
+        .contains(position)
+        .then_some(ArgumentListOwner::FragmentSpread {
+            spread: fragment_spread_path.inner,
+            arguments,
+        })
+}
+
+/// The active parameter is the one of the argument at the position. After the
+/// last argument, or while its name is typed, it is the first parameter that
+/// is not passed yet and starts with the typed name.
+fn get_active_parameter(
+    parameters: &[Parameter],
+    arguments: &List<Argument>,
+    position: Span,
+) -> Option<u32> {
+    let current_argument = arguments.items.iter().find(|argument| {
+        argument.span.start <= position.start && position.end <= argument.span.end
+    });
+    if let Some(argument) = current_argument {
+        if let Some(index) = parameters
+            .iter()
+            .position(|parameter| parameter.name == argument.name.value)
+        {
+            return Some(index as u32);
+        }
+    }
+    let typed_name = current_argument.map_or("", |argument| argument.name.value.lookup());
+    parameters
+        .iter()
+        .position(|parameter| {
+            parameter.name.lookup().starts_with(typed_name)
+                && !arguments
+                    .items
+                    .iter()
+                    .any(|argument| argument.name.value == parameter.name)
+        })
+        .map(|index| index as u32)
+}
+
+fn join_parameter_labels(parameters: &[Parameter]) -> String {
+    parameters
+        .iter()
+        .map(|parameter| parameter.label.as_str())
+        .collect::<Vec<_>>()
+        .join(", ")
+}
+
+/// Parameters are labeled with their offsets in the signature label, which
+/// lists them in order after the opening parenthesis.
+fn get_parameter_information(label: &str, parameters: Vec<Parameter>) -> Vec<ParameterInformation> {
+    let mut offset = label.find('(').map_or(0, |index| index + 1);
+    parameters
+        .into_iter()
+        .map(|parameter| {
+            let start = label[..offset].encode_utf16().count() as u32;
+            let end = start + parameter.label.encode_utf16().count() as u32;
+            offset += parameter.label.len() + ", ".len();
+            ParameterInformation {
+                label: ParameterLabel::LabelOffsets([start, end]),
+                documentation: parameter.description.map(to_markdown),
+            }
+        })
+        .collect()
+}
+
+fn to_markdown(value: String) -> Documentation {
+    Documentation::MarkupContent(MarkupContent {
+        kind: MarkupKind::Markdown,
+        value,
+    })
+}
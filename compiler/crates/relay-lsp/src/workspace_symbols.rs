@@ -0,0 +1,141 @@
+/*
+ * Copyright (c) Meta Platforms, Inc. and affiliates.
+ *
+ * This source code is licensed under the MIT license found in the
+ * LICENSE file in the root directory of this source tree.
+ */
+
+//! Utilities for providing the workspace symbol feature
+
+use common::Location;
+use common::NamedItem;
+use docblock_shared::RELAY_RESOLVER_DIRECTIVE_NAME;
+use graphql_ir::OperationDefinition;
+use graphql_ir::Program;
+use graphql_syntax::OperationKind;
+use lsp_types::request::Request;
+use lsp_types::request::WorkspaceSymbolRequest;
+use lsp_types::SymbolInformation;
+use lsp_types::SymbolKind;
+use lsp_types::WorkspaceSymbolResponse;
+use schema::Schema;
+
+use crate::lsp_runtime_error::LSPRuntimeResult;
+use crate::server::GlobalState;
+
+/// A fragment, operation or Relay Resolver field matching a workspace symbol
+/// query.
+#[derive(Debug, PartialEq)]
+pub struct GraphQLWorkspaceSymbol {
+    pub name: String,
+    pub kind: SymbolKind,
+    /// The parent type of Relay Resolver fields.
+    pub container_name: Option<String>,
+    pub location: Location,
+}
+
+pub fn on_workspace_symbol(
+    state: &impl GlobalState,
+    params: <WorkspaceSymbolRequest as Request>::Params,
+) -> LSPRuntimeResult<<WorkspaceSymbolRequest as Request>::Result> {
+    let mut symbols = vec![];
+    for project_name in state.get_project_names() {
+        let program = state.get_program(&project_name)?;
+        symbols.extend(get_workspace_symbols(&program, &params.query));
+    }
+    symbols.sort_by(|a, b| {
+        (&a.name, &a.container_name, a.location).cmp(&(&b.name, &b.container_name, b.location))
+    });
+    // Projects extending a base project share its definitions.
+    symbols.dedup();
+
+    let symbols = symbols
+        .into_iter()
+        .filter_map(|symbol| {
+            // Generated definitions have no location in a file.
+            let location = state
+                .transform_relay_location_in_editor_to_lsp_location(symbol.location)
+                .ok()?;
+            #[allow(deprecated)]
+            Some(SymbolInformation {
+                name: symbol.name,
+                kind: symbol.kind,
+                tags: None,
+                deprecated: None,
+                location,
+                container_name: symbol.container_name,
+            })
+        })
+        .collect();
+
+    Ok(Some(WorkspaceSymbolResponse::Flat(symbols)))
+}
+
+/// Returns the fragments, operations and Relay Resolver fields of the program
+/// whose name matches the query. The characters of the query should appear in
+/// the name in the same order, ignoring case. Resolver fields are also matched
+/// by their `Type.field` coordinate.
+pub fn get_workspace_symbols(program: &Program, query: &str) -> Vec<GraphQLWorkspaceSymbol> {
+    let query = query.to_lowercase();
+    let mut symbols = vec![];
+    for fragment in program.fragments() {
+        let name = fragment.name.item.to_string();
+        if matches_query(&name, &query) {
+            symbols.push(GraphQLWorkspaceSymbol {
+                name,
+                kind: SymbolKind::CLASS,
+                container_name: None,
+                location: fragment.name.location,
+            });
+        }
+    }
+    for operation in program.operations() {
+        let name = operation.name.item.to_string();
+        if matches_query(&name, &query) {
+            symbols.push(GraphQLWorkspaceSymbol {
+                name,
+                kind: get_operation_symbol_kind(operation),
+                container_name: None,
+                location: operation.name.location,
+            });
+        }
+    }
+    let schema = &program.schema;
+    for field in schema.get_fields() {
+        if field
+            .directives
+            .named(*RELAY_RESOLVER_DIRECTIVE_NAME)
+            .is_none()
+        {
+            continue;
+        }
+        let Some(parent_type) = field.parent_type else {
+            continue;
+        };
+        let name = field.name.item.to_string();
+        let type_name = schema.get_type_name(parent_type).to_string();
+        if matches_query(&format!("{}.{}", type_name, name), &query) {
+            symbols.push(GraphQLWorkspaceSymbol {
+                name,
+                kind: SymbolKind::PROPERTY,
+                container_name: Some(type_name),
+                location: field.name.location,
+            });
+        }
+    }
+    symbols
+}
+
+fn get_operation_symbol_kind(operation: &OperationDefinition) -> SymbolKind {
+    match operation.kind {
+        OperationKind::Query => SymbolKind::FUNCTION,
+        OperationKind::Mutation | OperationKind::Subscription => SymbolKind::METHOD,
+    }
+}
+
+fn matches_query(name: &str, lowercase_query: &str) -> bool {
+    let mut name_chars = name.chars().flat_map(char::to_lowercase);
+    lowercase_query
+        .chars()
+        .all(|query_char| name_chars.any(|name_char| name_char == query_char))
+}
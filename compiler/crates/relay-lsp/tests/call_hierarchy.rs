@@ -0,0 +1,124 @@
+/*
+ * Copyright (c) Meta Platforms, Inc. and affiliates.
+ *
+ * This source code is licensed under the MIT license found in the
+ * LICENSE file in the root directory of this source tree.
+ */
+
+use std::sync::Arc;
+
+use common::Location;
+use common::SourceLocationKey;
+use fixture_tests::Fixture;
+use graphql_ir::build;
+use graphql_ir::Program;
+use graphql_syntax::parse_executable;
+use graphql_syntax::ExecutableDefinition;
+use graphql_test_helpers::diagnostics_to_sorted_string;
+use intern::string_key::Intern;
+use relay_lsp::call_hierarchy::get_call_hierarchy_item;
+use relay_lsp::call_hierarchy::get_incoming_calls;
+use relay_lsp::call_hierarchy::get_outgoing_calls;
+use relay_lsp::call_hierarchy::CallHierarchyIndex;
+use relay_lsp::call_hierarchy::CallHierarchyNode;
+use relay_lsp::call_hierarchy::GraphQLCallHierarchyCall;
+use relay_test_schema::get_test_schema_with_located_extensions;
+
+// The fixture contains the document, and optionally schema extensions with
+// Relay Resolvers after `%extensions%`. Prints the calls of every operation,
+// fragment and resolver, which are listed after `%resolvers%` as
+// `Type.field`, one per line.
+pub async fn transform_fixture(fixture: &Fixture<'_>) -> Result<String, String> {
+    let (source, extensions) = fixture
+        .content
+        .split_once("%extensions%")
+        .unwrap_or((fixture.content, ""));
+    let (extensions, resolvers) = extensions
+        .split_once("%resolvers%")
+        .unwrap_or((extensions, ""));
+
+    let source_location = SourceLocationKey::embedded(fixture.file_name, 0);
+    let extensions_location = SourceLocationKey::embedded(fixture.file_name, 1);
+    let schema = get_test_schema_with_located_extensions(extensions, extensions_location);
+    let ast = parse_executable(source, source_location)
+        .map_err(|diagnostics| diagnostics_to_sorted_string(source, &diagnostics))?;
+    let ir = build(&schema, &ast.definitions)
+        .map_err(|diagnostics| diagnostics_to_sorted_string(source, &diagnostics))?;
+    let program = Program::from_definitions(Arc::clone(&schema), ir);
+    let index = CallHierarchyIndex::new(&program);
+
+    let mut nodes = ast
+        .definitions
+        .iter()
+        .filter_map(|definition| match definition {
+            ExecutableDefinition::Operation(operation) => Some(CallHierarchyNode::Operation {
+                name: operation.name.as_ref()?.value,
+            }),
+            ExecutableDefinition::Fragment(fragment) => Some(CallHierarchyNode::Fragment {
+                name: fragment.name.value,
+            }),
+        })
+        .collect::<Vec<_>>();
+    nodes.extend(resolvers.lines().filter_map(|line| {
+        let (parent_type, field_name) = line.trim().split_once('.')?;
+        Some(CallHierarchyNode::Resolver {
+            parent_type: parent_type.intern(),
+            field_name: field_name.intern(),
+        })
+    }));
+
+    let print_location = |location: Location| {
+        let text = if location.source_location() == source_location {
+            source
+        } else {
+            extensions
+        };
+        let span = location.span();
+        format!(
+            "`{}` {}..{}",
+            &text[span.start as usize..span.end as usize],
+            span.start,
+            span.end
+        )
+    };
+    let print_calls = |direction: &str, calls: Vec<GraphQLCallHierarchyCall>| {
+        calls
+            .into_iter()
+            .map(|call| {
+                format!(
+                    "  {} {}: [{}]",
+                    direction,
+                    call.item.name,
+                    call.locations
+                        .into_iter()
+                        .map(print_location)
+                        .collect::<Vec<_>>()
+                        .join(", ")
+                )
+            })
+            .collect::<Vec<_>>()
+    };
+
+    Ok(nodes
+        .into_iter()
+        .map(|node| match get_call_hierarchy_item(&program, node) {
+            Some(item) => {
+                let mut lines = vec![format!(
+                    "{} ({:?}, {}) at {}",
+                    item.name,
+                    item.kind,
+                    item.detail.unwrap_or_default(),
+                    print_location(item.location)
+                )];
+                lines.extend(print_calls(
+                    "<-",
+                    get_incoming_calls(&program, &index, node),
+                ));
+                lines.extend(print_calls("->", get_outgoing_calls(&program, node)));
+                lines.join("\n")
+            }
+            None => format!("{:?}: <NO ITEM>", node),
+        })
+        .collect::<Vec<_>>()
+        .join("\n\n"))
+}
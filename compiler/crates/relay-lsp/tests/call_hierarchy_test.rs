@@ -0,0 +1,27 @@
+/*
+ * Copyright (c) Meta Platforms, Inc. and affiliates.
+ *
+ * This source code is licensed under the MIT license found in the
+ * LICENSE file in the root directory of this source tree.
+ *
+ * @generated SignedSource<<01350588f1f61c54a1494d898b5ff047>>
+ */
+
+mod call_hierarchy;
+
+use call_hierarchy::transform_fixture;
+use fixture_tests::test_fixture;
+
+#[tokio::test]
+async fn fragment_spreads() {
+    let input = include_str!("call_hierarchy/fixtures/fragment-spreads.graphql");
+    let expected = include_str!("call_hierarchy/fixtures/fragment-spreads.expected");
+    test_fixture(transform_fixture, file!(), "fragment-spreads.graphql", "call_hierarchy/fixtures/fragment-spreads.expected", input, expected).await;
+}
+
+#[tokio::test]
+async fn relay_resolvers() {
+    let input = include_str!("call_hierarchy/fixtures/relay-resolvers.graphql");
+    let expected = include_str!("call_hierarchy/fixtures/relay-resolvers.expected");
+    test_fixture(transform_fixture, file!(), "relay-resolvers.graphql", "call_hierarchy/fixtures/relay-resolvers.expected", input, expected).await;
+}
@@ -0,0 +1,208 @@
+/*
+ * Copyright (c) Meta Platforms, Inc. and affiliates.
+ *
+ * This source code is licensed under the MIT license found in the
+ * LICENSE file in the root directory of this source tree.
+ */
+
+use common::SourceLocationKey;
+use common::Span;
+use fixture_tests::Fixture;
+use graphql_ir::ValidationFix;
+use graphql_syntax::parse_executable_with_features;
+use graphql_syntax::ExecutableDocument;
+use graphql_syntax::FragmentArgumentSyntaxKind;
+use graphql_syntax::ParserFeatures;
+use graphql_test_helpers::diagnostics_to_sorted_string;
+use intern::string_key::Intern;
+use relay_lsp::build_ir_for_lsp;
+use relay_lsp::code_action::get_definition_removal_location;
+use relay_lsp::code_action::get_extracted_fragment;
+use relay_lsp::code_action::get_inlined_fragment_spread;
+use relay_lsp::code_action::get_validation_fix_edits;
+use relay_test_schema::get_test_schema;
+
+// The first line of the fixture is a comment with the code action to apply:
+// - `# validation-fix: <fix>` applies the `ValidationFix`, as the JSON of the
+//   data of a diagnostic, to every definition of the document.
+// - `# extract-fragment` extracts the selections between the `|` markers of
+//   the document into a fragment.
+// - `# inline-fragment: <spread>` inlines the fragment spread with the text.
+//   The fragment of the program is the one defined in the document, or after
+//   `%fragment%` if any.
+// Prints the titles of the code actions, and the document with their edits.
+pub async fn transform_fixture(fixture: &Fixture<'_>) -> Result<String, String> {
+    let (header, source) = fixture.content.split_once('\n').unwrap();
+    let header = header
+        .strip_prefix('#')
+        .expect("Expected the first line to be `# <action>: <argument>`");
+    let (action, argument) = header.split_once(':').unwrap_or((header, ""));
+    let (action, argument) = (action.trim(), argument.trim());
+
+    match action {
+        "validation-fix" => {
+            let fix: ValidationFix = serde_json::from_str(argument).unwrap();
+            let document = parse(fixture, source)?;
+            let mut titles = vec![];
+            let mut edits = vec![];
+            for definition in &document.definitions {
+                if let Some((title, definition_edits)) = get_validation_fix_edits(&fix, definition)
+                {
+                    titles.push(title);
+                    edits.extend(definition_edits);
+                }
+            }
+            Ok(print_result(&titles, source, edits))
+        }
+        "extract-fragment" => {
+            let start = source.find('|').unwrap();
+            let end = source.rfind('|').unwrap() - 1;
+            let source = source.replace('|', "");
+            let document = parse(fixture, &source)?;
+            let span = Span::new(start as u32, end as u32);
+            let Some(definition) = document
+                .definitions
+                .iter()
+                .find(|definition| definition.location().span().contains(span))
+            else {
+                return Ok("<NO CODE ACTION>".to_string());
+            };
+            match get_extracted_fragment(
+                &get_test_schema(),
+                &source,
+                definition,
+                span,
+                |prop_name| Some(format!("Component_{}", prop_name)),
+            ) {
+                Some(extracted_fragment) => Ok(print_result(
+                    &[format!(
+                        "Extract selections into fragment '{}'",
+                        extracted_fragment.name
+                    )],
+                    &source,
+                    extracted_fragment.edits,
+                )),
+                None => Ok("<NO CODE ACTION>".to_string()),
+            }
+        }
+        "inline-fragment" => {
+            let (source, fragment_source) = match source.split_once("%fragment%") {
+                Some((source, fragment_source)) => (source, Some(fragment_source)),
+                None => (source, None),
+            };
+            let schema = get_test_schema();
+            let document = parse(fixture, source)?;
+            let definitions = build_ir_for_lsp(&schema, &document.definitions)
+                .map_err(|diagnostics| diagnostics_to_sorted_string(source, &diagnostics))?;
+            let fragment_definitions = match fragment_source {
+                Some(fragment_source) => {
+                    build_ir_for_lsp(&schema, &parse(fixture, fragment_source)?.definitions)
+                        .map_err(|diagnostics| {
+                            diagnostics_to_sorted_string(fragment_source, &diagnostics)
+                        })?
+                }
+                None => definitions.clone(),
+            };
+            let fragment_name = argument
+                .trim_start_matches("...")
+                .split(|chr: char| !chr.is_alphanumeric() && chr != '_')
+                .next()
+                .unwrap()
+                .intern();
+            let fragment = fragment_definitions
+                .iter()
+                .find_map(|definition| match definition {
+                    graphql_ir::ExecutableDefinition::Fragment(fragment)
+                        if fragment.name.item.0 == fragment_name =>
+                    {
+                        Some(fragment)
+                    }
+                    _ => None,
+                })
+                .unwrap();
+
+            let start = source.find(argument).unwrap();
+            let spread_span = Span::from_usize(start, start + argument.len());
+            let definition_index = document
+                .definitions
+                .iter()
+                .position(|definition| definition.location().span().contains(spread_span))
+                .unwrap();
+            let indentation = source[..start]
+                .rsplit('\n')
+                .next()
+                .unwrap()
+                .chars()
+                .take_while(|chr| chr.is_whitespace())
+                .collect::<String>();
+            let Some(inlined_text) = get_inlined_fragment_spread(
+                &schema,
+                &definitions[definition_index],
+                fragment,
+                spread_span,
+                &indentation,
+            ) else {
+                return Ok("<NO CODE ACTION>".to_string());
+            };
+
+            let mut results = vec![print_result(
+                &[format!("Inline fragment '{}'", fragment_name)],
+                source,
+                vec![(spread_span, inlined_text.clone())],
+            )];
+            if fragment_source.is_none() {
+                if let Some(removal_location) =
+                    get_definition_removal_location(&document.definitions, fragment_name)
+                {
+                    results.push(print_result(
+                        &[format!(
+                            "Inline fragment '{}' and delete the unused fragment",
+                            fragment_name
+                        )],
+                        source,
+                        vec![
+                            (spread_span, inlined_text),
+                            (removal_location.span(), String::new()),
+                        ],
+                    ));
+                }
+            }
+            Ok(results.join(
+                "\n\n-------------------------------------------------------------------------------\n\n",
+            ))
+        }
+        _ => panic!("Unknown code action `{}`", action),
+    }
+}
+
+fn parse(fixture: &Fixture<'_>, source: &str) -> Result<ExecutableDocument, String> {
+    parse_executable_with_features(
+        source,
+        SourceLocationKey::standalone(fixture.file_name),
+        ParserFeatures {
+            fragment_argument_capability:
+                FragmentArgumentSyntaxKind::SpreadArgumentsAndFragmentVariableDefinitions,
+        },
+    )
+    .map_err(|diagnostics| diagnostics_to_sorted_string(source, &diagnostics))
+}
+
+fn print_result(titles: &[String], source: &str, edits: Vec<(Span, String)>) -> String {
+    format!(
+        "{}\n\n{}",
+        titles.join("\n"),
+        apply_edits(source, edits).trim_end()
+    )
+}
+
+/// Replaces the spans of the source with the new texts of the edits, which
+/// don't overlap.
+fn apply_edits(source: &str, mut edits: Vec<(Span, String)>) -> String {
+    edits.sort_by_key(|(span, _)| std::cmp::Reverse(span.start));
+    let mut result = source.to_string();
+    for (span, new_text) in edits {
+        let (start, end) = span.as_usize();
+        result.replace_range(start..end, &new_text);
+    }
+    result
+}
@@ -0,0 +1,125 @@
+/*
+ * Copyright (c) Meta Platforms, Inc. and affiliates.
+ *
+ * This source code is licensed under the MIT license found in the
+ * LICENSE file in the root directory of this source tree.
+ *
+ * @generated SignedSource<<b0e34f1ab1b5eef8e9125663e16b4790>>
+ */
+
+mod code_action;
+
+use code_action::transform_fixture;
+use fixture_tests::test_fixture;
+
+#[tokio::test]
+async fn declare_fragment_argument() {
+    let input = include_str!("code_action/fixtures/declare-fragment-argument.graphql");
+    let expected = include_str!("code_action/fixtures/declare-fragment-argument.expected");
+    test_fixture(transform_fixture, file!(), "declare-fragment-argument.graphql", "code_action/fixtures/declare-fragment-argument.expected", input, expected).await;
+}
+
+#[tokio::test]
+async fn extract_selections() {
+    let input = include_str!("code_action/fixtures/extract-selections.graphql");
+    let expected = include_str!("code_action/fixtures/extract-selections.expected");
+    test_fixture(transform_fixture, file!(), "extract-selections.graphql", "code_action/fixtures/extract-selections.expected", input, expected).await;
+}
+
+#[tokio::test]
+async fn extract_selections_in_inline_fragment() {
+    let input = include_str!("code_action/fixtures/extract-selections-in-inline-fragment.graphql");
+    let expected = include_str!("code_action/fixtures/extract-selections-in-inline-fragment.expected");
+    test_fixture(transform_fixture, file!(), "extract-selections-in-inline-fragment.graphql", "code_action/fixtures/extract-selections-in-inline-fragment.expected", input, expected).await;
+}
+
+#[tokio::test]
+async fn extract_selections_outside_selections() {
+    let input = include_str!("code_action/fixtures/extract-selections-outside-selections.graphql");
+    let expected = include_str!("code_action/fixtures/extract-selections-outside-selections.expected");
+    test_fixture(transform_fixture, file!(), "extract-selections-outside-selections.graphql", "code_action/fixtures/extract-selections-outside-selections.expected", input, expected).await;
+}
+
+#[tokio::test]
+async fn extract_selections_with_argument_definitions() {
+    let input = include_str!("code_action/fixtures/extract-selections-with-argument-definitions.graphql");
+    let expected = include_str!("code_action/fixtures/extract-selections-with-argument-definitions.expected");
+    test_fixture(transform_fixture, file!(), "extract-selections-with-argument-definitions.graphql", "code_action/fixtures/extract-selections-with-argument-definitions.expected", input, expected).await;
+}
+
+#[tokio::test]
+async fn extract_selections_with_default_values() {
+    let input = include_str!("code_action/fixtures/extract-selections-with-default-values.graphql");
+    let expected = include_str!("code_action/fixtures/extract-selections-with-default-values.expected");
+    test_fixture(transform_fixture, file!(), "extract-selections-with-default-values.graphql", "code_action/fixtures/extract-selections-with-default-values.expected", input, expected).await;
+}
+
+#[tokio::test]
+async fn extract_selections_with_fragment_variables() {
+    let input = include_str!("code_action/fixtures/extract-selections-with-fragment-variables.graphql");
+    let expected = include_str!("code_action/fixtures/extract-selections-with-fragment-variables.expected");
+    test_fixture(transform_fixture, file!(), "extract-selections-with-fragment-variables.graphql", "code_action/fixtures/extract-selections-with-fragment-variables.expected", input, expected).await;
+}
+
+#[tokio::test]
+async fn extract_selections_with_variable_default_values() {
+    let input = include_str!("code_action/fixtures/extract-selections-with-variable-default-values.graphql");
+    let expected = include_str!("code_action/fixtures/extract-selections-with-variable-default-values.expected");
+    test_fixture(transform_fixture, file!(), "extract-selections-with-variable-default-values.graphql", "code_action/fixtures/extract-selections-with-variable-default-values.expected", input, expected).await;
+}
+
+#[tokio::test]
+async fn extract_selections_with_variables() {
+    let input = include_str!("code_action/fixtures/extract-selections-with-variables.graphql");
+    let expected = include_str!("code_action/fixtures/extract-selections-with-variables.expected");
+    test_fixture(transform_fixture, file!(), "extract-selections-with-variables.graphql", "code_action/fixtures/extract-selections-with-variables.expected", input, expected).await;
+}
+
+#[tokio::test]
+async fn inline_fragment() {
+    let input = include_str!("code_action/fixtures/inline-fragment.graphql");
+    let expected = include_str!("code_action/fixtures/inline-fragment.expected");
+    test_fixture(transform_fixture, file!(), "inline-fragment.graphql", "code_action/fixtures/inline-fragment.expected", input, expected).await;
+}
+
+#[tokio::test]
+async fn inline_fragment_with_arguments() {
+    let input = include_str!("code_action/fixtures/inline-fragment-with-arguments.graphql");
+    let expected = include_str!("code_action/fixtures/inline-fragment-with-arguments.expected");
+    test_fixture(transform_fixture, file!(), "inline-fragment-with-arguments.graphql", "code_action/fixtures/inline-fragment-with-arguments.expected", input, expected).await;
+}
+
+#[tokio::test]
+async fn inline_fragment_with_type_condition() {
+    let input = include_str!("code_action/fixtures/inline-fragment-with-type-condition.graphql");
+    let expected = include_str!("code_action/fixtures/inline-fragment-with-type-condition.expected");
+    test_fixture(transform_fixture, file!(), "inline-fragment-with-type-condition.graphql", "code_action/fixtures/inline-fragment-with-type-condition.expected", input, expected).await;
+}
+
+#[tokio::test]
+async fn inline_fragment_with_unset_argument() {
+    let input = include_str!("code_action/fixtures/inline-fragment-with-unset-argument.graphql");
+    let expected = include_str!("code_action/fixtures/inline-fragment-with-unset-argument.expected");
+    test_fixture(transform_fixture, file!(), "inline-fragment-with-unset-argument.graphql", "code_action/fixtures/inline-fragment-with-unset-argument.expected", input, expected).await;
+}
+
+#[tokio::test]
+async fn inline_fragment_without_spread_only_directives() {
+    let input = include_str!("code_action/fixtures/inline-fragment-without-spread-only-directives.graphql");
+    let expected = include_str!("code_action/fixtures/inline-fragment-without-spread-only-directives.expected");
+    test_fixture(transform_fixture, file!(), "inline-fragment-without-spread-only-directives.graphql", "code_action/fixtures/inline-fragment-without-spread-only-directives.expected", input, expected).await;
+}
+
+#[tokio::test]
+async fn remove_argument_definition() {
+    let input = include_str!("code_action/fixtures/remove-argument-definition.graphql");
+    let expected = include_str!("code_action/fixtures/remove-argument-definition.expected");
+    test_fixture(transform_fixture, file!(), "remove-argument-definition.graphql", "code_action/fixtures/remove-argument-definition.expected", input, expected).await;
+}
+
+#[tokio::test]
+async fn remove_variable_definition() {
+    let input = include_str!("code_action/fixtures/remove-variable-definition.graphql");
+    let expected = include_str!("code_action/fixtures/remove-variable-definition.expected");
+    test_fixture(transform_fixture, file!(), "remove-variable-definition.graphql", "code_action/fixtures/remove-variable-definition.expected", input, expected).await;
+}
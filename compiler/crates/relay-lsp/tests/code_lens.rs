@@ -0,0 +1,95 @@
+/*
+ * Copyright (c) Meta Platforms, Inc. and affiliates.
+ *
+ * This source code is licensed under the MIT license found in the
+ * LICENSE file in the root directory of this source tree.
+ */
+
+use std::sync::Arc;
+
+use common::SourceLocationKey;
+use fixture_tests::Fixture;
+use graphql_ir::build;
+use graphql_ir::Program;
+use graphql_syntax::parse_executable;
+use graphql_test_helpers::diagnostics_to_sorted_string;
+use relay_compiler::ArtifactRecord;
+use relay_compiler::ProjectConfig;
+use relay_lsp::code_lens::get_code_lenses;
+use relay_lsp::code_lens::GraphQLCodeLensKind;
+use relay_test_schema::get_test_schema;
+
+// The fixture contains the document, and optionally the ids of the artifacts
+// of its operations after `%artifact_ids%`, one
+// `OperationName persisted_operation_id|text_hash id` per line.
+pub async fn transform_fixture(fixture: &Fixture<'_>) -> Result<String, String> {
+    let (source, artifact_ids) = fixture
+        .content
+        .split_once("%artifact_ids%")
+        .unwrap_or((fixture.content, ""));
+    let artifact_ids = artifact_ids
+        .lines()
+        .filter_map(|line| {
+            let mut parts = line.split_whitespace();
+            Some((parts.next()?, parts.next()?, parts.next()?))
+        })
+        .collect::<Vec<_>>();
+    let get_artifact_id = |name: &str, kind: &str| {
+        artifact_ids
+            .iter()
+            .find(|(operation_name, id_kind, _)| *operation_name == name && *id_kind == kind)
+            .map(|(_, _, id)| id.to_string())
+    };
+
+    let schema = get_test_schema();
+    let source_location = SourceLocationKey::standalone(fixture.file_name);
+    let ast = parse_executable(source, source_location)
+        .map_err(|diagnostics| diagnostics_to_sorted_string(source, &diagnostics))?;
+    let ir = build(&schema, &ast.definitions)
+        .map_err(|diagnostics| diagnostics_to_sorted_string(source, &diagnostics))?;
+    let program = Program::from_definitions(Arc::clone(&schema), ir);
+
+    let project_config = ProjectConfig::default();
+    let code_lenses = get_code_lenses(&ast.definitions, &program, |definition_name| {
+        let name = definition_name.item.to_string();
+        Some(ArtifactRecord {
+            path: project_config.artifact_path_for_definition(definition_name),
+            persisted_operation_id: get_artifact_id(&name, "persisted_operation_id"),
+            text_hash: get_artifact_id(&name, "text_hash"),
+        })
+    });
+
+    Ok(code_lenses
+        .into_iter()
+        .map(|code_lens| {
+            let span = code_lens.location.span();
+            let title = match code_lens.kind {
+                GraphQLCodeLensKind::FragmentSpreads(locations) => format!(
+                    "spreads: [{}]",
+                    locations
+                        .iter()
+                        .map(|location| format!(
+                            "{}..{}",
+                            location.span().start,
+                            location.span().end
+                        ))
+                        .collect::<Vec<_>>()
+                        .join(", ")
+                ),
+                GraphQLCodeLensKind::GeneratedArtifact(path) => {
+                    format!("artifact: {}", path.display())
+                }
+                GraphQLCodeLensKind::PersistedOperationId(id) => format!("persisted id: {}", id),
+                GraphQLCodeLensKind::OperationTextHash(text_hash) => {
+                    format!("text hash: {}", text_hash)
+                }
+            };
+            format!(
+                "`{}` {}",
+                &source[span.start as usize..span.end as usize],
+                title
+            )
+        })
+        .collect::<Vec<_>>()
+        .join("\n"))
+}
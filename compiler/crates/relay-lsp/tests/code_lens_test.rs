@@ -0,0 +1,27 @@
+/*
+ * Copyright (c) Meta Platforms, Inc. and affiliates.
+ *
+ * This source code is licensed under the MIT license found in the
+ * LICENSE file in the root directory of this source tree.
+ *
+ * @generated SignedSource<<138045c035dd7b0538b31888d4e17deb>>
+ */
+
+mod code_lens;
+
+use code_lens::transform_fixture;
+use fixture_tests::test_fixture;
+
+#[tokio::test]
+async fn fragments() {
+    let input = include_str!("code_lens/fixtures/fragments.graphql");
+    let expected = include_str!("code_lens/fixtures/fragments.expected");
+    test_fixture(transform_fixture, file!(), "fragments.graphql", "code_lens/fixtures/fragments.expected", input, expected).await;
+}
+
+#[tokio::test]
+async fn operations() {
+    let input = include_str!("code_lens/fixtures/operations.graphql");
+    let expected = include_str!("code_lens/fixtures/operations.expected");
+    test_fixture(transform_fixture, file!(), "operations.graphql", "code_lens/fixtures/operations.expected", input, expected).await;
+}
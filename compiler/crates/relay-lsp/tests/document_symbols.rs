@@ -0,0 +1,55 @@
+/*
+ * Copyright (c) Meta Platforms, Inc. and affiliates.
+ *
+ * This source code is licensed under the MIT license found in the
+ * LICENSE file in the root directory of this source tree.
+ */
+
+use common::SourceLocationKey;
+use fixture_tests::Fixture;
+use graphql_syntax::parse_executable;
+use graphql_syntax::parse_schema_document;
+use graphql_test_helpers::diagnostics_to_sorted_string;
+use relay_lsp::document_symbols::get_executable_document_symbols;
+use relay_lsp::document_symbols::get_schema_document_symbols;
+use relay_lsp::document_symbols::GraphQLSymbol;
+
+// Fixtures with `# schema` on the first line contain a schema document.
+pub async fn transform_fixture(fixture: &Fixture<'_>) -> Result<String, String> {
+    let source = fixture.content;
+    let source_location = SourceLocationKey::standalone(fixture.file_name);
+    let symbols = if source.starts_with("# schema") {
+        let document = parse_schema_document(source, source_location)
+            .map_err(|diagnostics| diagnostics_to_sorted_string(source, &diagnostics))?;
+        get_schema_document_symbols(&document)
+    } else {
+        let document = parse_executable(source, source_location)
+            .map_err(|diagnostics| diagnostics_to_sorted_string(source, &diagnostics))?;
+        get_executable_document_symbols(&document.definitions)
+    };
+
+    let mut output = vec![];
+    print_symbols(&symbols, source, 0, &mut output);
+    Ok(output.join("\n"))
+}
+
+fn print_symbols(symbols: &[GraphQLSymbol], source: &str, depth: usize, output: &mut Vec<String>) {
+    for symbol in symbols {
+        let span = symbol.location.span();
+        let name_span = symbol.name_location.span();
+        output.push(format!(
+            "{}{} [{:?}]{} {}..{} `{}`",
+            "  ".repeat(depth),
+            symbol.name,
+            symbol.kind,
+            symbol
+                .detail
+                .as_ref()
+                .map_or(String::new(), |detail| format!(" ({})", detail)),
+            span.start,
+            span.end,
+            &source[name_span.start as usize..name_span.end as usize],
+        ));
+        print_symbols(&symbol.children, source, depth + 1, output);
+    }
+}
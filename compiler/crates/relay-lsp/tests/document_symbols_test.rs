@@ -0,0 +1,27 @@
+/*
+ * Copyright (c) Meta Platforms, Inc. and affiliates.
+ *
+ * This source code is licensed under the MIT license found in the
+ * LICENSE file in the root directory of this source tree.
+ *
+ * @generated SignedSource<<0bbf8b9f814b85e263c5b24009c2b88a>>
+ */
+
+mod document_symbols;
+
+use document_symbols::transform_fixture;
+use fixture_tests::test_fixture;
+
+#[tokio::test]
+async fn operation_selections() {
+    let input = include_str!("document_symbols/fixtures/operation-selections.graphql");
+    let expected = include_str!("document_symbols/fixtures/operation-selections.expected");
+    test_fixture(transform_fixture, file!(), "operation-selections.graphql", "document_symbols/fixtures/operation-selections.expected", input, expected).await;
+}
+
+#[tokio::test]
+async fn schema_document() {
+    let input = include_str!("document_symbols/fixtures/schema-document.graphql");
+    let expected = include_str!("document_symbols/fixtures/schema-document.expected");
+    test_fixture(transform_fixture, file!(), "schema-document.graphql", "document_symbols/fixtures/schema-document.expected", input, expected).await;
+}
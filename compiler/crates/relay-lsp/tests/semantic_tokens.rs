@@ -0,0 +1,52 @@
+/*
+ * Copyright (c) Meta Platforms, Inc. and affiliates.
+ *
+ * This source code is licensed under the MIT license found in the
+ * LICENSE file in the root directory of this source tree.
+ */
+
+use common::SourceLocationKey;
+use fixture_tests::Fixture;
+use graphql_syntax::parse_executable;
+use graphql_test_helpers::diagnostics_to_sorted_string;
+use relay_lsp::semantic_tokens::get_semantic_tokens;
+use relay_test_schema::get_test_schema_with_extensions;
+
+// The fixture contains the documents, and the schema extensions after
+// `%extensions%`.
+pub async fn transform_fixture(fixture: &Fixture<'_>) -> Result<String, String> {
+    let (document, extensions) = fixture
+        .content
+        .split_once("%extensions%")
+        .unwrap_or((fixture.content, ""));
+
+    let schema = get_test_schema_with_extensions(extensions);
+    let source_location = SourceLocationKey::standalone(fixture.file_name);
+    let ast = parse_executable(document, source_location)
+        .map_err(|diagnostics| diagnostics_to_sorted_string(document, &diagnostics))?;
+
+    let tokens = get_semantic_tokens(&schema, &ast)
+        .into_iter()
+        .map(|token| {
+            let span = token.location.span();
+            let modifiers = token
+                .modifiers
+                .iter()
+                .map(|modifier| modifier.as_str())
+                .collect::<Vec<_>>();
+            format!(
+                "{}..{} `{}` [{}]{}",
+                span.start,
+                span.end,
+                &document[span.start as usize..span.end as usize],
+                token.token_type.as_str(),
+                if modifiers.is_empty() {
+                    String::new()
+                } else {
+                    format!(" ({})", modifiers.join(", "))
+                }
+            )
+        })
+        .collect::<Vec<_>>();
+    Ok(tokens.join("\n"))
+}
@@ -0,0 +1,27 @@
+/*
+ * Copyright (c) Meta Platforms, Inc. and affiliates.
+ *
+ * This source code is licensed under the MIT license found in the
+ * LICENSE file in the root directory of this source tree.
+ *
+ * @generated SignedSource<<45daba3a17e0afb137c42b1cf00f0832>>
+ */
+
+mod semantic_tokens;
+
+use semantic_tokens::transform_fixture;
+use fixture_tests::test_fixture;
+
+#[tokio::test]
+async fn fields() {
+    let input = include_str!("semantic_tokens/fixtures/fields.graphql");
+    let expected = include_str!("semantic_tokens/fixtures/fields.expected");
+    test_fixture(transform_fixture, file!(), "fields.graphql", "semantic_tokens/fixtures/fields.expected", input, expected).await;
+}
+
+#[tokio::test]
+async fn fragment_variables() {
+    let input = include_str!("semantic_tokens/fixtures/fragment-variables.graphql");
+    let expected = include_str!("semantic_tokens/fixtures/fragment-variables.expected");
+    test_fixture(transform_fixture, file!(), "fragment-variables.graphql", "semantic_tokens/fixtures/fragment-variables.expected", input, expected).await;
+}
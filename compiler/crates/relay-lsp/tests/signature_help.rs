@@ -0,0 +1,85 @@
+/*
+ * Copyright (c) Meta Platforms, Inc. and affiliates.
+ *
+ * This source code is licensed under the MIT license found in the
+ * LICENSE file in the root directory of this source tree.
+ */
+
+use std::sync::Arc;
+
+use common::SourceLocationKey;
+use common::Span;
+use fixture_tests::Fixture;
+use graphql_ir::build;
+use graphql_ir::Program;
+use graphql_syntax::parse_executable_with_error_recovery;
+use graphql_test_helpers::diagnostics_to_sorted_string;
+use lsp_types::Documentation;
+use lsp_types::ParameterLabel;
+use relay_lsp::signature_help::get_signature_help;
+use relay_test_schema::get_test_schema_with_extensions;
+use resolution_path::ResolvePosition;
+
+// The fixture contains the document, with `|` at the cursor, and the schema
+// extensions after `%extensions%`. The definitions other than the one with
+// the cursor must be valid.
+pub async fn transform_fixture(fixture: &Fixture<'_>) -> Result<String, String> {
+    let (document, extensions) = fixture
+        .content
+        .split_once("%extensions%")
+        .unwrap_or((fixture.content, ""));
+    let cursor_position = document.find('|').unwrap();
+    let source = document.replace('|', "");
+
+    let schema = get_test_schema_with_extensions(extensions);
+    let source_location = SourceLocationKey::standalone(fixture.file_name);
+    let ast = parse_executable_with_error_recovery(&source, source_location).item;
+    let valid_definitions = ast
+        .definitions
+        .iter()
+        .filter(|definition| {
+            !definition
+                .location()
+                .span()
+                .contains(Span::from_usize(cursor_position, cursor_position))
+        })
+        .cloned()
+        .collect::<Vec<_>>();
+    let ir = build(&schema, &valid_definitions)
+        .map_err(|diagnostics| diagnostics_to_sorted_string(&source, &diagnostics))?;
+    let program = Program::from_definitions(Arc::clone(&schema), ir);
+
+    let position = Span::from_usize(cursor_position, cursor_position);
+    let path = ast.resolve((), position);
+    let signature_help = get_signature_help(&path, position, &schema, schema.as_ref(), &program)
+        .ok_or("<NO SIGNATURE HELP>")?;
+
+    let mut output = vec![];
+    for signature in signature_help.signatures {
+        output.push(signature.label.clone());
+        if let Some(Documentation::MarkupContent(documentation)) = &signature.documentation {
+            output.push(format!("  {}", documentation.value));
+        }
+        for (index, parameter) in signature.parameters.unwrap_or_default().iter().enumerate() {
+            let ParameterLabel::LabelOffsets([start, end]) = parameter.label else {
+                panic!("Expected the parameter label to be offsets");
+            };
+            output.push(format!(
+                "  {} `{}`{}",
+                if signature.active_parameter == Some(index as u32) {
+                    "*"
+                } else {
+                    "-"
+                },
+                &signature.label[start as usize..end as usize],
+                match &parameter.documentation {
+                    Some(Documentation::MarkupContent(documentation)) => {
+                        format!(": {}", documentation.value)
+                    }
+                    _ => String::new(),
+                },
+            ));
+        }
+    }
+    Ok(output.join("\n"))
+}
@@ -0,0 +1,41 @@
+/*
+ * Copyright (c) Meta Platforms, Inc. and affiliates.
+ *
+ * This source code is licensed under the MIT license found in the
+ * LICENSE file in the root directory of this source tree.
+ *
+ * @generated SignedSource<<739341ab7016d85e0f5ef238e1e0e3cc>>
+ */
+
+mod signature_help;
+
+use signature_help::transform_fixture;
+use fixture_tests::test_fixture;
+
+#[tokio::test]
+async fn field_argument_value() {
+    let input = include_str!("signature_help/fixtures/field-argument-value.graphql");
+    let expected = include_str!("signature_help/fixtures/field-argument-value.expected");
+    test_fixture(transform_fixture, file!(), "field-argument-value.graphql", "signature_help/fixtures/field-argument-value.expected", input, expected).await;
+}
+
+#[tokio::test]
+async fn field_arguments() {
+    let input = include_str!("signature_help/fixtures/field-arguments.graphql");
+    let expected = include_str!("signature_help/fixtures/field-arguments.expected");
+    test_fixture(transform_fixture, file!(), "field-arguments.graphql", "signature_help/fixtures/field-arguments.expected", input, expected).await;
+}
+
+#[tokio::test]
+async fn fragment_spread_arguments() {
+    let input = include_str!("signature_help/fixtures/fragment-spread-arguments.graphql");
+    let expected = include_str!("signature_help/fixtures/fragment-spread-arguments.expected");
+    test_fixture(transform_fixture, file!(), "fragment-spread-arguments.graphql", "signature_help/fixtures/fragment-spread-arguments.expected", input, expected).await;
+}
+
+#[tokio::test]
+async fn no_arguments() {
+    let input = include_str!("signature_help/fixtures/no-arguments.graphql");
+    let expected = include_str!("signature_help/fixtures/no-arguments.expected");
+    test_fixture(transform_fixture, file!(), "no-arguments.graphql", "signature_help/fixtures/no-arguments.expected", input, expected).await;
+}
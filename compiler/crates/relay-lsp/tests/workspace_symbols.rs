@@ -0,0 +1,45 @@
+/*
+ * Copyright (c) Meta Platforms, Inc. and affiliates.
+ *
+ * This source code is licensed under the MIT license found in the
+ * LICENSE file in the root directory of this source tree.
+ */
+
+use std::sync::Arc;
+
+use common::SourceLocationKey;
+use fixture_tests::Fixture;
+use graphql_ir::build;
+use graphql_ir::Program;
+use graphql_syntax::parse_executable;
+use graphql_test_helpers::diagnostics_to_sorted_string;
+use relay_lsp::workspace_symbols::get_workspace_symbols;
+use relay_test_schema::get_test_schema_with_extensions;
+
+// The fixture contains the query on the first line, the documents, and the
+// schema extensions after `%extensions%`.
+pub async fn transform_fixture(fixture: &Fixture<'_>) -> Result<String, String> {
+    let (query, content) = fixture.content.split_once('\n').unwrap();
+    let query = query.strip_prefix("# query:").unwrap().trim();
+    let (document, extensions) = content.split_once("%extensions%").unwrap_or((content, ""));
+
+    let schema = get_test_schema_with_extensions(extensions);
+    let source_location = SourceLocationKey::standalone(fixture.file_name);
+    let ast = parse_executable(document, source_location)
+        .map_err(|diagnostics| diagnostics_to_sorted_string(document, &diagnostics))?;
+    let ir = build(&schema, &ast.definitions)
+        .map_err(|diagnostics| diagnostics_to_sorted_string(document, &diagnostics))?;
+    let program = Program::from_definitions(Arc::clone(&schema), ir);
+
+    let mut symbols = get_workspace_symbols(&program, query)
+        .into_iter()
+        .map(|symbol| match symbol.container_name {
+            Some(container_name) => {
+                format!("{}.{} [{:?}]", container_name, symbol.name, symbol.kind)
+            }
+            None => format!("{} [{:?}]", symbol.name, symbol.kind),
+        })
+        .collect::<Vec<_>>();
+    symbols.sort();
+    Ok(symbols.join("\n"))
+}
@@ -0,0 +1,34 @@
+/*
+ * Copyright (c) Meta Platforms, Inc. and affiliates.
+ *
+ * This source code is licensed under the MIT license found in the
+ * LICENSE file in the root directory of this source tree.
+ *
+ * @generated SignedSource<<5c63f1537d407be70126f40a47697aa0>>
+ */
+
+mod workspace_symbols;
+
+use workspace_symbols::transform_fixture;
+use fixture_tests::test_fixture;
+
+#[tokio::test]
+async fn empty_query() {
+    let input = include_str!("workspace_symbols/fixtures/empty-query.graphql");
+    let expected = include_str!("workspace_symbols/fixtures/empty-query.expected");
+    test_fixture(transform_fixture, file!(), "empty-query.graphql", "workspace_symbols/fixtures/empty-query.expected", input, expected).await;
+}
+
+#[tokio::test]
+async fn fuzzy_query() {
+    let input = include_str!("workspace_symbols/fixtures/fuzzy-query.graphql");
+    let expected = include_str!("workspace_symbols/fixtures/fuzzy-query.expected");
+    test_fixture(transform_fixture, file!(), "fuzzy-query.graphql", "workspace_symbols/fixtures/fuzzy-query.expected", input, expected).await;
+}
+
+#[tokio::test]
+async fn resolver_fields() {
+    let input = include_str!("workspace_symbols/fixtures/resolver-fields.graphql");
+    let expected = include_str!("workspace_symbols/fixtures/resolver-fields.expected");
+    test_fixture(transform_fixture, file!(), "resolver-fields.graphql", "workspace_symbols/fixtures/resolver-fields.expected", input, expected).await;
+}
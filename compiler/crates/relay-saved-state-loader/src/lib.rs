@@ -6,7 +6,9 @@
  */
 
 mod config;
+mod local_saved_state_loader;
 mod saved_state;
 
 pub use config::SavedStateConfig;
+pub use local_saved_state_loader::LocalSavedStateLoader;
 pub use saved_state::SavedStateLoader;
@@ -0,0 +1,211 @@
+/*
+ * Copyright (c) Meta Platforms, Inc. and affiliates.
+ *
+ * This source code is licensed under the MIT license found in the
+ * LICENSE file in the root directory of this source tree.
+ */
+
+use std::collections::BTreeMap;
+use std::fs;
+use std::fs::File;
+use std::io;
+use std::path::Path;
+use std::path::PathBuf;
+
+use async_trait::async_trait;
+use serde_bser::value::Value;
+use sha2::Digest;
+use sha2::Sha256;
+
+use crate::config::SavedStateConfig;
+use crate::saved_state::SavedStateLoader;
+
+const OBJECTS_DIR: &str = "objects";
+const KEYS_DIR: &str = "keys";
+
+/// A saved state cache in a local directory.
+///
+/// Saved states are stored by the SHA-256 hash of their content in
+/// `<dir>/objects`. Each file in `<dir>/keys` is named after a key, a source
+/// control revision or a watchman clock, and maps saved state versions to the
+/// hash of a saved state.
+pub struct LocalSavedStateLoader {
+    dir: PathBuf,
+}
+
+impl LocalSavedStateLoader {
+    pub fn new(dir: PathBuf) -> Self {
+        Self { dir }
+    }
+
+    /// A path in the cache directory where a saved state can be written
+    /// before it is stored with `store`.
+    pub fn staging_path(&self) -> PathBuf {
+        self.dir.join(format!("staging-{}", std::process::id()))
+    }
+
+    /// Returns the path of the saved state stored for the key and version.
+    pub fn path_for_key(&self, key: &str, saved_state_version: &str) -> Option<PathBuf> {
+        let versions = self.read_key(key).ok()?;
+        let hash = versions.get(saved_state_version)?;
+        let path = self.dir.join(OBJECTS_DIR).join(hash);
+        path.exists().then_some(path)
+    }
+
+    /// Moves the saved state file into the cache, and stores it for the key
+    /// and version. Returns the new path of the saved state.
+    pub fn store(
+        &self,
+        key: &str,
+        saved_state_version: &str,
+        saved_state: &Path,
+    ) -> io::Result<PathBuf> {
+        let hash = {
+            let mut hasher = Sha256::new();
+            io::copy(&mut File::open(saved_state)?, &mut hasher)?;
+            hex::encode(hasher.finalize())
+        };
+        let objects_dir = self.dir.join(OBJECTS_DIR);
+        fs::create_dir_all(&objects_dir)?;
+        let object_path = objects_dir.join(&hash);
+        if object_path.exists() {
+            fs::remove_file(saved_state)?;
+        } else {
+            fs::rename(saved_state, &object_path)?;
+        }
+
+        let keys_dir = self.dir.join(KEYS_DIR);
+        fs::create_dir_all(&keys_dir)?;
+        let mut versions = self.read_key(key).unwrap_or_default();
+        versions.insert(saved_state_version.to_string(), hash);
+        let staging_path = self.staging_path().with_extension("key");
+        fs::write(&staging_path, serde_json::to_string_pretty(&versions)?)?;
+        fs::rename(&staging_path, self.key_path(key))?;
+
+        Ok(object_path)
+    }
+
+    fn read_key(&self, key: &str) -> io::Result<BTreeMap<String, String>> {
+        let content = fs::read_to_string(self.key_path(key))?;
+        Ok(serde_json::from_str(&content)?)
+    }
+
+    /// Keys are percent-encoded into file names, so that different keys,
+    /// like `a/b` and `a_b`, have different files, and no key names a special
+    /// file like `..`. Revisions are made of the unencoded characters, and
+    /// keep their names.
+    fn key_path(&self, key: &str) -> PathBuf {
+        let mut file_name = String::with_capacity(key.len());
+        for byte in key.bytes() {
+            match byte {
+                b'a'..=b'z' | b'A'..=b'Z' | b'0'..=b'9' | b'-' => file_name.push(byte as char),
+                _ => file_name.push_str(&format!("%{:02X}", byte)),
+            }
+        }
+        self.dir.join(KEYS_DIR).join(file_name)
+    }
+}
+
+#[async_trait]
+impl SavedStateLoader for LocalSavedStateLoader {
+    /// Loads the saved state of the source control revision in the saved
+    /// state info of watchman.
+    async fn load(&self, saved_state_info: &Value, config: &SavedStateConfig) -> Option<PathBuf> {
+        let Value::Object(saved_state_info) = saved_state_info else {
+            return None;
+        };
+        let key = ["commit-id", "mergebase"].iter().find_map(|field| {
+            match saved_state_info.get(*field) {
+                Some(Value::Utf8String(key)) => Some(key.clone()),
+                Some(Value::ByteString(key)) => Some(key.as_escaped_string()),
+                _ => None,
+            }
+        })?;
+        self.path_for_key(&key, &config.saved_state_version)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use std::collections::HashMap;
+
+    use super::*;
+
+    fn stage(loader: &LocalSavedStateLoader, content: &str) -> PathBuf {
+        let path = loader.staging_path();
+        fs::write(&path, content).unwrap();
+        path
+    }
+
+    #[test]
+    fn stores_saved_states_by_content() {
+        let dir = tempfile::tempdir().unwrap();
+        let loader = LocalSavedStateLoader::new(dir.path().to_path_buf());
+
+        let first = loader
+            .store("rev1", "v1", &stage(&loader, "state"))
+            .unwrap();
+        let second = loader
+            .store("rev2", "v1", &stage(&loader, "state"))
+            .unwrap();
+        let third = loader
+            .store("rev2", "v2", &stage(&loader, "other state"))
+            .unwrap();
+
+        assert_eq!(first, second);
+        assert_ne!(first, third);
+        assert_eq!(
+            fs::read_dir(dir.path().join(OBJECTS_DIR)).unwrap().count(),
+            2
+        );
+        assert_eq!(loader.path_for_key("rev1", "v1"), Some(first));
+        assert_eq!(loader.path_for_key("rev2", "v1"), Some(second));
+        assert_eq!(loader.path_for_key("rev2", "v2"), Some(third));
+        assert_eq!(loader.path_for_key("rev1", "v2"), None);
+        assert_eq!(loader.path_for_key("rev3", "v1"), None);
+    }
+
+    #[test]
+    fn stores_saved_states_of_similar_keys_separately() {
+        let dir = tempfile::tempdir().unwrap();
+        let loader = LocalSavedStateLoader::new(dir.path().to_path_buf());
+
+        let keys = ["a/b", "a_b", "a%2Fb", "a:b", ".", ".."];
+        let paths = keys
+            .iter()
+            .map(|key| loader.store(key, "v1", &stage(&loader, key)).unwrap())
+            .collect::<Vec<_>>();
+
+        for (key, path) in keys.iter().zip(paths) {
+            assert_eq!(loader.path_for_key(key, "v1"), Some(path));
+        }
+        assert_eq!(
+            fs::read_dir(dir.path().join(KEYS_DIR)).unwrap().count(),
+            keys.len()
+        );
+    }
+
+    #[tokio::test]
+    async fn loads_saved_states_of_watchman_revisions() {
+        let dir = tempfile::tempdir().unwrap();
+        let loader = LocalSavedStateLoader::new(dir.path().to_path_buf());
+        let path = loader
+            .store("abc123", "v1", &stage(&loader, "state"))
+            .unwrap();
+        let config = SavedStateConfig {
+            saved_state_version: "v1".to_string(),
+        };
+
+        let saved_state_info = Value::Object(HashMap::from([(
+            "commit-id".to_string(),
+            Value::Utf8String("abc123".to_string()),
+        )]));
+        assert_eq!(loader.load(&saved_state_info, &config).await, Some(path));
+
+        let saved_state_info = Value::Object(HashMap::from([(
+            "msg".to_string(),
+            Value::Utf8String("no suitable saved state found".to_string()),
+        )]));
+        assert_eq!(loader.load(&saved_state_info, &config).await, None);
+    }
+}
@@ -135,6 +135,26 @@ pub fn apply_fragment_arguments(
     }
 }
 
+/// Returns the selections of a fragment with the values of the arguments of
+/// a spread of it, or the default values of its argument definitions,
+/// substituted for its local variables. Unlike `apply_fragment_arguments`,
+/// global variables and nested fragment spreads are kept as they are, with
+/// only their arguments substituted.
+pub fn apply_fragment_spread_arguments(
+    fragment: &FragmentDefinition,
+    spread: &FragmentSpread,
+) -> Vec<Selection> {
+    let mut transform = ApplyFragmentSpreadArgumentsTransform {
+        scope: Scope::root_scope(),
+    };
+    transform
+        .scope
+        .push(spread.fragment.location, &spread.arguments, fragment);
+    transform
+        .transform_selections(&fragment.selections)
+        .replace_or_else(|| fragment.selections.clone())
+}
+
 type ProvidedVariablesMap = StringKeyIndexMap<VariableDefinition>;
 
 #[derive(Debug)]
@@ -360,23 +380,9 @@ impl Transformer for ApplyFragmentArgumentsTransform<'_, '_, '_> {
     fn transform_value(&mut self, value: &Value) -> TransformedValue<Value> {
         match value {
             Value::Variable(prev_variable) => {
-                if let Some(scope_value) = self.scope.get(prev_variable.name.item) {
-                    match scope_value {
-                        Value::Variable(replacement_variable) => {
-                            TransformedValue::Replace(Value::Variable(Variable {
-                                // Update the name/location to the applied variable name
-                                name: replacement_variable.name,
-                                // But keep the type of the previous variable, which reflects the type
-                                // expected at this location
-                                type_: prev_variable.type_.clone(),
-                            }))
-                        }
-                        _ => TransformedValue::Replace(scope_value.clone()),
-                    }
-                } else {
-                    // Assume a global variable if the variable has no local
-                    // bindings.
-                    TransformedValue::Keep
+                match get_variable_value(&self.scope, prev_variable) {
+                    Some(value) => TransformedValue::Replace(value),
+                    None => TransformedValue::Keep,
                 }
             }
             Value::Constant(_) => TransformedValue::Keep,
@@ -393,25 +399,10 @@ impl Transformer for ApplyFragmentArgumentsTransform<'_, '_, '_> {
     ) -> TransformedValue<ConditionValue> {
         match condition_value {
             ConditionValue::Variable(prev_variable) => {
-                match self.scope.get(prev_variable.name.item) {
-                    Some(Value::Variable(replacement_variable)) => {
-                        TransformedValue::Replace(ConditionValue::Variable(Variable {
-                            // Update the name/location to the applied variable name
-                            name: replacement_variable.name,
-                            // But keep the type of the previous variable, which reflects the type
-                            // expected at this location
-                            type_: prev_variable.type_.clone(),
-                        }))
-                    }
-                    Some(Value::Constant(ConstantValue::Boolean(constant_value))) => {
-                        TransformedValue::Replace(ConditionValue::Constant(*constant_value))
-                    }
-                    None => {
-                        // Assume a global variable if the variable has no local
-                        // bindings.
-                        TransformedValue::Keep
-                    }
-                    Some(other_binding) => {
+                match get_condition_variable_value(&self.scope, prev_variable) {
+                    Ok(Some(condition_value)) => TransformedValue::Replace(condition_value),
+                    Ok(None) => TransformedValue::Keep,
+                    Err(other_binding) => {
                         panic!("Invalid variable value for condition: {:?}", other_binding);
                     }
                 }
@@ -421,6 +412,91 @@ impl Transformer for ApplyFragmentArgumentsTransform<'_, '_, '_> {
     }
 }
 
+struct ApplyFragmentSpreadArgumentsTransform {
+    scope: Scope,
+}
+
+impl Transformer for ApplyFragmentSpreadArgumentsTransform {
+    const NAME: &'static str = "ApplyFragmentSpreadArgumentsTransform";
+    const VISIT_ARGUMENTS: bool = true;
+    const VISIT_DIRECTIVES: bool = true;
+
+    fn transform_value(&mut self, value: &Value) -> TransformedValue<Value> {
+        match value {
+            Value::Variable(prev_variable) => {
+                match get_variable_value(&self.scope, prev_variable) {
+                    Some(value) => TransformedValue::Replace(value),
+                    None => TransformedValue::Keep,
+                }
+            }
+            Value::Constant(_) => TransformedValue::Keep,
+            Value::List(items) => {
+                transform_list(items, |value| self.transform_value(value)).map(Value::List)
+            }
+            Value::Object(arguments) => self.transform_arguments(arguments).map(Value::Object),
+        }
+    }
+
+    fn transform_condition_value(
+        &mut self,
+        condition_value: &ConditionValue,
+    ) -> TransformedValue<ConditionValue> {
+        match condition_value {
+            ConditionValue::Variable(prev_variable) => {
+                match get_condition_variable_value(&self.scope, prev_variable) {
+                    Ok(Some(condition_value)) => TransformedValue::Replace(condition_value),
+                    // A local variable without a value or a default is null,
+                    // which is a false condition
+                    Err(Value::Constant(ConstantValue::Null())) => {
+                        TransformedValue::Replace(ConditionValue::Constant(false))
+                    }
+                    // Keep the variable of an invalid binding, which is
+                    // reported when the fragment is compiled
+                    Ok(None) | Err(_) => TransformedValue::Keep,
+                }
+            }
+            ConditionValue::Constant(_) => TransformedValue::Keep,
+        }
+    }
+}
+
+/// Returns the value bound to a variable in the scope, or `None` for a global
+/// variable, which has no local bindings.
+fn get_variable_value(scope: &Scope, prev_variable: &Variable) -> Option<Value> {
+    let scope_value = scope.get(prev_variable.name.item)?;
+    Some(match scope_value {
+        Value::Variable(replacement_variable) => Value::Variable(Variable {
+            // Update the name/location to the applied variable name
+            name: replacement_variable.name,
+            // But keep the type of the previous variable, which reflects the type
+            // expected at this location
+            type_: prev_variable.type_.clone(),
+        }),
+        _ => scope_value.clone(),
+    })
+}
+
+/// Returns the condition value bound to a variable in the scope, `None` for a
+/// global variable, or the binding if it is not a boolean or a variable.
+fn get_condition_variable_value<'scope>(
+    scope: &'scope Scope,
+    prev_variable: &Variable,
+) -> Result<Option<ConditionValue>, &'scope Value> {
+    match scope.get(prev_variable.name.item) {
+        Some(Value::Variable(replacement_variable)) => {
+            Ok(Some(ConditionValue::Variable(Variable {
+                name: replacement_variable.name,
+                type_: prev_variable.type_.clone(),
+            })))
+        }
+        Some(Value::Constant(ConstantValue::Boolean(constant_value))) => {
+            Ok(Some(ConditionValue::Constant(*constant_value)))
+        }
+        None => Ok(None),
+        Some(other_binding) => Err(other_binding),
+    }
+}
+
 impl ApplyFragmentArgumentsTransform<'_, '_, '_> {
     fn transform_no_inline_fragment(
         &mut self,
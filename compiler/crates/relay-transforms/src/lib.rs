@@ -90,6 +90,7 @@ pub use apply_custom_transforms::CustomTransform;
 pub use apply_custom_transforms::CustomTransforms;
 pub use apply_custom_transforms::CustomTransformsConfig;
 pub use apply_fragment_arguments::apply_fragment_arguments;
+pub use apply_fragment_arguments::apply_fragment_spread_arguments;
 pub use apply_fragment_arguments::NoInlineFragmentSpreadMetadata;
 pub use apply_transforms::apply_transforms;
 pub use apply_transforms::Programs;
@@ -177,6 +178,7 @@ pub use relay_actor_change::RELAY_ACTOR_CHANGE_DIRECTIVE_FOR_CODEGEN;
 pub use relay_directive::RelayDirective;
 pub use relay_node_identifier::RelayLocationAgnosticBehavior;
 pub use relay_resolvers::get_resolver_fragment_dependency_name;
+pub use relay_resolvers::get_resolver_output_type_info;
 pub use relay_resolvers::relay_resolvers;
 pub use relay_resolvers::resolver_type_import_alias;
 pub use relay_resolvers::FragmentDataInjectionMode;
@@ -416,7 +416,6 @@ impl<'program> RelayResolverFieldTransform<'program> {
                     }
 
                     let parent_type = schema_field.parent_type.unwrap();
-                    let inner_type = schema_field.type_.inner();
 
                     if let Some(fragment_name) = fragment_name {
                         match self.program.fragment(fragment_name) {
@@ -445,47 +444,12 @@ impl<'program> RelayResolverFieldTransform<'program> {
                         }
                     }
 
-                    let output_type_info = if has_output_type {
-                        if inner_type.is_composite_type() {
-                            let normalization_operation = generate_name_for_nested_object_operation(
-                                self.project_name,
-                                &self.program.schema,
-                                self.program.schema.field(field.definition().item),
-                            );
-
-                            let weak_object_instance_field =
-                                inner_type.get_object_id().and_then(|id| {
-                                    let object = self.program.schema.object(id);
-                                    if object
-                                        .directives
-                                        .named(*RELAY_RESOLVER_WEAK_OBJECT_DIRECTIVE)
-                                        .is_some()
-                                    {
-                                        // This is expect to be `__relay_model_instance`
-                                        // TODO: Add validation/panic to assert that weak object has only
-                                        // one field here, and it's a magic relay instance field.
-                                        Some(*object.fields.first().unwrap())
-                                    } else {
-                                        None
-                                    }
-                                });
-
-                            ResolverOutputTypeInfo::Composite(
-                                ResolverNormalizationInfo {
-                                    inner_type,
-                                    plural: schema_field.type_.is_list(),
-                                    normalization_operation,
-                                    weak_object_instance_field,
-                                },
-                            )
-                        } else {
-                            ResolverOutputTypeInfo::ScalarField
-                        }
-                    } else if inner_type.is_composite_type() {
-                        ResolverOutputTypeInfo::EdgeTo
-                    } else {
-                        ResolverOutputTypeInfo::Legacy
-                    };
+                    let output_type_info = get_resolver_output_type_info(
+                        self.project_name,
+                        &self.program.schema,
+                        schema_field,
+                        has_output_type,
+                    );
 
                     let resolver_field_metadata = RelayResolverFieldMetadata {
                         import_path,
@@ -606,12 +570,57 @@ impl Transformer for RelayResolverFieldTransform<'_> {
 
 #[derive(Debug)]
 pub struct ResolverInfo {
-    fragment_name: Option<FragmentDefinitionName>,
-    fragment_data_injection_mode: Option<FragmentDataInjectionMode>,
+    pub fragment_name: Option<FragmentDefinitionName>,
+    pub fragment_data_injection_mode: Option<FragmentDataInjectionMode>,
     pub import_path: StringKey,
     pub import_name: Option<StringKey>,
-    live: bool,
+    pub live: bool,
+    pub has_output_type: bool,
+}
+
+/// Describes what the resolver function backing `resolver_field` returns.
+pub fn get_resolver_output_type_info(
+    project_name: ProjectName,
+    schema: &SDLSchema,
+    resolver_field: &Field,
     has_output_type: bool,
+) -> ResolverOutputTypeInfo {
+    let inner_type = resolver_field.type_.inner();
+    if has_output_type {
+        if inner_type.is_composite_type() {
+            let normalization_operation =
+                generate_name_for_nested_object_operation(project_name, schema, resolver_field);
+
+            let weak_object_instance_field = inner_type.get_object_id().and_then(|id| {
+                let object = schema.object(id);
+                if object
+                    .directives
+                    .named(*RELAY_RESOLVER_WEAK_OBJECT_DIRECTIVE)
+                    .is_some()
+                {
+                    // This is expect to be `__relay_model_instance`
+                    // TODO: Add validation/panic to assert that weak object has only
+                    // one field here, and it's a magic relay instance field.
+                    Some(*object.fields.first().unwrap())
+                } else {
+                    None
+                }
+            });
+
+            ResolverOutputTypeInfo::Composite(ResolverNormalizationInfo {
+                inner_type,
+                plural: resolver_field.type_.is_list(),
+                normalization_operation,
+                weak_object_instance_field,
+            })
+        } else {
+            ResolverOutputTypeInfo::ScalarField
+        }
+    } else if inner_type.is_composite_type() {
+        ResolverOutputTypeInfo::EdgeTo
+    } else {
+        ResolverOutputTypeInfo::Legacy
+    }
 }
 
 pub fn get_resolver_info(
@@ -16,6 +16,7 @@ use graphql_ir::FragmentDefinitionNameSet;
 use graphql_ir::FragmentSpread;
 use graphql_ir::OperationDefinition;
 use graphql_ir::Program;
+use graphql_ir::ValidationFix;
 use graphql_ir::Validator;
 use graphql_ir::Variable;
 use schema::SDLSchema;
@@ -28,14 +29,16 @@ pub fn validate_resolver_fragments(program: &Program) -> DiagnosticsResult<()> {
     ValidateResolverFragments::new(&program.schema).validate_program(program)
 }
 
-struct ValidateResolverFragments {
+struct ValidateResolverFragments<'schema> {
+    schema: &'schema SDLSchema,
     resolver_fragments: FragmentDefinitionNameSet,
     current_fragment: Option<FragmentDefinition>,
 }
 
-impl ValidateResolverFragments {
-    fn new(schema: &SDLSchema) -> Self {
+impl<'schema> ValidateResolverFragments<'schema> {
+    fn new(schema: &'schema SDLSchema) -> Self {
         let validator = Self {
+            schema,
             current_fragment: None,
             resolver_fragments: schema
                 .fields()
@@ -65,7 +68,7 @@ impl ValidateResolverFragments {
     }
 }
 
-impl Validator for ValidateResolverFragments {
+impl Validator for ValidateResolverFragments<'_> {
     const NAME: &'static str = "ValidateResolverFragments";
     const VALIDATE_ARGUMENTS: bool = true;
     const VALIDATE_DIRECTIVES: bool = true;
@@ -103,13 +106,24 @@ impl Validator for ValidateResolverFragments {
             .iter()
             .any(|var| var.name.item == variable.name.item)
         {
-            return Err(vec![Diagnostic::error(
+            let diagnostic = Diagnostic::error(
                 ValidationMessage::UnsupportedGlobalVariablesInResolverFragment {
                     variable_name: variable.name.item,
                     fragment_name: current_fragment.name.item,
                 },
                 variable.name.location,
-            )]);
+            );
+            return Err(vec![match current_fragment
+                .used_global_variables
+                .named(variable.name.item)
+            {
+                Some(global_variable) => ValidationFix::DeclareFragmentArgument {
+                    variable_name: variable.name.item,
+                    type_: self.schema.get_type_string(&global_variable.type_),
+                }
+                .attach_to(diagnostic),
+                None => diagnostic,
+            }]);
         }
 
         Ok(())
@@ -13,6 +13,7 @@ use common::NamedItem;
 use graphql_ir::FragmentDefinition;
 use graphql_ir::OperationDefinition;
 use graphql_ir::Program;
+use graphql_ir::ValidationFix;
 use graphql_ir::ValidationMessage;
 use graphql_ir::Validator;
 use graphql_ir::Variable;
@@ -65,13 +66,18 @@ impl Validator for ValidateUnusedFragmentVariables {
                         expect_unused_directive.name.location,
                     ))
                 } else {
-                    Some(Diagnostic::error(
-                        ValidationMessage::UnusedFragmentVariable {
-                            fragment_name: fragment.name.item,
+                    Some(
+                        ValidationFix::RemoveVariableDefinition {
                             variable_name: variable_definition.name.item,
-                        },
-                        variable_definition.name.location,
-                    ))
+                        }
+                        .attach_to(Diagnostic::error(
+                            ValidationMessage::UnusedFragmentVariable {
+                                fragment_name: fragment.name.item,
+                                variable_name: variable_definition.name.item,
+                            },
+                            variable_definition.name.location,
+                        )),
+                    )
                 }
             })
             .collect();
@@ -12,6 +12,7 @@ use common::NamedItem;
 use graphql_ir::FragmentDefinition;
 use graphql_ir::OperationDefinition;
 use graphql_ir::Program;
+use graphql_ir::ValidationFix;
 use graphql_ir::ValidationMessage;
 use graphql_ir::Validator;
 use intern::string_key::Intern;
@@ -58,13 +59,16 @@ impl Validator for ValidateUnusedVariables<'_> {
             return Err(unused_variables
                 .into_iter()
                 .map(|unused_variable| {
-                    Diagnostic::error(
+                    ValidationFix::RemoveVariableDefinition {
+                        variable_name: unused_variable.name.item,
+                    }
+                    .attach_to(Diagnostic::error(
                         ValidationMessage::UnusedVariable {
                             operation_name: operation.name.item.0,
                             variable_name: unused_variable.name.item,
                         },
                         unused_variable.name.location,
-                    )
+                    ))
                 })
                 .collect());
         }
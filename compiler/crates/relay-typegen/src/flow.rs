@@ -70,6 +70,10 @@ impl Writer for FlowPrinter {
                 arguments,
                 return_type,
             }) => self.write_assert_function_type(*function_name, arguments, return_type),
+            AST::FunctionType {
+                arguments,
+                return_type,
+            } => self.write_function_type(arguments, return_type),
             AST::GenericType { outer, inner } => self.write_generic_type(*outer, inner),
             AST::PropertyType {
                 type_,
@@ -352,10 +356,22 @@ impl FlowPrinter {
             &mut self.result,
             "// A type error here indicates that the type signature of the resolver module is incorrect."
         )?;
+        write!(&mut self.result, "({}: ", function_name)?;
+        self.write_function_type(arguments, return_type)?;
+        writeln!(&mut self.result, ");")?;
+
+        Ok(())
+    }
+
+    fn write_function_type(
+        &mut self,
+        arguments: &[KeyValuePairProp],
+        return_type: &AST,
+    ) -> FmtResult {
         if arguments.is_empty() {
-            write!(&mut self.result, "({}: (", function_name)?;
+            write!(&mut self.result, "(")?;
         } else {
-            writeln!(&mut self.result, "({}: (", function_name)?;
+            writeln!(&mut self.result, "(")?;
             self.indentation += 1;
             for argument in arguments.iter() {
                 self.write_indentation()?;
@@ -364,12 +380,10 @@ impl FlowPrinter {
                 writeln!(&mut self.result, ",")?;
             }
             self.indentation -= 1;
+            self.write_indentation()?;
         }
         write!(&mut self.result, ") => ")?;
-        self.write(return_type)?;
-        writeln!(&mut self.result, ");")?;
-
-        Ok(())
+        self.write(return_type)
     }
 
     fn write_generic_type(&mut self, outer: StringKey, inner: &[AST]) -> FmtResult {
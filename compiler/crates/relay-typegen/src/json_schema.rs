@@ -0,0 +1,292 @@
+/*
+ * Copyright (c) Meta Platforms, Inc. and affiliates.
+ *
+ * This source code is licensed under the MIT license found in the
+ * LICENSE file in the root directory of this source tree.
+ */
+
+use std::fmt::Result as FmtResult;
+use std::fmt::Write;
+
+use ::intern::Lookup;
+use serde_json::json;
+use serde_json::Map;
+use serde_json::Value;
+
+use crate::writer::Prop;
+use crate::writer::Writer;
+use crate::writer::AST;
+
+static JSON_SCHEMA_DIALECT: &str = "https://json-schema.org/draft/2020-12/schema";
+
+/// Collects the exported types of an artifact into the `$defs` of a JSON
+/// Schema document, including the enums with their values from the schema.
+/// Imports, type assertions and raw text are dropped, and identifiers that
+/// are not exported by the same artifact (e.g. custom scalars) are printed
+/// with `print_opaque_type`.
+#[derive(Default)]
+pub struct JsonSchemaPrinter {
+    definitions: Vec<(String, AST)>,
+}
+
+impl Write for JsonSchemaPrinter {
+    fn write_str(&mut self, _s: &str) -> FmtResult {
+        Ok(())
+    }
+}
+
+impl Writer for JsonSchemaPrinter {
+    fn into_string(self: Box<Self>) -> String {
+        let definitions = self
+            .definitions
+            .iter()
+            .map(|(name, ast)| (name.clone(), self.print(ast)))
+            .collect::<Map<_, _>>();
+        let mut result = serde_json::to_string_pretty(&json!({
+            "$schema": JSON_SCHEMA_DIALECT,
+            "$defs": definitions,
+        }))
+        .unwrap();
+        result.push('\n');
+        result
+    }
+
+    fn get_runtime_fragment_import(&self) -> &'static str {
+        ""
+    }
+
+    fn write(&mut self, _ast: &AST) -> FmtResult {
+        Ok(())
+    }
+
+    fn write_type_assertion(&mut self, _name: &str, _value: &AST) -> FmtResult {
+        Ok(())
+    }
+
+    fn write_export_type(&mut self, name: &str, value: &AST) -> FmtResult {
+        self.definitions.push((name.to_string(), value.clone()));
+        Ok(())
+    }
+
+    fn write_import_module_default(&mut self, _name: &str, _from: &str) -> FmtResult {
+        Ok(())
+    }
+
+    fn write_import_module_named(
+        &mut self,
+        _name: &str,
+        _alias: Option<&str>,
+        _from: &str,
+    ) -> FmtResult {
+        Ok(())
+    }
+
+    fn write_import_type(&mut self, _types: &[&str], _from: &str) -> FmtResult {
+        Ok(())
+    }
+
+    fn write_import_fragment_type(&mut self, _types: &[&str], _from: &str) -> FmtResult {
+        Ok(())
+    }
+
+    fn write_export_fragment_type(&mut self, _name: &str) -> FmtResult {
+        Ok(())
+    }
+
+    fn write_export_fragment_types(
+        &mut self,
+        _fragment_type_name_1: &str,
+        _fragment_type_name_2: &str,
+    ) -> FmtResult {
+        Ok(())
+    }
+
+    fn write_any_type_definition(&mut self, name: &str) -> FmtResult {
+        self.definitions.push((name.to_string(), AST::Any));
+        Ok(())
+    }
+}
+
+impl JsonSchemaPrinter {
+    fn print(&self, ast: &AST) -> Value {
+        match ast {
+            AST::String | AST::OtherTypename => json!({ "type": "string" }),
+            AST::Number => json!({ "type": "number" }),
+            AST::Boolean => json!({ "type": "boolean" }),
+            AST::StringLiteral(literal) => json!({ "const": literal.lookup() }),
+            AST::Union(members) => {
+                let literals = members
+                    .iter()
+                    .map(|member| match member {
+                        AST::StringLiteral(literal) => Some(literal.lookup()),
+                        _ => None,
+                    })
+                    .collect::<Option<Vec<_>>>();
+                match literals {
+                    Some(literals) => json!({ "enum": literals }),
+                    None => json!({
+                        "anyOf": members.iter().map(|member| self.print(member)).collect::<Vec<_>>()
+                    }),
+                }
+            }
+            AST::ReadOnlyArray(of_type) => json!({
+                "type": "array",
+                "items": self.print(of_type),
+            }),
+            AST::Nullable(of_type) => json!({
+                "anyOf": [self.print(of_type), { "type": "null" }],
+            }),
+            AST::NonNullable(of_type)
+            | AST::Local3DPayload(_, of_type)
+            | AST::ActorChangePoint(of_type) => self.print(of_type),
+            AST::ExactObject(props) => self.print_object(props, true),
+            AST::InexactObject(props) => self.print_object(props, false),
+            AST::Identifier(identifier) => {
+                if self
+                    .definitions
+                    .iter()
+                    .any(|(name, _)| name == identifier.lookup())
+                {
+                    json!({ "$ref": format!("#/$defs/{}", identifier) })
+                } else {
+                    print_opaque_type(identifier.lookup())
+                }
+            }
+            AST::RawType(raw) => print_opaque_type(raw.lookup()),
+            AST::Any
+            | AST::Mixed
+            | AST::Callable(_)
+            | AST::FragmentReference(_)
+            | AST::FragmentReferenceType(_)
+            | AST::ReturnTypeOfFunctionWithName(_)
+            | AST::ReturnTypeOfMethodCall(_, _)
+            | AST::AssertFunctionType(_)
+            | AST::FunctionType { .. }
+            | AST::GenericType { .. }
+            | AST::PropertyType { .. } => json!({}),
+        }
+    }
+
+    fn print_object(&self, props: &[Prop], exact: bool) -> Value {
+        let mut properties = Map::new();
+        let mut required = vec![];
+        let mut has_fragment_references = false;
+        for prop in props {
+            match prop {
+                Prop::KeyValuePair(key_value_pair) => {
+                    // Keys like `$fragmentSpreads` or `$data` only exist in the static types
+                    if key_value_pair.key.lookup().starts_with('$') {
+                        has_fragment_references = true;
+                        continue;
+                    }
+                    properties.insert(
+                        key_value_pair.key.to_string(),
+                        self.print(&key_value_pair.value),
+                    );
+                    if !key_value_pair.optional {
+                        required.push(key_value_pair.key.to_string());
+                    }
+                }
+                Prop::GetterSetterPair(getter_setter_pair) => {
+                    properties.insert(
+                        getter_setter_pair.key.to_string(),
+                        self.print(&getter_setter_pair.getter_return_value),
+                    );
+                    required.push(getter_setter_pair.key.to_string());
+                }
+                Prop::Spread(_) => {}
+            }
+        }
+        let mut object = json!({
+            "type": "object",
+            "properties": properties,
+            "required": required,
+        });
+        // The fields of spread fragments are also present in the data, they
+        // are only hidden from the static types
+        if exact && !has_fragment_references {
+            object["additionalProperties"] = Value::Bool(false);
+        }
+        object
+    }
+}
+
+/// The schema of a custom scalar, or another type only known to JavaScript:
+/// the JSON representation of its values is not described by the GraphQL
+/// schema, so any value is accepted. The title and description tell it apart
+/// from the `{}` of types that are intentionally untyped.
+fn print_opaque_type(name: &str) -> Value {
+    json!({
+        "title": name,
+        "description": "Opaque type, defined outside of the GraphQL schema: any value is accepted.",
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use ::intern::string_key::Intern;
+
+    use super::*;
+    use crate::writer::ExactObject;
+    use crate::writer::KeyValuePairProp;
+    use crate::writer::SortedASTList;
+    use crate::writer::StringLiteral;
+
+    fn print_type(ast: &AST) -> String {
+        let printer = JsonSchemaPrinter::default();
+        printer.print(ast).to_string()
+    }
+
+    #[test]
+    fn scalar_types() {
+        assert_eq!(print_type(&AST::Any), r#"{}"#);
+        assert_eq!(print_type(&AST::String), r#"{"type":"string"}"#);
+        assert_eq!(print_type(&AST::Number), r#"{"type":"number"}"#);
+        assert_eq!(
+            print_type(&AST::RawType("CustomBoolean".intern())),
+            r#"{"description":"Opaque type, defined outside of the GraphQL schema: any value is accepted.","title":"CustomBoolean"}"#
+        );
+    }
+
+    #[test]
+    fn union_type() {
+        assert_eq!(
+            print_type(&AST::Union(SortedASTList::new(vec![
+                AST::StringLiteral(StringLiteral("A".intern())),
+                AST::StringLiteral(StringLiteral("B".intern())),
+            ]))),
+            r#"{"enum":["A","B"]}"#
+        );
+        assert_eq!(
+            print_type(&AST::Nullable(Box::new(AST::String))),
+            r#"{"anyOf":[{"type":"string"},{"type":"null"}]}"#
+        );
+    }
+
+    #[test]
+    fn object_type() {
+        assert_eq!(
+            print_type(&AST::ExactObject(ExactObject::new(vec![
+                Prop::KeyValuePair(KeyValuePairProp {
+                    key: "name".intern(),
+                    value: AST::Nullable(Box::new(AST::String)),
+                    read_only: true,
+                    optional: true,
+                }),
+                Prop::KeyValuePair(KeyValuePairProp {
+                    key: "id".intern(),
+                    value: AST::String,
+                    read_only: true,
+                    optional: false,
+                }),
+                Prop::KeyValuePair(KeyValuePairProp {
+                    key: "$fragmentType".intern(),
+                    value: AST::FragmentReferenceType("Foo".intern()),
+                    read_only: true,
+                    optional: false,
+                }),
+            ]))),
+            r#"{"properties":{"id":{"type":"string"},"name":{"anyOf":[{"type":"string"},{"type":"null"}]}},"required":["id"],"type":"object"}"#
+        );
+    }
+}
@@ -11,6 +11,8 @@
 
 mod flow;
 mod javascript;
+mod json_schema;
+mod response_validator;
 mod type_selection;
 mod typegen_state;
 mod typescript;
@@ -18,6 +20,8 @@ mod visit;
 mod write;
 mod writer;
 
+use std::path::PathBuf;
+
 use ::intern::string_key::Intern;
 use ::intern::string_key::StringKey;
 use common::DirectiveName;
@@ -26,6 +30,7 @@ use common::ScalarName;
 use common::WithLocation;
 use graphql_ir::FragmentDefinition;
 use graphql_ir::OperationDefinition;
+use json_schema::JsonSchemaPrinter;
 use lazy_static::lazy_static;
 use relay_config::ProjectConfig;
 pub use relay_config::TypegenConfig;
@@ -36,16 +41,21 @@ pub use typegen_state::FragmentLocations;
 pub use write::has_raw_response_type_directive;
 use write::write_fragment_type_exports_section;
 use write::write_operation_type_exports_section;
+use write::write_resolver_function_types_section;
+use write::write_response_validator_function;
 use write::write_split_operation_type_exports_section;
 use write::write_validator_function;
 use writer::new_writer_from_config;
+use writer::Writer;
 
 static REACT_RELAY_MULTI_ACTOR: &str = "react-relay/multi-actor";
 static RELAY_RUNTIME: &str = "relay-runtime";
 static LOCAL_3D_PAYLOAD: &str = "Local3DPayload";
 static ACTOR_CHANGE_POINT: &str = "ActorChangePoint";
 static VALIDATOR_EXPORT_NAME: &str = "validate";
+static RESPONSE_VALIDATOR_EXPORT_NAME: &str = "validateResponse";
 static LIVE_RESOLVERS_LIVE_STATE: &str = "LiveState";
+static FRAGMENT_TYPE_BRAND: &str = "FragmentType";
 
 lazy_static! {
     static ref KEY_CLIENTID: StringKey = "__id".intern();
@@ -158,6 +168,7 @@ fn generate_fragment_type_exports_section_impl(
         TypegenOptions {
             no_optional_fields_in_raw_response_type: false,
             is_extra_artifact_branch_module,
+            export_enum_values: false,
         },
     );
     let mut writer = new_writer_from_config(&project_config.typegen_config);
@@ -184,6 +195,7 @@ pub fn generate_named_validator_export(
         TypegenOptions {
             no_optional_fields_in_raw_response_type: false,
             is_extra_artifact_branch_module: false,
+            export_enum_values: false,
         },
     );
     let mut writer = new_writer_from_config(&project_config.typegen_config);
@@ -223,6 +235,7 @@ pub fn generate_operation_type_exports_section(
         TypegenOptions {
             no_optional_fields_in_raw_response_type: false,
             is_extra_artifact_branch_module: false,
+            export_enum_values: false,
         },
     );
     let mut writer = new_writer_from_config(&project_config.typegen_config);
@@ -237,6 +250,116 @@ pub fn generate_operation_type_exports_section(
     writer.into_string()
 }
 
+/// Generates a JSON Schema document with the variables and response types of
+/// an operation in its `$defs`.
+pub fn generate_operation_json_schema(
+    typegen_operation: &OperationDefinition,
+    normalization_operation: &OperationDefinition,
+    schema: &SDLSchema,
+    project_config: &ProjectConfig,
+    fragment_locations: &FragmentLocations,
+    maybe_provided_variables: Option<String>,
+) -> String {
+    let typegen_context = TypegenContext::new(
+        schema,
+        project_config,
+        typegen_operation
+            .directives
+            .named(*UPDATABLE_DIRECTIVE)
+            .is_some(),
+        WithLocation::new(
+            typegen_operation.name.location,
+            typegen_operation.name.item.0,
+        ),
+        fragment_locations,
+        TypegenOptions {
+            no_optional_fields_in_raw_response_type: false,
+            is_extra_artifact_branch_module: false,
+            export_enum_values: true,
+        },
+    );
+    let mut writer: Box<dyn Writer> = Box::<JsonSchemaPrinter>::default();
+    write_operation_type_exports_section(
+        &typegen_context,
+        typegen_operation,
+        normalization_operation,
+        &mut writer,
+        maybe_provided_variables,
+    )
+    .unwrap();
+    writer.into_string()
+}
+
+/// Generates a JSON Schema document with the data type of a fragment in its
+/// `$defs`.
+pub fn generate_fragment_json_schema(
+    fragment_definition: &FragmentDefinition,
+    schema: &SDLSchema,
+    project_config: &ProjectConfig,
+    fragment_locations: &FragmentLocations,
+) -> String {
+    let typegen_context = TypegenContext::new(
+        schema,
+        project_config,
+        fragment_definition
+            .directives
+            .named(*UPDATABLE_DIRECTIVE)
+            .is_some(),
+        fragment_definition.name.map(|x| x.0),
+        fragment_locations,
+        TypegenOptions {
+            no_optional_fields_in_raw_response_type: false,
+            is_extra_artifact_branch_module: false,
+            export_enum_values: true,
+        },
+    );
+    let mut writer: Box<dyn Writer> = Box::<JsonSchemaPrinter>::default();
+    write_fragment_type_exports_section(&typegen_context, fragment_definition, &mut writer)
+        .unwrap();
+    writer.into_string()
+}
+
+/// Generates the `validateResponse` export of an operation, which checks the
+/// data of a response against the generated `$data` type at runtime.
+pub fn generate_response_validator_export(
+    typegen_operation: &OperationDefinition,
+    schema: &SDLSchema,
+    project_config: &ProjectConfig,
+    fragment_locations: &FragmentLocations,
+) -> String {
+    let typegen_context = TypegenContext::new(
+        schema,
+        project_config,
+        typegen_operation
+            .directives
+            .named(*UPDATABLE_DIRECTIVE)
+            .is_some(),
+        WithLocation::new(
+            typegen_operation.name.location,
+            typegen_operation.name.item.0,
+        ),
+        fragment_locations,
+        TypegenOptions {
+            no_optional_fields_in_raw_response_type: false,
+            is_extra_artifact_branch_module: false,
+            export_enum_values: false,
+        },
+    );
+    let mut writer = new_writer_from_config(&project_config.typegen_config);
+    write_response_validator_function(&typegen_context, typegen_operation, &mut writer).unwrap();
+    let validator_function_body = writer.into_string();
+
+    let typegen_config = &project_config.typegen_config;
+    if typegen_config.eager_es_modules || typegen_config.language == TypegenLanguage::TypeScript {
+        format!("export {}", validator_function_body)
+    } else {
+        format!(
+            "module.exports.{} = {};",
+            RESPONSE_VALIDATOR_EXPORT_NAME, validator_function_body
+        )
+    }
+}
+
 pub fn generate_split_operation_type_exports_section(
     typegen_operation: &OperationDefinition,
     normalization_operation: &OperationDefinition,
@@ -260,6 +383,7 @@ pub fn generate_split_operation_type_exports_section(
         TypegenOptions {
             no_optional_fields_in_raw_response_type,
             is_extra_artifact_branch_module: false,
+            export_enum_values: false,
         },
     );
     let mut writer = new_writer_from_config(&project_config.typegen_config);
@@ -274,6 +398,25 @@ pub fn generate_split_operation_type_exports_section(
     writer.into_string()
 }
 
+/// Generates a module exporting the type of the function implementing each
+/// Relay Resolver field of the schema, to be written at `artifact_path`.
+pub fn generate_resolver_function_types_module(
+    schema: &SDLSchema,
+    project_config: &ProjectConfig,
+    fragment_locations: &FragmentLocations,
+    artifact_path: PathBuf,
+) -> String {
+    let typegen_context = TypegenContext::new_for_schema_module(
+        schema,
+        project_config,
+        artifact_path,
+        fragment_locations,
+    );
+    let mut writer = new_writer_from_config(&project_config.typegen_config);
+    write_resolver_function_types_section(&typegen_context, &mut writer).unwrap();
+    writer.into_string()
+}
+
 /// An immutable grab bag of configuration, etc. for type generation.
 /// A new `TypegenContext` is created for each operation, fragment, and so on.
 struct TypegenContext<'a> {
@@ -282,7 +425,8 @@ struct TypegenContext<'a> {
     fragment_locations: &'a FragmentLocations,
     has_unified_output: bool,
     generating_updatable_types: bool,
-    definition_source_location: WithLocation<StringKey>,
+    /// The path of the generated module, which imports are relative to.
+    artifact_path: PathBuf,
     typegen_options: TypegenOptions,
 }
 
@@ -301,10 +445,49 @@ impl<'a> TypegenContext<'a> {
             fragment_locations,
             has_unified_output: project_config.output.is_some(),
             generating_updatable_types,
-            definition_source_location,
+            artifact_path: project_config.artifact_path_for_definition(definition_source_location),
             typegen_options,
         }
     }
+
+    /// Creates the context for a module generated from the schema alone,
+    /// rather than from an operation or fragment.
+    fn new_for_schema_module(
+        schema: &'a SDLSchema,
+        project_config: &'a ProjectConfig,
+        artifact_path: PathBuf,
+        fragment_locations: &'a FragmentLocations,
+    ) -> Self {
+        Self {
+            schema,
+            project_config,
+            fragment_locations,
+            has_unified_output: project_config.output.is_some(),
+            generating_updatable_types: false,
+            artifact_path,
+            typegen_options: TypegenOptions {
+                no_optional_fields_in_raw_response_type: false,
+                is_extra_artifact_branch_module: false,
+                export_enum_values: false,
+            },
+        }
+    }
+
+    /// The import path of the module declaring the fragment reference brands,
+    /// if they are enabled for this TypeScript project.
+    fn fragment_ref_brands_module(&self) -> Option<StringKey> {
+        let typegen_config = &self.project_config.typegen_config;
+        if typegen_config.language != TypegenLanguage::TypeScript {
+            return None;
+        }
+        typegen_config
+            .typescript_fragment_ref_brands_module
+            .as_ref()
+            .map(|path| {
+                self.project_config
+                    .js_module_import_identifier(&self.artifact_path, path)
+            })
+    }
 }
 
 struct TypegenOptions {
@@ -312,4 +495,7 @@ struct TypegenOptions {
     no_optional_fields_in_raw_response_type: bool,
     // Some extra artifacts require special type generation
     is_extra_artifact_branch_module: bool,
+    // JSON Schemas can't import the enum modules, so the values of all enums
+    // are exported
+    export_enum_values: bool,
 }
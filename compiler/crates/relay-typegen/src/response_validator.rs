@@ -0,0 +1,534 @@
+/*
+ * Copyright (c) Meta Platforms, Inc. and affiliates.
+ *
+ * This source code is licensed under the MIT license found in the
+ * LICENSE file in the root directory of this source tree.
+ */
+
+use std::borrow::Cow;
+use std::fmt::Result as FmtResult;
+
+use ::intern::intern;
+use ::intern::string_key::StringKey;
+use ::intern::Lookup;
+use graphql_ir::Directive;
+use graphql_ir::LinkedField;
+use graphql_ir::OperationDefinition;
+use graphql_ir::ScalarField;
+use graphql_ir::Selection;
+use graphql_ir::Transformed;
+use graphql_ir::Transformer;
+use relay_config::TypegenLanguage;
+use relay_transforms::RequiredMetadataDirective;
+use relay_transforms::CHILDREN_CAN_BUBBLE_METADATA_KEY;
+use schema::SDLSchema;
+use schema::Schema;
+use schema::Type;
+
+use crate::writer::Prop;
+use crate::writer::Writer;
+use crate::writer::AST;
+use crate::RESPONSE_VALIDATOR_EXPORT_NAME;
+
+/// Write a function that checks at runtime that a value matches the `$data`
+/// type of an operation, and returns the list of mismatches. Flow example:
+/// function validateResponse(value/*: mixed*/)/*: $ReadOnlyArray<string>*/ {
+///   const errors/*: Array<string>*/ = [];
+///   const v0/*: any*/ = value;
+///   if (v0 == null) {
+///     errors.push("data: expected a non-null value");
+///   } else if (typeof v0 !== "object") {
+///     errors.push("data: expected an object");
+///   } else {
+///     const v1 = v0.me;
+///     ...
+///   }
+///   return errors;
+/// }
+pub(crate) fn write_response_validator(
+    schema: &SDLSchema,
+    language: TypegenLanguage,
+    data_type: &AST,
+    writer: &mut Box<dyn Writer>,
+) -> FmtResult {
+    write!(writer, "function {}(value", RESPONSE_VALIDATOR_EXPORT_NAME)?;
+    write_annotation(language, &AST::Mixed, writer)?;
+    write!(writer, ")")?;
+    write_annotation(language, &AST::ReadOnlyArray(Box::new(AST::String)), writer)?;
+    write!(writer, " {{\n  const errors")?;
+    write_annotation(
+        language,
+        &AST::GenericType {
+            outer: intern!("Array"),
+            inner: vec![AST::String],
+        },
+        writer,
+    )?;
+    write!(writer, " = [];\n  const v0")?;
+    write_annotation(language, &AST::Any, writer)?;
+    writeln!(writer, " = value;")?;
+
+    let mut printer = ResponseValidatorPrinter {
+        schema,
+        variable_count: 1,
+    };
+    for line in printer.check_value(data_type, "v0", "\"data\"") {
+        writeln!(writer, "  {}", line)?;
+    }
+
+    write!(writer, "  return errors;\n}}")
+}
+
+/// Returns the operation with only the selections sent by the server: the
+/// fields of client schema extensions are removed, and the `@required` fields
+/// get back the nullability of the schema, as the server can return null for
+/// them.
+pub(crate) fn get_server_operation<'a>(
+    schema: &SDLSchema,
+    operation: &'a OperationDefinition,
+) -> Cow<'a, OperationDefinition> {
+    match (ServerSelectionsTransform { schema }).transform_operation(operation) {
+        Transformed::Keep => Cow::Borrowed(operation),
+        Transformed::Replace(operation) => Cow::Owned(operation),
+        Transformed::Delete => Cow::Owned(OperationDefinition {
+            selections: vec![],
+            ..operation.clone()
+        }),
+    }
+}
+
+struct ServerSelectionsTransform<'a> {
+    schema: &'a SDLSchema,
+}
+
+impl Transformer for ServerSelectionsTransform<'_> {
+    const NAME: &'static str = "ServerSelectionsTransform";
+    const VISIT_ARGUMENTS: bool = false;
+    const VISIT_DIRECTIVES: bool = true;
+
+    fn transform_scalar_field(&mut self, field: &ScalarField) -> Transformed<Selection> {
+        if self.schema.field(field.definition.item).is_extension {
+            Transformed::Delete
+        } else {
+            self.default_transform_scalar_field(field)
+        }
+    }
+
+    fn transform_linked_field(&mut self, field: &LinkedField) -> Transformed<Selection> {
+        if self.schema.field(field.definition.item).is_extension {
+            Transformed::Delete
+        } else {
+            self.default_transform_linked_field(field)
+        }
+    }
+
+    fn transform_directive(&mut self, directive: &Directive) -> Transformed<Directive> {
+        if directive.name.item == RequiredMetadataDirective::directive_name()
+            || directive.name.item == *CHILDREN_CAN_BUBBLE_METADATA_KEY
+        {
+            Transformed::Delete
+        } else {
+            Transformed::Keep
+        }
+    }
+}
+
+fn write_annotation(
+    language: TypegenLanguage,
+    ast: &AST,
+    writer: &mut Box<dyn Writer>,
+) -> FmtResult {
+    match language {
+        TypegenLanguage::Flow => {
+            write!(writer, "/*: ")?;
+            writer.write(ast)?;
+            write!(writer, "*/")
+        }
+        TypegenLanguage::TypeScript => {
+            write!(writer, ": ")?;
+            writer.write(ast)
+        }
+        TypegenLanguage::JavaScript => Ok(()),
+    }
+}
+
+/// Generates the statements checking a value, one line per statement with
+/// nested blocks already indented. Values are stored in `vN` variables and
+/// paths are JavaScript string expressions, so that the path of an array item
+/// can include its index.
+struct ResponseValidatorPrinter<'a> {
+    schema: &'a SDLSchema,
+    variable_count: usize,
+}
+
+impl ResponseValidatorPrinter<'_> {
+    /// Checks a value that is neither optional nor known to be present.
+    fn check_value(&mut self, ast: &AST, variable: &str, path: &str) -> Vec<String> {
+        match ast {
+            AST::Nullable(of_type) => {
+                let checks = self.check_non_null_value(of_type, variable, path);
+                if checks.is_empty() {
+                    vec![]
+                } else {
+                    if_block(&format!("{} != null", variable), checks)
+                }
+            }
+            _ if is_checked_type(ast) => {
+                let checks = self.check_non_null_value(ast, variable, path);
+                if_else_block(
+                    &format!("{} == null", variable),
+                    vec![push_error(path, "expected a non-null value")],
+                    checks,
+                )
+            }
+            _ => vec![],
+        }
+    }
+
+    /// Checks the value of a property, which can also be missing from its object.
+    fn check_prop_value(
+        &mut self,
+        ast: &AST,
+        optional: bool,
+        variable: &str,
+        path: &str,
+    ) -> Vec<String> {
+        if optional {
+            let checks = self.check_value(ast, variable, path);
+            if checks.is_empty() {
+                vec![]
+            } else {
+                if_block(&format!("{} !== undefined", variable), checks)
+            }
+        } else if let AST::Nullable(of_type) = ast {
+            let checks = self.check_non_null_value(of_type, variable, path);
+            let mut lines = if_block(
+                &format!("{} === undefined", variable),
+                vec![push_error(path, "missing value")],
+            );
+            if !checks.is_empty() {
+                lines.pop();
+                lines.push(format!("}} else if ({} !== null) {{", variable));
+                lines.extend(indent(checks));
+                lines.push("}".to_string());
+            }
+            lines
+        } else {
+            self.check_value(ast, variable, path)
+        }
+    }
+
+    /// Checks a value that is known to be neither null nor undefined.
+    fn check_non_null_value(&mut self, ast: &AST, variable: &str, path: &str) -> Vec<String> {
+        match ast {
+            AST::Nullable(of_type) | AST::NonNullable(of_type) => {
+                self.check_non_null_value(of_type, variable, path)
+            }
+            AST::String | AST::OtherTypename => check_typeof(variable, path, "string"),
+            AST::Number => check_typeof(variable, path, "number"),
+            AST::Boolean => check_typeof(variable, path, "boolean"),
+            AST::StringLiteral(literal) => if_block(
+                &format!("{} !== \"{}\"", variable, literal.lookup()),
+                vec![push_error(
+                    path,
+                    &format!("expected \"{}\"", literal.lookup()),
+                )],
+            ),
+            AST::Identifier(name) => self.check_enum_value(*name, variable, path),
+            AST::ReadOnlyArray(of_type) => {
+                let item_variable = self.new_variable("v");
+                let index_variable = self.new_variable("i");
+                let item_path =
+                    format!("{} + {} + \"]\"", append_to_path(path, "["), index_variable);
+                let checks = self.check_value(of_type, &item_variable, &item_path);
+                let mut else_lines = vec![];
+                if !checks.is_empty() {
+                    else_lines.push(format!(
+                        "{}.forEach(({}, {}) => {{",
+                        variable, item_variable, index_variable
+                    ));
+                    else_lines.extend(indent(checks));
+                    else_lines.push("});".to_string());
+                }
+                if_else_block(
+                    &format!("!Array.isArray({})", variable),
+                    vec![push_error(path, "expected an array")],
+                    else_lines,
+                )
+            }
+            AST::ExactObject(props) => self.check_object(props, variable, path),
+            AST::InexactObject(props) => self.check_object(props, variable, path),
+            AST::Union(members) => self.check_union(members, variable, path),
+            _ => vec![],
+        }
+    }
+
+    /// Values of enum types must be one of the values in the schema, even if
+    /// the generated type accepts future added values.
+    fn check_enum_value(&mut self, name: StringKey, variable: &str, path: &str) -> Vec<String> {
+        let enum_values = match self.schema.get_type(name) {
+            Some(Type::Enum(enum_id)) => self
+                .schema
+                .enum_(enum_id)
+                .values
+                .iter()
+                .map(|value| format!("\"{}\"", value.value))
+                .collect::<Vec<_>>(),
+            _ => return vec![],
+        };
+        if_block(
+            &format!("![{}].includes({})", enum_values.join(", "), variable),
+            vec![push_error(
+                path,
+                &format!("expected a value of enum {}", name.lookup()),
+            )],
+        )
+    }
+
+    fn check_object(&mut self, props: &[Prop], variable: &str, path: &str) -> Vec<String> {
+        let checks = self.check_props(props, None, variable, path);
+        if_else_block(
+            &format!("typeof {} !== \"object\"", variable),
+            vec![push_error(path, "expected an object")],
+            checks,
+        )
+    }
+
+    fn check_props(
+        &mut self,
+        props: &[Prop],
+        skipped_key: Option<StringKey>,
+        variable: &str,
+        path: &str,
+    ) -> Vec<String> {
+        let mut lines = vec![];
+        for prop in props {
+            let key_value_pair = match prop {
+                Prop::KeyValuePair(key_value_pair) => key_value_pair,
+                Prop::Spread(_) | Prop::GetterSetterPair(_) => continue,
+            };
+            // Fragment references are only a type-level marker.
+            if Some(key_value_pair.key) == skipped_key
+                || key_value_pair.key.lookup().starts_with('$')
+                || !is_checked_type(&key_value_pair.value)
+            {
+                continue;
+            }
+            let prop_variable = self.new_variable("v");
+            let prop_path = append_to_path(path, &format!(".{}", key_value_pair.key));
+            let checks = self.check_prop_value(
+                &key_value_pair.value,
+                key_value_pair.optional,
+                &prop_variable,
+                &prop_path,
+            );
+            if !checks.is_empty() {
+                lines.push(format!(
+                    "const {} = {}.{};",
+                    prop_variable, variable, key_value_pair.key
+                ));
+                lines.extend(checks);
+            }
+        }
+        lines
+    }
+
+    /// Discriminated unions are checked by switching over their typename, the
+    /// `%other` branch accepting any other string. Members of other unions are
+    /// not checked.
+    fn check_union(&mut self, members: &[AST], variable: &str, path: &str) -> Vec<String> {
+        let Some(discriminator) = get_union_discriminator(members) else {
+            return vec![];
+        };
+        let discriminator_variable = self.new_variable("v");
+        let discriminator_path = append_to_path(path, &format!(".{}", discriminator));
+        let mut switch_lines = vec![
+            format!(
+                "const {} = {}.{};",
+                discriminator_variable, variable, discriminator
+            ),
+            format!("switch ({}) {{", discriminator_variable),
+        ];
+        let mut has_other_member = false;
+        let mut typenames = vec![];
+        for member in members {
+            let props = get_object_props(member).unwrap();
+            match get_prop_value(props, discriminator).unwrap() {
+                AST::StringLiteral(literal) => {
+                    let mut case_lines =
+                        self.check_props(props, Some(discriminator), variable, path);
+                    case_lines.push("break;".to_string());
+                    switch_lines.push(format!("  case \"{}\": {{", literal.lookup()));
+                    switch_lines.extend(indent(indent(case_lines)));
+                    switch_lines.push("  }".to_string());
+                    typenames.push(format!("\"{}\"", literal.lookup()));
+                }
+                _ => has_other_member = true,
+            }
+        }
+        switch_lines.push("  default: {".to_string());
+        let default_lines = if has_other_member {
+            check_typeof(&discriminator_variable, &discriminator_path, "string")
+        } else {
+            vec![push_error(
+                &discriminator_path,
+                &format!(
+                    "expected one of {}",
+                    typenames.join(", ").replace('"', "\\\"")
+                ),
+            )]
+        };
+        switch_lines.extend(indent(indent(default_lines)));
+        switch_lines.push("  }".to_string());
+        switch_lines.push("}".to_string());
+
+        if_else_block(
+            &format!("typeof {} !== \"object\"", variable),
+            vec![push_error(path, "expected an object")],
+            switch_lines,
+        )
+    }
+
+    fn new_variable(&mut self, prefix: &str) -> String {
+        let variable = format!("{}{}", prefix, self.variable_count);
+        self.variable_count += 1;
+        variable
+    }
+}
+
+/// Only types derived from the schema are checked. Types returned by Relay
+/// Resolvers, `@catch` results and other client-side values are skipped.
+fn is_checked_type(ast: &AST) -> bool {
+    match ast {
+        AST::Nullable(of_type) | AST::NonNullable(of_type) => is_checked_type(of_type),
+        AST::Union(_)
+        | AST::ReadOnlyArray(_)
+        | AST::Identifier(_)
+        | AST::RawType(_)
+        | AST::String
+        | AST::StringLiteral(_)
+        | AST::OtherTypename
+        | AST::ExactObject(_)
+        | AST::InexactObject(_)
+        | AST::Number
+        | AST::Boolean
+        | AST::Any => true,
+        AST::Local3DPayload(_, _)
+        | AST::Callable(_)
+        | AST::Mixed
+        | AST::FragmentReference(_)
+        | AST::FragmentReferenceType(_)
+        | AST::ReturnTypeOfFunctionWithName(_)
+        | AST::ReturnTypeOfMethodCall(_, _)
+        | AST::ActorChangePoint(_)
+        | AST::AssertFunctionType(_)
+        | AST::FunctionType { .. }
+        | AST::GenericType { .. }
+        | AST::PropertyType { .. } => false,
+    }
+}
+
+/// Returns the key whose value is a distinct typename literal in each member
+/// of the union, if there is one.
+fn get_union_discriminator(members: &[AST]) -> Option<StringKey> {
+    let first_props = get_object_props(members.first()?)?;
+    first_props.iter().find_map(|prop| {
+        let key = match prop {
+            Prop::KeyValuePair(key_value_pair) => key_value_pair.key,
+            _ => return None,
+        };
+        members
+            .iter()
+            .all(|member| {
+                get_object_props(member)
+                    .and_then(|props| get_prop_value(props, key))
+                    .is_some_and(|value| {
+                        matches!(value, AST::StringLiteral(_) | AST::OtherTypename)
+                    })
+            })
+            .then_some(key)
+    })
+}
+
+fn get_object_props(ast: &AST) -> Option<&[Prop]> {
+    match ast {
+        AST::ExactObject(props) => Some(props),
+        AST::InexactObject(props) => Some(props),
+        _ => None,
+    }
+}
+
+fn get_prop_value(props: &[Prop], key: StringKey) -> Option<&AST> {
+    props.iter().find_map(|prop| match prop {
+        Prop::KeyValuePair(key_value_pair) if key_value_pair.key == key => {
+            Some(&key_value_pair.value)
+        }
+        _ => None,
+    })
+}
+
+fn check_typeof(variable: &str, path: &str, type_: &str) -> Vec<String> {
+    if_block(
+        &format!("typeof {} !== \"{}\"", variable, type_),
+        vec![push_error(path, &format!("expected a {}", type_))],
+    )
+}
+
+fn push_error(path: &str, message: &str) -> String {
+    format!(
+        "errors.push({});",
+        append_to_path(path, &format!(": {}", message))
+    )
+}
+
+/// Appends text to a path expression, merging it into the trailing string
+/// literal of the expression.
+fn append_to_path(path: &str, text: &str) -> String {
+    match path.strip_suffix('"') {
+        Some(prefix) => format!("{}{}\"", prefix, text),
+        None => format!("{} + \"{}\"", path, text),
+    }
+}
+
+fn if_block(condition: &str, body: Vec<String>) -> Vec<String> {
+    let mut lines = vec![format!("if ({}) {{", condition)];
+    lines.extend(indent(body));
+    lines.push("}".to_string());
+    lines
+}
+
+fn if_else_block(condition: &str, body: Vec<String>, else_body: Vec<String>) -> Vec<String> {
+    let mut lines = if_block(condition, body);
+    if !else_body.is_empty() {
+        lines.pop();
+        match else_body.as_slice() {
+            // Chain a single nested `if` without an `else` branch
+            [first, .., last]
+                if first.starts_with("if (") && last == "}" && is_single_block(&else_body) =>
+            {
+                lines.push(format!("}} else {}", first));
+                lines.extend(else_body.into_iter().skip(1));
+            }
+            _ => {
+                lines.push("} else {".to_string());
+                lines.extend(indent(else_body));
+                lines.push("}".to_string());
+            }
+        }
+    }
+    lines
+}
+
+/// Whether the lines form one `if`/`else if`/`else` statement.
+fn is_single_block(lines: &[String]) -> bool {
+    lines[1..lines.len() - 1]
+        .iter()
+        .all(|line| line.starts_with("  ") || line.starts_with("} else"))
+}
+
+fn indent(lines: Vec<String>) -> Vec<String> {
+    lines
+        .into_iter()
+        .map(|line| format!("  {}", line))
+        .collect()
+}
@@ -24,6 +24,8 @@ use schema::Schema;
 use crate::writer::ExactObject;
 use crate::writer::Writer;
 use crate::writer::AST;
+use crate::TypegenContext;
+use crate::FRAGMENT_TYPE_BRAND;
 use crate::KEY_DATA_ID;
 use crate::LIVE_RESOLVERS_LIVE_STATE;
 use crate::LOCAL_3D_PAYLOAD;
@@ -39,15 +41,21 @@ pub(crate) struct RuntimeImports {
     pub(crate) resolver_live_state_type: bool,
     pub(crate) data_id_type: bool,
     pub(crate) result_type: bool,
+    pub(crate) fragment_type: bool,
 }
 
 impl RuntimeImports {
-    pub(crate) fn write_runtime_imports(&self, writer: &mut Box<dyn Writer>) -> FmtResult {
+    pub(crate) fn write_runtime_imports(
+        &self,
+        typegen_context: &'_ TypegenContext<'_>,
+        writer: &mut Box<dyn Writer>,
+    ) -> FmtResult {
+        let fragment_ref_brands_module = typegen_context.fragment_ref_brands_module();
         let mut runtime_import_types = vec![];
         if self.resolver_live_state_type {
             runtime_import_types.push(LIVE_RESOLVERS_LIVE_STATE);
         }
-        if self.generic_fragment_type {
+        if self.generic_fragment_type && fragment_ref_brands_module.is_none() {
             runtime_import_types.push(writer.get_runtime_fragment_import())
         }
         if self.local_3d_payload_type {
@@ -60,10 +68,23 @@ impl RuntimeImports {
             runtime_import_types.push(RESULT_TYPE_NAME.lookup());
         }
         if !runtime_import_types.is_empty() {
-            writer.write_import_type(&runtime_import_types, RELAY_RUNTIME)
-        } else {
-            Ok(())
+            writer.write_import_type(&runtime_import_types, RELAY_RUNTIME)?;
         }
+
+        if let Some(fragment_ref_brands_module) = fragment_ref_brands_module {
+            let mut brand_import_types = vec![];
+            if self.generic_fragment_type {
+                brand_import_types.push(writer.get_runtime_fragment_import());
+            }
+            if self.fragment_type {
+                brand_import_types.push(FRAGMENT_TYPE_BRAND);
+            }
+            if !brand_import_types.is_empty() {
+                writer
+                    .write_import_type(&brand_import_types, fragment_ref_brands_module.lookup())?;
+            }
+        }
+        Ok(())
     }
 }
 
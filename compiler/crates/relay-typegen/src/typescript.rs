@@ -13,12 +13,14 @@ use intern::intern;
 use itertools::Itertools;
 use relay_config::TypegenConfig;
 
+use crate::writer::KeyValuePairProp;
 use crate::writer::Prop;
 use crate::writer::SortedASTList;
 use crate::writer::SortedStringKeyList;
 use crate::writer::StringLiteral;
 use crate::writer::Writer;
 use crate::writer::AST;
+use crate::FRAGMENT_TYPE_BRAND;
 use crate::KEY_DATA;
 use crate::KEY_FRAGMENT_SPREADS;
 use crate::KEY_FRAGMENT_TYPE;
@@ -27,6 +29,7 @@ pub struct TypeScriptPrinter {
     result: String,
     use_import_type_syntax: bool,
     include_undefined_in_nullable_union: bool,
+    brand_fragment_types: bool,
     indentation: usize,
 }
 
@@ -82,6 +85,10 @@ impl Writer for TypeScriptPrinter {
                 // typescript: https://github.com/facebook/relay/issues/4772
                 Ok(())
             }
+            AST::FunctionType {
+                arguments,
+                return_type,
+            } => self.write_function_type(arguments, return_type),
             AST::GenericType { outer, inner } => self.write_generic_type(*outer, inner),
             AST::PropertyType {
                 type_,
@@ -174,6 +181,7 @@ impl TypeScriptPrinter {
             use_import_type_syntax: config.use_import_type_syntax,
             include_undefined_in_nullable_union: !config
                 .typescript_exclude_undefined_from_nullable_union,
+            brand_fragment_types: config.typescript_fragment_ref_brands_module.is_some(),
         }
     }
 
@@ -332,7 +340,13 @@ impl TypeScriptPrinter {
     }
 
     fn write_fragment_references_type(&mut self, fragment: StringKey) -> FmtResult {
-        self.write(&AST::StringLiteral(StringLiteral(fragment)))
+        if self.brand_fragment_types {
+            write!(&mut self.result, "{}<", FRAGMENT_TYPE_BRAND)?;
+            self.write_string_literal(fragment)?;
+            write!(&mut self.result, ">")
+        } else {
+            self.write(&AST::StringLiteral(StringLiteral(fragment)))
+        }
     }
 
     fn write_return_type_of_function_with_name(&mut self, function_name: StringKey) -> FmtResult {
@@ -354,6 +368,29 @@ impl TypeScriptPrinter {
         self.write(return_type)
     }
 
+    fn write_function_type(
+        &mut self,
+        arguments: &[KeyValuePairProp],
+        return_type: &AST,
+    ) -> FmtResult {
+        if arguments.is_empty() {
+            write!(&mut self.result, "(")?;
+        } else {
+            writeln!(&mut self.result, "(")?;
+            self.indentation += 1;
+            for argument in arguments.iter() {
+                self.write_indentation()?;
+                write!(&mut self.result, "{}: ", argument.key)?;
+                self.write(&argument.value)?;
+                writeln!(&mut self.result, ",")?;
+            }
+            self.indentation -= 1;
+            self.write_indentation()?;
+        }
+        write!(&mut self.result, ") => ")?;
+        self.write(return_type)
+    }
+
     fn write_generic_type(&mut self, outer: StringKey, inner: &[AST]) -> FmtResult {
         write!(&mut self.result, "{}<", outer)?;
         for (i, inner_type) in inner.iter().enumerate() {
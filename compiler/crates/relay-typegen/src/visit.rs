@@ -64,6 +64,8 @@ use relay_transforms::RELAY_ACTOR_CHANGE_DIRECTIVE_FOR_CODEGEN;
 use relay_transforms::UPDATABLE_DIRECTIVE_FOR_TYPEGEN;
 use schema::EnumID;
 use schema::Field;
+use schema::FieldID;
+use schema::InterfaceID;
 use schema::ObjectID;
 use schema::SDLSchema;
 use schema::ScalarID;
@@ -352,13 +354,13 @@ fn generate_resolver_type(
     resolver_metadata: &RelayResolverMetadata,
     context_import: Option<ResolverContextType>,
 ) -> AST {
-    // For the purposes of function type assertion, we always use the semantic type.
     let schema_field = resolver_metadata.field(typegen_context.schema);
-    let schema_field_type = schema_field.semantic_type();
 
     let resolver_arguments = get_resolver_arguments(
         fragment_name,
-        resolver_metadata,
+        resolver_metadata
+            .fragment_data_injection_mode
+            .map(|(fragment_name, injection_mode)| (fragment_name.item, injection_mode)),
         encountered_fragments,
         typegen_context,
         input_object_types,
@@ -368,7 +370,50 @@ fn generate_resolver_type(
         context_import,
     );
 
-    let inner_ast = match &resolver_metadata.output_type_info {
+    let ast = get_resolver_return_type(
+        typegen_context,
+        schema_field,
+        &resolver_metadata.output_type_info,
+        encountered_enums,
+        custom_scalars,
+        imported_raw_response_types,
+        runtime_imports,
+    );
+
+    let return_type = if matches!(
+        typegen_context.project_config.typegen_config.language,
+        TypegenLanguage::TypeScript
+    ) {
+        // TODO: Add proper support for Resolver type generation in typescript: https://github.com/facebook/relay/issues/4772
+        AST::Any
+    } else if resolver_metadata.live {
+        get_live_state_type(ast, runtime_imports)
+    } else {
+        ast
+    };
+
+    AST::AssertFunctionType(FunctionTypeAssertion {
+        function_name: resolver_function_name,
+        arguments: resolver_arguments,
+        return_type: Box::new(return_type),
+    })
+}
+
+/// The type of the value returned by the resolver function backing
+/// `schema_field`, or `mixed` if it can only be inferred from the function.
+pub(crate) fn get_resolver_return_type(
+    typegen_context: &'_ TypegenContext<'_>,
+    schema_field: &Field,
+    output_type_info: &ResolverOutputTypeInfo,
+    encountered_enums: &mut EncounteredEnums,
+    custom_scalars: &mut CustomScalarsImports,
+    imported_raw_response_types: &mut ImportedRawResponseTypes,
+    runtime_imports: &mut RuntimeImports,
+) -> AST {
+    // For the purposes of function type assertion, we always use the semantic type.
+    let schema_field_type = schema_field.semantic_type();
+
+    let inner_ast = match output_type_info {
         ResolverOutputTypeInfo::ScalarField => {
             if is_relay_resolver_type(typegen_context, schema_field) {
                 match schema_field_type.is_non_null() {
@@ -400,29 +445,15 @@ fn generate_resolver_type(
         ResolverOutputTypeInfo::Legacy => AST::Mixed,
     };
 
-    let ast = transform_type_reference_into_ast(&schema_field_type, |_| inner_ast);
-
-    let return_type = if matches!(
-        typegen_context.project_config.typegen_config.language,
-        TypegenLanguage::TypeScript
-    ) {
-        // TODO: Add proper support for Resolver type generation in typescript: https://github.com/facebook/relay/issues/4772
-        AST::Any
-    } else if resolver_metadata.live {
-        runtime_imports.resolver_live_state_type = true;
-        AST::GenericType {
-            outer: *LIVE_STATE_TYPE,
-            inner: vec![ast],
-        }
-    } else {
-        ast
-    };
+    transform_type_reference_into_ast(&schema_field_type, |_| inner_ast)
+}
 
-    AST::AssertFunctionType(FunctionTypeAssertion {
-        function_name: resolver_function_name,
-        arguments: resolver_arguments,
-        return_type: Box::new(return_type),
-    })
+pub(crate) fn get_live_state_type(ast: AST, runtime_imports: &mut RuntimeImports) -> AST {
+    runtime_imports.resolver_live_state_type = true;
+    AST::GenericType {
+        outer: *LIVE_STATE_TYPE,
+        inner: vec![ast],
+    }
 }
 
 fn add_fragment_name_to_encountered_fragments(
@@ -476,9 +507,9 @@ fn add_model_argument_for_interface_resolver(
 }
 
 #[allow(clippy::too_many_arguments)]
-fn get_resolver_arguments(
+pub(crate) fn get_resolver_arguments(
     fragment_name: Option<FragmentDefinitionName>,
-    resolver_metadata: &RelayResolverMetadata,
+    fragment_data_injection_mode: Option<(FragmentDefinitionName, FragmentDataInjectionMode)>,
     encountered_fragments: &mut EncounteredFragments,
     typegen_context: &TypegenContext<'_>,
     input_object_types: &mut IndexMap<common::InputObjectName, GeneratedInputObject>,
@@ -516,19 +547,17 @@ fn get_resolver_arguments(
         }
     }
     if let Some(fragment_name) = fragment_name {
-        if let Some((fragment_name, injection_mode)) =
-            resolver_metadata.fragment_data_injection_mode
-        {
+        if let Some((fragment_name, injection_mode)) = fragment_data_injection_mode {
             match injection_mode {
                 FragmentDataInjectionMode::Field { name, .. } => {
                     add_fragment_name_to_encountered_fragments(
-                        fragment_name.item,
+                        fragment_name,
                         encountered_fragments,
                     );
                     resolver_arguments.push(KeyValuePairProp {
                         key: name,
                         value: AST::PropertyType {
-                            type_: get_fragment_data_type(fragment_name.item.0),
+                            type_: get_fragment_data_type(fragment_name.0),
                             property_name: name,
                         },
                         read_only: false,
@@ -618,32 +647,11 @@ fn import_relay_resolver_function_type(
     };
 
     let import_path = typegen_context.project_config.js_module_import_identifier(
-        &typegen_context
-            .project_config
-            .artifact_path_for_definition(typegen_context.definition_source_location),
+        &typegen_context.artifact_path,
         &PathBuf::from(resolver_metadata.import_path.lookup()),
     );
 
-    let context_import = match &typegen_context
-        .project_config
-        .typegen_config
-        .resolver_context_type
-    {
-        Some(ResolverContextTypeInput::Path(context_import)) => Some(ResolverContextType {
-            name: context_import.name,
-            import_path: typegen_context.project_config.js_module_import_identifier(
-                &typegen_context
-                    .project_config
-                    .artifact_path_for_definition(typegen_context.definition_source_location),
-                &PathBuf::from(&context_import.path),
-            ),
-        }),
-        Some(ResolverContextTypeInput::Package(context_import)) => Some(ResolverContextType {
-            name: context_import.name,
-            import_path: context_import.package,
-        }),
-        None => None,
-    };
+    let context_import = get_resolver_context_type(typegen_context);
 
     let imported_resolver = ImportedResolver {
         resolver_name,
@@ -670,6 +678,29 @@ fn import_relay_resolver_function_type(
         .or_insert(imported_resolver);
 }
 
+pub(crate) fn get_resolver_context_type(
+    typegen_context: &'_ TypegenContext<'_>,
+) -> Option<ResolverContextType> {
+    match &typegen_context
+        .project_config
+        .typegen_config
+        .resolver_context_type
+    {
+        Some(ResolverContextTypeInput::Path(context_import)) => Some(ResolverContextType {
+            name: context_import.name,
+            import_path: typegen_context.project_config.js_module_import_identifier(
+                &typegen_context.artifact_path,
+                &PathBuf::from(&context_import.path),
+            ),
+        }),
+        Some(ResolverContextTypeInput::Package(context_import)) => Some(ResolverContextType {
+            name: context_import.name,
+            import_path: context_import.package,
+        }),
+        None => None,
+    }
+}
+
 /// Check if the scalar field has the special type `RelayResolverValue`. This is a type that
 /// indicates that the return type is an opaque scalar, whose type is determined by the return
 /// type of the resolver function.
@@ -1264,8 +1295,10 @@ fn visit_scalar_field(
         }
     }
 
-    let ast = transform_type_reference_into_ast(&field_type, |type_| {
-        expect_scalar_type(typegen_context, encountered_enums, custom_scalars, type_)
+    let custom_field_type = get_custom_field_type(typegen_context, scalar_field.definition.item);
+    let ast = transform_type_reference_into_ast(&field_type, |type_| match custom_field_type {
+        Some(custom_type) => transform_custom_type(custom_type, custom_scalars),
+        None => expect_scalar_type(typegen_context, encountered_enums, custom_scalars, type_),
     });
 
     type_selections.push(TypeSelection::ScalarField(TypeSelectionScalarField {
@@ -1330,6 +1363,9 @@ pub(crate) fn get_data_type(
     runtime_imports: &mut RuntimeImports,
     custom_error_import: &mut Option<CustomTypeImport>,
 ) -> AST {
+    if fragment_type_name.is_some() {
+        runtime_imports.fragment_type = true;
+    }
     let mut data_type = selections_to_babel(
         typegen_context,
         selections,
@@ -2133,6 +2169,7 @@ fn transform_non_nullable_type_reference_into_ast(
 fn transform_graphql_scalar_type(
     typegen_context: &'_ TypegenContext<'_>,
     scalar: ScalarID,
+    is_input_type: bool,
     custom_scalars: &mut CustomScalarsImports,
 ) -> AST {
     let scalar_definition = typegen_context.schema.scalar(scalar);
@@ -2153,9 +2190,7 @@ fn transform_graphql_scalar_type(
             .expect_string_literal();
 
         let import_path = typegen_context.project_config.js_module_import_identifier(
-            &typegen_context
-                .project_config
-                .artifact_path_for_definition(typegen_context.definition_source_location),
+            &typegen_context.artifact_path,
             &PathBuf::from(path.lookup()),
         );
 
@@ -2175,20 +2210,17 @@ fn transform_graphql_scalar_type(
     // TODO: We could implement custom variables that are provided via the
     // config by inserting them into the schema with directives, thus avoiding
     // having two different ways to express typed custom scalars internally.
-    if let Some(custom_scalar) = typegen_context
-        .project_config
-        .typegen_config
-        .custom_scalar_types
-        .get(&scalar_name.item)
-    {
-        match custom_scalar {
-            CustomType::Name(custom_scalar) => AST::RawType(*custom_scalar),
-            CustomType::Path(CustomTypeImport { name, path }) => {
-                custom_scalars.insert((*name, path.clone()));
-
-                AST::RawType(*name)
-            }
-        }
+    let typegen_config = &typegen_context.project_config.typegen_config;
+    let custom_scalar = if is_input_type {
+        typegen_config
+            .custom_scalar_input_types
+            .get(&scalar_name.item)
+            .or_else(|| typegen_config.custom_scalar_types.get(&scalar_name.item))
+    } else {
+        typegen_config.custom_scalar_types.get(&scalar_name.item)
+    };
+    if let Some(custom_scalar) = custom_scalar {
+        transform_custom_type(custom_scalar, custom_scalars)
     } else if scalar_name.item == *TYPE_ID || scalar_name.item == *TYPE_STRING {
         AST::String
     } else if scalar_name.item == *TYPE_FLOAT || scalar_name.item == *TYPE_INT {
@@ -2210,6 +2242,73 @@ fn transform_graphql_scalar_type(
     }
 }
 
+fn transform_custom_type(
+    custom_type: &CustomType,
+    custom_scalars: &mut CustomScalarsImports,
+) -> AST {
+    match custom_type {
+        CustomType::Name(name) => AST::RawType(*name),
+        CustomType::Path(CustomTypeImport { name, path }) => {
+            custom_scalars.insert((*name, path.clone()));
+
+            AST::RawType(*name)
+        }
+    }
+}
+
+/// Returns the custom JS type configured in `customFieldTypes` for a field
+/// definition, if any. The type configured for the field of an interface
+/// also applies to the field of the types that implement it. A field selected
+/// on an interface only has the type configured for the interface, as the
+/// concrete type is not known.
+fn get_custom_field_type<'a>(
+    typegen_context: &'a TypegenContext<'_>,
+    field_id: FieldID,
+) -> Option<&'a CustomType> {
+    if typegen_context
+        .project_config
+        .typegen_config
+        .custom_field_types
+        .is_empty()
+    {
+        return None;
+    }
+    let schema = typegen_context.schema;
+    let field = schema.field(field_id);
+    let parent_type = field.parent_type?;
+    let interfaces: &[InterfaceID] = match parent_type {
+        Type::Object(object_id) => &schema.object(object_id).interfaces,
+        Type::Interface(interface_id) => &schema.interface(interface_id).interfaces,
+        _ => &[],
+    };
+    std::iter::once(parent_type)
+        .chain(interfaces.iter().copied().map(Type::Interface))
+        .find_map(|type_| {
+            get_configured_field_type(
+                typegen_context,
+                schema.get_type_name(type_),
+                field.name.item,
+            )
+        })
+}
+
+/// Returns the custom JS type configured in `customFieldTypes` for the field
+/// `field_name` of the type `type_name`, if any.
+fn get_configured_field_type<'a>(
+    typegen_context: &'a TypegenContext<'_>,
+    type_name: StringKey,
+    field_name: StringKey,
+) -> Option<&'a CustomType> {
+    let custom_field_types = &typegen_context
+        .project_config
+        .typegen_config
+        .custom_field_types;
+    if custom_field_types.is_empty() {
+        return None;
+    }
+    custom_field_types.get(&format!("{}.{}", type_name, field_name).intern())
+}
+
 fn transform_graphql_enum_type(
     schema: &SDLSchema,
     enum_id: EnumID,
@@ -2357,7 +2456,7 @@ fn transform_non_nullable_input_type(
         ))),
         TypeReference::Named(named_type) => match named_type {
             Type::Scalar(scalar) => {
-                transform_graphql_scalar_type(typegen_context, *scalar, custom_scalars)
+                transform_graphql_scalar_type(typegen_context, *scalar, true, custom_scalars)
             }
             Type::Enum(enum_id) => {
                 transform_graphql_enum_type(typegen_context.schema, *enum_id, encountered_enums)
@@ -2383,13 +2482,24 @@ fn transform_non_nullable_input_type(
                                             .optional_input_fields
                                             .contains(&field.name.item.0)
                                         || field.default_value.is_some(),
-                                    value: transform_input_type(
+                                    value: match get_configured_field_type(
                                         typegen_context,
-                                        &field.type_,
-                                        input_object_types,
-                                        encountered_enums,
-                                        custom_scalars,
-                                    ),
+                                        input_object.name.item.0,
+                                        field.name.item.0,
+                                    ) {
+                                        Some(custom_type) => {
+                                            transform_type_reference_into_ast(&field.type_, |_| {
+                                                transform_custom_type(custom_type, custom_scalars)
+                                            })
+                                        }
+                                        None => transform_input_type(
+                                            typegen_context,
+                                            &field.type_,
+                                            input_object_types,
+                                            encountered_enums,
+                                            custom_scalars,
+                                        ),
+                                    },
                                 })
                             })
                             .collect(),
@@ -2765,7 +2875,7 @@ fn expect_scalar_type(
             transform_graphql_enum_type(typegen_context.schema, *enum_id, encountered_enums)
         }
         Type::Scalar(scalar_id) => {
-            transform_graphql_scalar_type(typegen_context, *scalar_id, custom_scalars)
+            transform_graphql_scalar_type(typegen_context, *scalar_id, false, custom_scalars)
         }
         Type::InputObject(_) => panic!("Unexpected input type"),
         Type::Interface(_) | Type::Object(_) | Type::Union(_) => {
@@ -2789,7 +2899,7 @@ fn return_ast_in_object_case(
             transform_graphql_enum_type(typegen_context.schema, *enum_id, encountered_enums)
         }
         Type::Scalar(scalar_id) => {
-            transform_graphql_scalar_type(typegen_context, *scalar_id, custom_scalars)
+            transform_graphql_scalar_type(typegen_context, *scalar_id, false, custom_scalars)
         }
         Type::InputObject(_) => panic!("Unexpected input type"),
         Type::Interface(_) | Type::Object(_) | Type::Union(_) => ast_in_object_case,
@@ -15,6 +15,7 @@ use ::intern::string_key::StringKey;
 use ::intern::Lookup;
 use common::DirectiveName;
 use common::InputObjectName;
+use common::Location;
 use common::NamedItem;
 use graphql_ir::FragmentDefinition;
 use graphql_ir::FragmentDefinitionName;
@@ -27,13 +28,18 @@ use lazy_static::lazy_static;
 use relay_config::CustomTypeImport;
 use relay_config::JsModuleFormat;
 use relay_config::TypegenLanguage;
+use relay_transforms::get_resolver_output_type_info;
+use relay_transforms::relay_resolvers::get_resolver_info;
 use relay_transforms::RefetchableDerivedFromMetadata;
 use relay_transforms::RefetchableMetadata;
 use relay_transforms::RelayDirective;
 use relay_transforms::ASSIGNABLE_DIRECTIVE;
 use relay_transforms::CHILDREN_CAN_BUBBLE_METADATA_KEY;
+use relay_transforms::RESOLVER_BELONGS_TO_BASE_SCHEMA_DIRECTIVE;
 use schema::Schema;
 
+use crate::response_validator::get_server_operation;
+use crate::response_validator::write_response_validator;
 use crate::typegen_state::ActorChangeStatus;
 use crate::typegen_state::EncounteredEnums;
 use crate::typegen_state::EncounteredFragment;
@@ -46,7 +52,11 @@ use crate::typegen_state::MatchFields;
 use crate::typegen_state::RuntimeImports;
 use crate::visit::get_data_type;
 use crate::visit::get_input_variables_type;
+use crate::visit::get_live_state_type;
 use crate::visit::get_operation_type_export;
+use crate::visit::get_resolver_arguments;
+use crate::visit::get_resolver_context_type;
+use crate::visit::get_resolver_return_type;
 use crate::visit::raw_response_selections_to_babel;
 use crate::visit::raw_response_visit_selections;
 use crate::visit::transform_input_type;
@@ -104,9 +114,10 @@ pub(crate) fn write_operation_type_exports_section(
         .named(*THROW_ON_FIELD_ERROR_DIRECTIVE)
         .is_some();
 
-    let type_selections = visit_selections(
+    let data_type = get_operation_data_type(
         typegen_context,
-        &typegen_operation.selections,
+        typegen_operation,
+        is_throw_on_field_error,
         &mut input_object_types,
         &mut encountered_enums,
         &mut imported_raw_response_types,
@@ -116,27 +127,6 @@ pub(crate) fn write_operation_type_exports_section(
         &mut custom_scalars,
         &mut runtime_imports,
         &mut custom_error_import,
-        None,
-        is_throw_on_field_error,
-    );
-
-    let emit_optional_type = typegen_operation
-        .directives
-        .named(*CHILDREN_CAN_BUBBLE_METADATA_KEY)
-        .is_some();
-
-    let data_type = get_data_type(
-        typegen_context,
-        type_selections.into_iter(),
-        MaskStatus::Masked, // Queries are never unmasked
-        None,
-        emit_optional_type,
-        false, // Query types can never be plural
-        &mut encountered_enums,
-        &mut encountered_fragments,
-        &mut custom_scalars,
-        &mut runtime_imports,
-        &mut custom_error_import,
     );
 
     let raw_response_type_and_match_fields =
@@ -183,7 +173,7 @@ pub(crate) fn write_operation_type_exports_section(
     }
 
     write_import_actor_change_point(actor_change_status, writer)?;
-    runtime_imports.write_runtime_imports(writer)?;
+    runtime_imports.write_runtime_imports(typegen_context, writer)?;
     write_fragment_imports(typegen_context, None, encountered_fragments, writer)?;
     if custom_error_import.is_some() {
         write_import_custom_type(custom_error_import, writer)?;
@@ -258,6 +248,59 @@ pub(crate) fn write_operation_type_exports_section(
     Ok(())
 }
 
+/// Returns the type of the response data of an operation, collecting the
+/// enums, fragments, resolvers and other types that it references.
+#[allow(clippy::too_many_arguments)]
+fn get_operation_data_type(
+    typegen_context: &'_ TypegenContext<'_>,
+    typegen_operation: &OperationDefinition,
+    is_throw_on_field_error: bool,
+    input_object_types: &mut InputObjectTypes,
+    encountered_enums: &mut EncounteredEnums,
+    imported_raw_response_types: &mut ImportedRawResponseTypes,
+    encountered_fragments: &mut EncounteredFragments,
+    imported_resolvers: &mut ImportedResolvers,
+    actor_change_status: &mut ActorChangeStatus,
+    custom_scalars: &mut CustomScalarsImports,
+    runtime_imports: &mut RuntimeImports,
+    custom_error_import: &mut Option<CustomTypeImport>,
+) -> AST {
+    let type_selections = visit_selections(
+        typegen_context,
+        &typegen_operation.selections,
+        input_object_types,
+        encountered_enums,
+        imported_raw_response_types,
+        encountered_fragments,
+        imported_resolvers,
+        actor_change_status,
+        custom_scalars,
+        runtime_imports,
+        custom_error_import,
+        None,
+        is_throw_on_field_error,
+    );
+
+    let emit_optional_type = typegen_operation
+        .directives
+        .named(*CHILDREN_CAN_BUBBLE_METADATA_KEY)
+        .is_some();
+
+    get_data_type(
+        typegen_context,
+        type_selections.into_iter(),
+        MaskStatus::Masked, // Queries are never unmasked
+        None,
+        emit_optional_type,
+        false, // Query types can never be plural
+        encountered_enums,
+        encountered_fragments,
+        custom_scalars,
+        runtime_imports,
+        custom_error_import,
+    )
+}
+
 fn write_raw_response_and_get_raw_response_prop(
     raw_response_type_and_match_fields: Option<(AST, MatchFields)>,
     writer: &mut Box<dyn Writer>,
@@ -320,7 +363,7 @@ pub(crate) fn write_split_operation_type_exports_section(
         &mut custom_scalars,
     );
 
-    runtime_imports.write_runtime_imports(writer)?;
+    runtime_imports.write_runtime_imports(typegen_context, writer)?;
     write_fragment_imports(typegen_context, None, encountered_fragments, writer)?;
     write_split_raw_response_type_imports(typegen_context, imported_raw_response_types, writer)?;
 
@@ -465,7 +508,7 @@ pub(crate) fn write_fragment_type_exports_section(
     write_enum_definitions(typegen_context, encountered_enums, writer)?;
     write_custom_scalar_imports(custom_scalars, writer)?;
 
-    runtime_imports.write_runtime_imports(writer)?;
+    runtime_imports.write_runtime_imports(typegen_context, writer)?;
 
     write_relay_resolver_imports(imported_resolvers, writer)?;
 
@@ -531,20 +574,8 @@ fn write_fragment_imports(
     writer: &mut Box<dyn Writer>,
 ) -> FmtResult {
     for current_referenced_fragment in encountered_fragments.0.into_iter().sorted() {
-        let (current_referenced_fragment, fragment_type_name) = match current_referenced_fragment {
-            EncounteredFragment::Key(current_referenced_fragment) => (
-                current_referenced_fragment,
-                format!("{}$key", current_referenced_fragment),
-            ),
-            EncounteredFragment::Spread(current_referenced_fragment) => (
-                current_referenced_fragment,
-                format!("{}$fragmentType", current_referenced_fragment),
-            ),
-            EncounteredFragment::Data(current_referenced_fragment) => (
-                current_referenced_fragment,
-                format!("{}$data", current_referenced_fragment),
-            ),
-        };
+        let (current_referenced_fragment, fragment_type_name, fragment_import_path) =
+            get_fragment_type_import(typegen_context, current_referenced_fragment);
 
         let should_write_current_referenced_fragment = fragment_name_to_skip
             .map_or(true, |fragment_name_to_skip| {
@@ -555,49 +586,197 @@ fn write_fragment_imports(
             continue;
         }
 
-        match typegen_context.project_config.js_module_format {
-            JsModuleFormat::CommonJS => {
-                if typegen_context.has_unified_output {
-                    writer.write_import_fragment_type(
-                        &[&fragment_type_name],
-                        &format!("./{}.graphql", current_referenced_fragment),
-                    )?;
-                } else {
-                    let fragment_location = typegen_context
-                        .fragment_locations
-                        .location(&current_referenced_fragment)
-                        .unwrap_or_else(|| {
-                            panic!(
-                                "Expected location for fragment {}.",
-                                current_referenced_fragment
-                            )
-                        });
-
-                    let fragment_import_path =
+        writer.write_import_fragment_type(&[&fragment_type_name], &fragment_import_path)?;
+    }
+    Ok(())
+}
+
+/// Returns the name of the referenced fragment, the name of the type to import
+/// for it and the path of the artifact it is imported from.
+fn get_fragment_type_import(
+    typegen_context: &'_ TypegenContext<'_>,
+    encountered_fragment: EncounteredFragment,
+) -> (FragmentDefinitionName, String, String) {
+    let (fragment_name, fragment_type_name) = match encountered_fragment {
+        EncounteredFragment::Key(fragment_name) => {
+            (fragment_name, format!("{}$key", fragment_name))
+        }
+        EncounteredFragment::Spread(fragment_name) => {
+            (fragment_name, format!("{}$fragmentType", fragment_name))
+        }
+        EncounteredFragment::Data(fragment_name) => {
+            (fragment_name, format!("{}$data", fragment_name))
+        }
+    };
+    let fragment_import_path = get_artifact_import_path(
+        typegen_context,
+        fragment_name.0,
+        typegen_context.fragment_locations.location(&fragment_name),
+    )
+    .unwrap_or_else(|| panic!("Expected location for fragment {}.", fragment_name));
+    (fragment_name, fragment_type_name, fragment_import_path)
+}
+
+/// Returns the path to import the types of the artifact generated for the
+/// definition `definition_name` from, if it can be determined.
+fn get_artifact_import_path(
+    typegen_context: &'_ TypegenContext<'_>,
+    definition_name: StringKey,
+    definition_location: Option<Location>,
+) -> Option<String> {
+    match typegen_context.project_config.js_module_format {
+        JsModuleFormat::CommonJS => {
+            if typegen_context.has_unified_output {
+                Some(format!("./{}.graphql", definition_name))
+            } else {
+                definition_location.map(|definition_location| {
+                    let artifact_import_path =
                         typegen_context.project_config.js_module_import_identifier(
-                            &typegen_context.project_config.artifact_path_for_definition(
-                                typegen_context.definition_source_location,
-                            ),
+                            &typegen_context.artifact_path,
                             &typegen_context.project_config.create_path_for_artifact(
-                                fragment_location.source_location(),
-                                current_referenced_fragment.to_string(),
+                                definition_location.source_location(),
+                                definition_name.to_string(),
                             ),
                         );
-
-                    writer.write_import_fragment_type(
-                        &[&fragment_type_name],
-                        &format!("./{}.graphql", fragment_import_path),
-                    )?;
-                }
+                    format!("./{}.graphql", artifact_import_path)
+                })
             }
-            JsModuleFormat::Haste => {
-                writer.write_import_fragment_type(
-                    &[&fragment_type_name],
-                    &format!("{}.graphql", current_referenced_fragment),
-                )?;
+        }
+        JsModuleFormat::Haste => Some(format!("{}.graphql", definition_name)),
+    }
+}
+
+/// Writes the type of the function implementing each Relay Resolver field of
+/// the schema, e.g. `User__name$resolver`, so that resolver modules can be
+/// checked against the schema.
+pub(crate) fn write_resolver_function_types_section(
+    typegen_context: &'_ TypegenContext<'_>,
+    writer: &mut Box<dyn Writer>,
+) -> FmtResult {
+    let schema = typegen_context.schema;
+    let mut resolver_fields = schema
+        .get_fields()
+        .filter_map(|field| {
+            let resolver_info = get_resolver_info(schema, field, field.name.location)?.ok()?;
+            if field
+                .directives
+                .named(*RESOLVER_BELONGS_TO_BASE_SCHEMA_DIRECTIVE)
+                .is_some()
+            {
+                return None;
             }
+            Some((
+                schema.get_type_name(field.parent_type?),
+                field,
+                resolver_info,
+            ))
+        })
+        .collect::<Vec<_>>();
+    resolver_fields.sort_by_key(|(parent_type_name, field, _)| {
+        (parent_type_name.lookup(), field.name.item.lookup())
+    });
+
+    let mut input_object_types = Default::default();
+    let mut encountered_enums = Default::default();
+    let mut encountered_fragments = Default::default();
+    let mut custom_scalars = CustomScalarsImports::default();
+    let mut imported_raw_response_types = Default::default();
+    let mut runtime_imports = RuntimeImports::default();
+    let context_import = get_resolver_context_type(typegen_context);
+
+    let mut resolver_function_types = vec![];
+    for (parent_type_name, field, resolver_info) in resolver_fields {
+        let arguments = get_resolver_arguments(
+            resolver_info.fragment_name,
+            resolver_info
+                .fragment_data_injection_mode
+                .zip(resolver_info.fragment_name)
+                .map(|(injection_mode, fragment_name)| (fragment_name, injection_mode)),
+            &mut encountered_fragments,
+            typegen_context,
+            &mut input_object_types,
+            &mut encountered_enums,
+            &mut custom_scalars,
+            field,
+            context_import,
+        );
+        let output_type_info = get_resolver_output_type_info(
+            typegen_context.project_config.name,
+            schema,
+            field,
+            resolver_info.has_output_type,
+        );
+        let mut return_type = get_resolver_return_type(
+            typegen_context,
+            field,
+            &output_type_info,
+            &mut encountered_enums,
+            &mut custom_scalars,
+            &mut imported_raw_response_types,
+            &mut runtime_imports,
+        );
+        if resolver_info.live {
+            return_type = get_live_state_type(return_type, &mut runtime_imports);
+        }
+        resolver_function_types.push((
+            format!(
+                "{}$resolver",
+                typegen_context
+                    .project_config
+                    .name
+                    .generate_name_for_object_and_field(parent_type_name, field.name.item)
+            ),
+            AST::FunctionType {
+                arguments,
+                return_type: Box::new(return_type),
+            },
+        ));
+    }
+
+    runtime_imports.write_runtime_imports(typegen_context, writer)?;
+    // Unlike in operation and fragment artifacts, the types of the fragments
+    // are needed in TypeScript too.
+    for encountered_fragment in encountered_fragments.0.into_iter().sorted() {
+        let (_, fragment_type_name, fragment_import_path) =
+            get_fragment_type_import(typegen_context, encountered_fragment);
+        writer.write_import_type(&[&fragment_type_name], &fragment_import_path)?;
+    }
+    if let Some(context_import) = context_import {
+        if !resolver_function_types.is_empty() {
+            writer.write_import_type(
+                &[context_import.name.lookup()],
+                context_import.import_path.lookup(),
+            )?;
+        }
+    }
+    imported_raw_response_types.0.sort_keys();
+    for (imported_raw_response_type, imported_raw_response_document_location) in
+        imported_raw_response_types.0
+    {
+        match get_artifact_import_path(
+            typegen_context,
+            imported_raw_response_type,
+            imported_raw_response_document_location,
+        ) {
+            Some(artifact_import_path) => writer.write_import_type(
+                &[imported_raw_response_type.lookup()],
+                &artifact_import_path,
+            )?,
+            None => writer.write_any_type_definition(imported_raw_response_type.lookup())?,
         }
     }
+    write_custom_scalar_imports(custom_scalars, writer)?;
+    write_enum_definitions(typegen_context, encountered_enums, writer)?;
+    write_input_object_types(
+        input_object_types
+            .into_iter()
+            .map(|(key, val)| (key, val.unwrap_resolved_type())),
+        writer,
+    )?;
+
+    for (name, function_type) in resolver_function_types {
+        writer.write_export_type(&name, &function_type)?;
+    }
     Ok(())
 }
 
@@ -665,41 +844,16 @@ fn write_split_raw_response_type_imports(
     for (imported_raw_response_type, imported_raw_response_document_location) in
         imported_raw_response_types.0
     {
-        match typegen_context.project_config.js_module_format {
-            JsModuleFormat::CommonJS => {
-                if typegen_context.has_unified_output {
-                    writer.write_import_fragment_type(
-                        &[imported_raw_response_type.lookup()],
-                        &format!("./{}.graphql", imported_raw_response_type),
-                    )?;
-                } else if let Some(imported_raw_response_document_location) =
-                    imported_raw_response_document_location
-                {
-                    let artifact_import_path =
-                        typegen_context.project_config.js_module_import_identifier(
-                            &typegen_context.project_config.artifact_path_for_definition(
-                                typegen_context.definition_source_location,
-                            ),
-                            &typegen_context.project_config.create_path_for_artifact(
-                                imported_raw_response_document_location.source_location(),
-                                imported_raw_response_type.to_string(),
-                            ),
-                        );
-
-                    writer.write_import_fragment_type(
-                        &[imported_raw_response_type.lookup()],
-                        &format!("./{}.graphql", artifact_import_path),
-                    )?;
-                } else {
-                    writer.write_any_type_definition(imported_raw_response_type.lookup())?;
-                }
-            }
-            JsModuleFormat::Haste => {
-                writer.write_import_fragment_type(
-                    &[imported_raw_response_type.lookup()],
-                    &format!("{}.graphql", imported_raw_response_type),
-                )?;
-            }
+        match get_artifact_import_path(
+            typegen_context,
+            imported_raw_response_type,
+            imported_raw_response_document_location,
+        ) {
+            Some(artifact_import_path) => writer.write_import_fragment_type(
+                &[imported_raw_response_type.lookup()],
+                &artifact_import_path,
+            )?,
+            None => writer.write_any_type_definition(imported_raw_response_type.lookup())?,
         }
     }
 
@@ -718,7 +872,10 @@ fn write_enum_definitions(
         .enum_module_suffix;
     for enum_id in enum_ids {
         let enum_type = typegen_context.schema.enum_(enum_id);
-        if !enum_type.is_extension && maybe_suffix.is_some() {
+        if !enum_type.is_extension
+            && maybe_suffix.is_some()
+            && !typegen_context.typegen_options.export_enum_values
+        {
             // We can't chain `if let` statements, so we need to unwrap here.
             let suffix = maybe_suffix.as_ref().unwrap();
             writer.write_import_type(
@@ -812,6 +969,42 @@ fn write_input_object_types(
     Ok(())
 }
 
+/// Write the function that checks at runtime that a response matches the
+/// data type of the operation.
+pub(crate) fn write_response_validator_function(
+    typegen_context: &'_ TypegenContext<'_>,
+    typegen_operation: &OperationDefinition,
+    writer: &mut Box<dyn Writer>,
+) -> FmtResult {
+    let is_throw_on_field_error = typegen_operation
+        .directives
+        .named(*THROW_ON_FIELD_ERROR_DIRECTIVE)
+        .is_some();
+    let server_operation = get_server_operation(typegen_context.schema, typegen_operation);
+
+    let data_type = get_operation_data_type(
+        typegen_context,
+        &server_operation,
+        is_throw_on_field_error,
+        &mut Default::default(),
+        &mut Default::default(),
+        &mut Default::default(),
+        &mut Default::default(),
+        &mut Default::default(),
+        &mut ActorChangeStatus::NoActorChange,
+        &mut Default::default(),
+        &mut Default::default(),
+        &mut None,
+    );
+
+    write_response_validator(
+        typegen_context.schema,
+        typegen_context.project_config.typegen_config.language,
+        &data_type,
+        writer,
+    )
+}
+
 /// Write the assignable fragment validator function.
 ///
 /// Validators accept an item which *may* be valid for assignment and returns either
@@ -51,6 +51,10 @@ pub enum AST {
     ReturnTypeOfMethodCall(Box<AST>, StringKey),
     ActorChangePoint(Box<AST>),
     AssertFunctionType(FunctionTypeAssertion),
+    FunctionType {
+        arguments: Vec<KeyValuePairProp>,
+        return_type: Box<AST>,
+    },
     GenericType {
         outer: StringKey,
         inner: Vec<AST>,
@@ -0,0 +1,49 @@
+/*
+ * Copyright (c) Meta Platforms, Inc. and affiliates.
+ *
+ * This source code is licensed under the MIT license found in the
+ * LICENSE file in the root directory of this source tree.
+ */
+
+//! Parsing of the typegen config of the fixtures, shared by the Flow and
+//! TypeScript tests.
+
+use ::intern::string_key::StringKey;
+use common::ScalarName;
+use fnv::FnvBuildHasher;
+use indexmap::IndexMap;
+use relay_config::CustomType;
+
+type FnvIndexMap<K, V> = IndexMap<K, V, FnvBuildHasher>;
+
+/// The custom types of the typegen config in the `%typegen_config%` section
+/// at the end of a fixture, as JSON with the keys of the config file.
+pub fn parse_fixture_custom_types(
+    typegen_config: Option<&str>,
+) -> (
+    FnvIndexMap<ScalarName, CustomType>,
+    FnvIndexMap<ScalarName, CustomType>,
+    FnvIndexMap<StringKey, CustomType>,
+) {
+    let Some(typegen_config) = typegen_config else {
+        return Default::default();
+    };
+    let mut typegen_config: serde_json::Map<String, serde_json::Value> =
+        serde_json::from_str(typegen_config).unwrap();
+    let mut take = |key: &str| {
+        typegen_config
+            .remove(key)
+            .unwrap_or_else(|| serde_json::Value::Object(Default::default()))
+    };
+    let custom_types = (
+        serde_json::from_value(take("customScalarTypes")).unwrap(),
+        serde_json::from_value(take("customScalarInputTypes")).unwrap(),
+        serde_json::from_value(take("customFieldTypes")).unwrap(),
+    );
+    assert!(
+        typegen_config.is_empty(),
+        "Unsupported typegen config options: {:?}",
+        typegen_config.keys().collect::<Vec<_>>()
+    );
+    custom_types
+}
@@ -4,7 +4,7 @@
  * This source code is licensed under the MIT license found in the
  * LICENSE file in the root directory of this source tree.
  *
- * @generated SignedSource<<efd0cc9dd8094edb0a45db08e783e4b1>>
+ * @generated SignedSource<<5178b9ef74df1b98791f3fbd988f4105>>
  */
 
 mod generate_flow;
@@ -166,6 +166,13 @@ async fn conditional() {
     test_fixture(transform_fixture, file!(), "conditional.graphql", "generate_flow/fixtures/conditional.expected", input, expected).await;
 }
 
+#[tokio::test]
+async fn custom_scalar_input_and_field_types() {
+    let input = include_str!("generate_flow/fixtures/custom-scalar-input-and-field-types.graphql");
+    let expected = include_str!("generate_flow/fixtures/custom-scalar-input-and-field-types.expected");
+    test_fixture(transform_fixture, file!(), "custom-scalar-input-and-field-types.graphql", "generate_flow/fixtures/custom-scalar-input-and-field-types.expected", input, expected).await;
+}
+
 #[tokio::test]
 async fn custom_scalar_type_import() {
     let input = include_str!("generate_flow/fixtures/custom-scalar-type-import.graphql");
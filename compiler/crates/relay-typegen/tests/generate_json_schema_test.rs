@@ -0,0 +1,41 @@
+/*
+ * Copyright (c) Meta Platforms, Inc. and affiliates.
+ *
+ * This source code is licensed under the MIT license found in the
+ * LICENSE file in the root directory of this source tree.
+ *
+ * @generated SignedSource<<c425a3c5571122e0b2e2ac231a26ae09>>
+ */
+
+mod generate_json_schema;
+
+use generate_json_schema::transform_fixture;
+use fixture_tests::test_fixture;
+
+#[tokio::test]
+async fn fragment_with_custom_scalar_and_spread() {
+    let input = include_str!("generate_json_schema/fixtures/fragment-with-custom-scalar-and-spread.graphql");
+    let expected = include_str!("generate_json_schema/fixtures/fragment-with-custom-scalar-and-spread.expected");
+    test_fixture(transform_fixture, file!(), "fragment-with-custom-scalar-and-spread.graphql", "generate_json_schema/fixtures/fragment-with-custom-scalar-and-spread.expected", input, expected).await;
+}
+
+#[tokio::test]
+async fn mutation_with_input_object() {
+    let input = include_str!("generate_json_schema/fixtures/mutation-with-input-object.graphql");
+    let expected = include_str!("generate_json_schema/fixtures/mutation-with-input-object.expected");
+    test_fixture(transform_fixture, file!(), "mutation-with-input-object.graphql", "generate_json_schema/fixtures/mutation-with-input-object.expected", input, expected).await;
+}
+
+#[tokio::test]
+async fn query_with_enum_modules() {
+    let input = include_str!("generate_json_schema/fixtures/query-with-enum-modules.graphql");
+    let expected = include_str!("generate_json_schema/fixtures/query-with-enum-modules.expected");
+    test_fixture(transform_fixture, file!(), "query-with-enum-modules.graphql", "generate_json_schema/fixtures/query-with-enum-modules.expected", input, expected).await;
+}
+
+#[tokio::test]
+async fn query_with_variables_and_enums() {
+    let input = include_str!("generate_json_schema/fixtures/query-with-variables-and-enums.graphql");
+    let expected = include_str!("generate_json_schema/fixtures/query-with-variables-and-enums.expected");
+    test_fixture(transform_fixture, file!(), "query-with-variables-and-enums.graphql", "generate_json_schema/fixtures/query-with-variables-and-enums.expected", input, expected).await;
+}
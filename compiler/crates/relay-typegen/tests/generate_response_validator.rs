@@ -0,0 +1,102 @@
+/*
+ * Copyright (c) Meta Platforms, Inc. and affiliates.
+ *
+ * This source code is licensed under the MIT license found in the
+ * LICENSE file in the root directory of this source tree.
+ */
+
+use std::sync::Arc;
+
+use common::ConsoleLogger;
+use common::FeatureFlags;
+use common::ScalarName;
+use common::SourceLocationKey;
+use fixture_tests::Fixture;
+use fnv::FnvBuildHasher;
+use graphql_ir::build_ir_in_relay_mode;
+use graphql_ir::Program;
+use graphql_syntax::parse_executable;
+use graphql_test_helpers::diagnostics_to_sorted_string;
+use indexmap::IndexMap;
+use intern::string_key::Intern;
+use relay_codegen::JsModuleFormat;
+use relay_config::CustomType;
+use relay_config::ProjectConfig;
+use relay_config::ProjectName;
+use relay_test_schema::get_test_schema;
+use relay_test_schema::get_test_schema_with_extensions;
+use relay_transforms::apply_transforms;
+use relay_typegen::FragmentLocations;
+use relay_typegen::TypegenConfig;
+use relay_typegen::TypegenLanguage;
+
+type FnvIndexMap<K, V> = IndexMap<K, V, FnvBuildHasher>;
+
+pub async fn transform_fixture(fixture: &Fixture<'_>) -> Result<String, String> {
+    let parts = fixture.content.split("%extensions%").collect::<Vec<_>>();
+    let (source, schema) = match parts.as_slice() {
+        [source, extensions] => (source, get_test_schema_with_extensions(extensions)),
+        [source] => (source, get_test_schema()),
+        _ => panic!(),
+    };
+
+    let source_location = SourceLocationKey::standalone(fixture.file_name);
+    let ast = parse_executable(source, source_location)
+        .map_err(|diagnostics| diagnostics_to_sorted_string(source, &diagnostics))?;
+    let feature_flags = FeatureFlags {
+        enable_relay_resolver_transform: true,
+        ..Default::default()
+    };
+    let ir = build_ir_in_relay_mode(&schema, &ast.definitions, &feature_flags)
+        .map_err(|diagnostics| diagnostics_to_sorted_string(source, &diagnostics))?;
+    let program = Program::from_definitions(Arc::clone(&schema), ir);
+
+    let mut custom_scalar_types = FnvIndexMap::default();
+    custom_scalar_types.insert(
+        ScalarName("JSON".intern()),
+        CustomType::Name("JSONValue".intern()),
+    );
+    let language = if fixture.content.contains("# relay:language typescript") {
+        TypegenLanguage::TypeScript
+    } else {
+        TypegenLanguage::Flow
+    };
+    let project_config = ProjectConfig {
+        name: ProjectName::default(),
+        js_module_format: JsModuleFormat::Haste,
+        feature_flags: Arc::new(feature_flags),
+        typegen_config: TypegenConfig {
+            language,
+            custom_scalar_types,
+            generate_response_validators: true,
+            ..Default::default()
+        },
+        ..Default::default()
+    };
+
+    let programs = apply_transforms(
+        &project_config,
+        Arc::new(program),
+        Default::default(),
+        Arc::new(ConsoleLogger),
+        None,
+        None,
+    )
+    .map_err(|diagnostics| diagnostics_to_sorted_string(source, &diagnostics))?;
+
+    let fragment_locations = FragmentLocations::new(programs.typegen.fragments());
+    let mut operations: Vec<_> = programs.typegen.operations().collect();
+    operations.sort_by_key(|op| op.name.item.0);
+    let operation_strings = operations.into_iter().map(|typegen_operation| {
+        relay_typegen::generate_response_validator_export(
+            typegen_operation,
+            &schema,
+            &project_config,
+            &fragment_locations,
+        )
+    });
+
+    Ok(operation_strings.collect::<Vec<_>>().join(
+        "\n-------------------------------------------------------------------------------\n",
+    ))
+}
@@ -0,0 +1,48 @@
+/*
+ * Copyright (c) Meta Platforms, Inc. and affiliates.
+ *
+ * This source code is licensed under the MIT license found in the
+ * LICENSE file in the root directory of this source tree.
+ *
+ * @generated SignedSource<<ebfcfe61bb678c9547bfa375e22a68a5>>
+ */
+
+mod generate_response_validator;
+
+use generate_response_validator::transform_fixture;
+use fixture_tests::test_fixture;
+
+#[tokio::test]
+async fn query_typescript() {
+    let input = include_str!("generate_response_validator/fixtures/query-typescript.graphql");
+    let expected = include_str!("generate_response_validator/fixtures/query-typescript.expected");
+    test_fixture(transform_fixture, file!(), "query-typescript.graphql", "generate_response_validator/fixtures/query-typescript.expected", input, expected).await;
+}
+
+#[tokio::test]
+async fn query_with_custom_scalar_and_fragment_spread() {
+    let input = include_str!("generate_response_validator/fixtures/query-with-custom-scalar-and-fragment-spread.graphql");
+    let expected = include_str!("generate_response_validator/fixtures/query-with-custom-scalar-and-fragment-spread.expected");
+    test_fixture(transform_fixture, file!(), "query-with-custom-scalar-and-fragment-spread.graphql", "generate_response_validator/fixtures/query-with-custom-scalar-and-fragment-spread.expected", input, expected).await;
+}
+
+#[tokio::test]
+async fn query_with_discriminated_union() {
+    let input = include_str!("generate_response_validator/fixtures/query-with-discriminated-union.graphql");
+    let expected = include_str!("generate_response_validator/fixtures/query-with-discriminated-union.expected");
+    test_fixture(transform_fixture, file!(), "query-with-discriminated-union.graphql", "generate_response_validator/fixtures/query-with-discriminated-union.expected", input, expected).await;
+}
+
+#[tokio::test]
+async fn query_with_enum() {
+    let input = include_str!("generate_response_validator/fixtures/query-with-enum.graphql");
+    let expected = include_str!("generate_response_validator/fixtures/query-with-enum.expected");
+    test_fixture(transform_fixture, file!(), "query-with-enum.graphql", "generate_response_validator/fixtures/query-with-enum.expected", input, expected).await;
+}
+
+#[tokio::test]
+async fn query_with_nullable_and_list_fields() {
+    let input = include_str!("generate_response_validator/fixtures/query-with-nullable-and-list-fields.graphql");
+    let expected = include_str!("generate_response_validator/fixtures/query-with-nullable-and-list-fields.expected");
+    test_fixture(transform_fixture, file!(), "query-with-nullable-and-list-fields.graphql", "generate_response_validator/fixtures/query-with-nullable-and-list-fields.expected", input, expected).await;
+}
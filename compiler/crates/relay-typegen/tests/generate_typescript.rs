@@ -5,6 +5,9 @@
  * LICENSE file in the root directory of this source tree.
  */
 
+#[path = "fixture_typegen_config/mod.rs"]
+mod fixture_typegen_config;
+
 use core::panic;
 use std::path::PathBuf;
 use std::str::FromStr;
@@ -17,6 +20,7 @@ use common::FeatureFlags;
 use common::ScalarName;
 use common::SourceLocationKey;
 use fixture_tests::Fixture;
+use fixture_typegen_config::parse_fixture_custom_types;
 use fnv::FnvBuildHasher;
 use fnv::FnvHashMap;
 use graphql_ir::build;
@@ -41,7 +45,13 @@ use relay_typegen::TypegenLanguage;
 type FnvIndexMap<K, V> = IndexMap<K, V, FnvBuildHasher>;
 
 pub async fn transform_fixture(fixture: &Fixture<'_>) -> Result<String, String> {
-    let parts = fixture.content.split("%extensions%").collect::<Vec<_>>();
+    let (content, typegen_config) = match fixture.content.split_once("%typegen_config%") {
+        Some((content, typegen_config)) => (content, Some(typegen_config)),
+        None => (fixture.content, None),
+    };
+    let (fixture_custom_scalar_types, custom_scalar_input_types, custom_field_types) =
+        parse_fixture_custom_types(typegen_config);
+    let parts = content.split("%extensions%").collect::<Vec<_>>();
     let (source, schema) = match parts.as_slice() {
         [source, extensions] => (source, get_test_schema_with_extensions(extensions)),
         [source] => (source, get_test_schema()),
@@ -67,6 +77,7 @@ pub async fn transform_fixture(fixture: &Fixture<'_>) -> Result<String, String>
             path: "TypeDefsFile".into(),
         }),
     );
+    custom_scalar_types.extend(fixture_custom_scalar_types);
     // TODO: T195687167 This is currently duplicated in flow and TS - export this to a common place
     let custom_error_type: Option<CustomTypeImport> = {
         let rgx =
@@ -102,10 +113,20 @@ pub async fn transform_fixture(fixture: &Fixture<'_>) -> Result<String, String>
         typegen_config: TypegenConfig {
             language: TypegenLanguage::TypeScript,
             custom_scalar_types,
+            custom_scalar_input_types,
+            custom_field_types,
             use_import_type_syntax: fixture
                 .content
                 .contains("# typegen_config.use_import_type_syntax = true"),
             custom_error_type,
+            typescript_fragment_ref_brands_module: if fixture
+                .content
+                .contains("# typegen_config.typescript_fragment_ref_brands_module = true")
+            {
+                Some("RelayFragmentRefBrands.ts".into())
+            } else {
+                None
+            },
             ..Default::default()
         },
         feature_flags: Arc::new(FeatureFlags {
@@ -4,7 +4,7 @@
  * This source code is licensed under the MIT license found in the
  * LICENSE file in the root directory of this source tree.
  *
- * @generated SignedSource<<dc6df4bac160f12fe89c63e98c18f01d>>
+ * @generated SignedSource<<8c8bbbbcc07bcc9f80a142ba57f156d6>>
  */
 
 mod generate_typescript;
@@ -138,6 +138,13 @@ async fn conditional() {
     test_fixture(transform_fixture, file!(), "conditional.graphql", "generate_typescript/fixtures/conditional.expected", input, expected).await;
 }
 
+#[tokio::test]
+async fn custom_scalar_input_and_field_types() {
+    let input = include_str!("generate_typescript/fixtures/custom-scalar-input-and-field-types.graphql");
+    let expected = include_str!("generate_typescript/fixtures/custom-scalar-input-and-field-types.expected");
+    test_fixture(transform_fixture, file!(), "custom-scalar-input-and-field-types.graphql", "generate_typescript/fixtures/custom-scalar-input-and-field-types.expected", input, expected).await;
+}
+
 #[tokio::test]
 async fn custom_scalar_type_import() {
     let input = include_str!("generate_typescript/fixtures/custom-scalar-type-import.graphql");
@@ -152,6 +159,13 @@ async fn default_input() {
     test_fixture(transform_fixture, file!(), "default-input.graphql", "generate_typescript/fixtures/default-input.expected", input, expected).await;
 }
 
+#[tokio::test]
+async fn fragment_ref_brands() {
+    let input = include_str!("generate_typescript/fixtures/fragment-ref-brands.graphql");
+    let expected = include_str!("generate_typescript/fixtures/fragment-ref-brands.expected");
+    test_fixture(transform_fixture, file!(), "fragment-ref-brands.graphql", "generate_typescript/fixtures/fragment-ref-brands.expected", input, expected).await;
+}
+
 #[tokio::test]
 async fn fragment_spread() {
     let input = include_str!("generate_typescript/fixtures/fragment-spread.graphql");
@@ -24,12 +24,18 @@ use crate::definitions::Type;
 use crate::definitions::TypeChange;
 
 // This enum is very similar to the schema Type enum but uses StringKey instead of id
-#[derive(Eq, PartialEq, Hash)]
+#[derive(Clone, Eq, PartialEq, Hash)]
 pub enum IncrementalBuildSchemaChange {
     Enum(StringKey),
     Object(StringKey),
     Union(StringKey),
     Interface(StringKey),
+    /// Only used to find the documents affected by a removed scalar, since
+    /// that change needs a full build.
+    Scalar(StringKey),
+    /// Only used to find the documents affected by a removed or changed input
+    /// object, since those changes need a full build.
+    InputObject(StringKey),
 }
 
 impl fmt::Debug for IncrementalBuildSchemaChange {
@@ -39,6 +45,8 @@ impl fmt::Debug for IncrementalBuildSchemaChange {
             IncrementalBuildSchemaChange::Object(name) => write!(f, "object({})", name),
             IncrementalBuildSchemaChange::Union(name) => write!(f, "union({})", name),
             IncrementalBuildSchemaChange::Interface(name) => write!(f, "interface({})", name),
+            IncrementalBuildSchemaChange::Scalar(name) => write!(f, "scalar({})", name),
+            IncrementalBuildSchemaChange::InputObject(name) => write!(f, "input_object({})", name),
         }
     }
 }
@@ -137,7 +145,7 @@ impl SchemaChange {
                         | DefinitionChange::InputObjectAdded(_) => {}
 
                         // safe with incremental build changes
-                        DefinitionChange::EnumChanged { name }
+                        DefinitionChange::EnumChanged { name, .. }
                         | DefinitionChange::EnumRemoved(name) => {
                             needs_incremental_build
                                 .insert(IncrementalBuildSchemaChange::Enum(name));
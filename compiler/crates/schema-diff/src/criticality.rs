@@ -0,0 +1,434 @@
+/*
+ * Copyright (c) Meta Platforms, Inc. and affiliates.
+ *
+ * This source code is licensed under the MIT license found in the
+ * LICENSE file in the root directory of this source tree.
+ */
+
+use std::fmt;
+
+use fnv::FnvHashMap;
+use intern::string_key::StringKey;
+use rustc_hash::FxHashSet;
+
+use crate::check::IncrementalBuildSchemaChange;
+use crate::definitions::ArgumentChange;
+use crate::definitions::DefinitionChange;
+use crate::definitions::SchemaChange;
+use crate::definitions::Type;
+use crate::definitions::TypeChange;
+
+/// How a schema change affects the clients of the schema, following the
+/// breaking and dangerous changes of the GraphQL reference implementation.
+#[derive(Clone, Copy, Debug, Eq, PartialEq, PartialOrd, Ord, Hash)]
+pub enum ChangeCriticality {
+    /// Existing documents might not be valid anymore, or might get responses
+    /// that they do not expect.
+    Breaking,
+    /// Existing documents are valid, but clients might see values they do not
+    /// handle, like new enum values or union members.
+    Dangerous,
+    Safe,
+}
+
+impl fmt::Display for ChangeCriticality {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ChangeCriticality::Breaking => write!(f, "breaking"),
+            ChangeCriticality::Dangerous => write!(f, "dangerous"),
+            ChangeCriticality::Safe => write!(f, "safe"),
+        }
+    }
+}
+
+#[derive(Debug, Eq, PartialEq)]
+pub struct ClassifiedChange {
+    pub criticality: ChangeCriticality,
+    /// The changed type, if documents can select it or use it as the type of
+    /// a variable or an argument.
+    pub type_change: Option<IncrementalBuildSchemaChange>,
+    pub message: String,
+}
+
+impl SchemaChange {
+    /// Classifies every change of the schema. Changes that are not detected
+    /// in detail, like the changes of directive definitions, are reported as
+    /// a single dangerous change.
+    pub fn classify(&self) -> Vec<ClassifiedChange> {
+        match self {
+            SchemaChange::None => vec![],
+            SchemaChange::GenericChange | SchemaChange::InvalidSchema => vec![ClassifiedChange {
+                criticality: ChangeCriticality::Dangerous,
+                type_change: None,
+                message: "The schema changed in a way that cannot be classified, like a change of directive definitions or schema extensions.".to_string(),
+            }],
+            SchemaChange::DefinitionChanges(changes) => {
+                let mut classified_changes = changes
+                    .iter()
+                    .flat_map(DefinitionChange::classify)
+                    .collect::<Vec<_>>();
+                classified_changes.sort_by(|a, b| {
+                    a.criticality
+                        .cmp(&b.criticality)
+                        .then_with(|| a.message.cmp(&b.message))
+                });
+                classified_changes
+            }
+        }
+    }
+}
+
+impl DefinitionChange {
+    pub fn classify(&self) -> Vec<ClassifiedChange> {
+        let mut changes = ClassifiedChanges::default();
+        match self {
+            DefinitionChange::EnumAdded(name) => {
+                changes.push_safe(None, format!("Enum `{}` was added.", name))
+            }
+            DefinitionChange::UnionAdded(name) => {
+                changes.push_safe(None, format!("Union `{}` was added.", name))
+            }
+            DefinitionChange::ScalarAdded(name) => {
+                changes.push_safe(None, format!("Scalar `{}` was added.", name))
+            }
+            DefinitionChange::InputObjectAdded(name) => {
+                changes.push_safe(None, format!("Input object `{}` was added.", name))
+            }
+            DefinitionChange::InterfaceAdded(name) => {
+                changes.push_safe(None, format!("Interface `{}` was added.", name))
+            }
+            DefinitionChange::ObjectAdded(name) => {
+                changes.push_safe(None, format!("Object `{}` was added.", name))
+            }
+            DefinitionChange::EnumRemoved(name) => changes.push(
+                ChangeCriticality::Breaking,
+                Some(IncrementalBuildSchemaChange::Enum(*name)),
+                format!("Enum `{}` was removed.", name),
+            ),
+            DefinitionChange::UnionRemoved(name) => changes.push(
+                ChangeCriticality::Breaking,
+                Some(IncrementalBuildSchemaChange::Union(*name)),
+                format!("Union `{}` was removed.", name),
+            ),
+            DefinitionChange::ScalarRemoved(name) => changes.push(
+                ChangeCriticality::Breaking,
+                Some(IncrementalBuildSchemaChange::Scalar(*name)),
+                format!("Scalar `{}` was removed.", name),
+            ),
+            DefinitionChange::InputObjectRemoved(name) => changes.push(
+                ChangeCriticality::Breaking,
+                Some(IncrementalBuildSchemaChange::InputObject(*name)),
+                format!("Input object `{}` was removed.", name),
+            ),
+            DefinitionChange::InterfaceRemoved(name) => changes.push(
+                ChangeCriticality::Breaking,
+                Some(IncrementalBuildSchemaChange::Interface(*name)),
+                format!("Interface `{}` was removed.", name),
+            ),
+            DefinitionChange::ObjectRemoved(name) => changes.push(
+                ChangeCriticality::Breaking,
+                Some(IncrementalBuildSchemaChange::Object(*name)),
+                format!("Object `{}` was removed.", name),
+            ),
+            DefinitionChange::EnumChanged {
+                name,
+                added,
+                removed,
+            } => {
+                let type_change = Some(IncrementalBuildSchemaChange::Enum(*name));
+                for value in added {
+                    changes.push(
+                        ChangeCriticality::Dangerous,
+                        type_change.clone(),
+                        format!("Value `{}` was added to enum `{}`.", value, name),
+                    );
+                }
+                for value in removed {
+                    changes.push(
+                        ChangeCriticality::Breaking,
+                        type_change.clone(),
+                        format!("Value `{}` was removed from enum `{}`.", value, name),
+                    );
+                }
+                if added.is_empty() && removed.is_empty() {
+                    changes.push_safe(
+                        type_change,
+                        format!("Values of enum `{}` were reordered.", name),
+                    );
+                }
+            }
+            DefinitionChange::UnionChanged {
+                name,
+                added,
+                removed,
+            } => {
+                let type_change = Some(IncrementalBuildSchemaChange::Union(*name));
+                for member in added {
+                    changes.push(
+                        ChangeCriticality::Dangerous,
+                        type_change.clone(),
+                        format!("Type `{}` was added to union `{}`.", member, name),
+                    );
+                }
+                for member in removed {
+                    changes.push(
+                        ChangeCriticality::Breaking,
+                        type_change.clone(),
+                        format!("Type `{}` was removed from union `{}`.", member, name),
+                    );
+                }
+            }
+            DefinitionChange::InputObjectChanged {
+                name,
+                added,
+                removed,
+            } => {
+                let type_change = Some(IncrementalBuildSchemaChange::InputObject(*name));
+                changes.classify_input_values(type_change, added, removed, |field| {
+                    format!("Input field `{}.{}`", name, field)
+                });
+            }
+            DefinitionChange::InterfaceChanged {
+                name,
+                added,
+                removed,
+                changed,
+            } => {
+                changes.classify_fields(
+                    IncrementalBuildSchemaChange::Interface(*name),
+                    *name,
+                    added,
+                    removed,
+                    changed,
+                );
+            }
+            DefinitionChange::ObjectChanged {
+                name,
+                added,
+                removed,
+                changed,
+                interfaces_added,
+                interfaces_removed,
+            } => {
+                let type_change = IncrementalBuildSchemaChange::Object(*name);
+                for interface in interfaces_added {
+                    changes.push(
+                        ChangeCriticality::Dangerous,
+                        Some(type_change.clone()),
+                        format!(
+                            "Object `{}` now implements interface `{}`.",
+                            name, interface
+                        ),
+                    );
+                }
+                for interface in interfaces_removed {
+                    changes.push(
+                        ChangeCriticality::Breaking,
+                        Some(type_change.clone()),
+                        format!(
+                            "Object `{}` no longer implements interface `{}`.",
+                            name, interface
+                        ),
+                    );
+                }
+                changes.classify_fields(type_change, *name, added, removed, changed);
+            }
+        }
+        changes.0
+    }
+}
+
+/// The types changed by breaking or dangerous changes, to find the documents
+/// that select them.
+pub fn get_unsafe_type_changes(
+    changes: &[ClassifiedChange],
+) -> FxHashSet<IncrementalBuildSchemaChange> {
+    changes
+        .iter()
+        .filter(|change| change.criticality != ChangeCriticality::Safe)
+        .filter_map(|change| change.type_change.clone())
+        .collect()
+}
+
+#[derive(Default)]
+struct ClassifiedChanges(Vec<ClassifiedChange>);
+
+impl ClassifiedChanges {
+    fn push(
+        &mut self,
+        criticality: ChangeCriticality,
+        type_change: Option<IncrementalBuildSchemaChange>,
+        message: String,
+    ) {
+        self.0.push(ClassifiedChange {
+            criticality,
+            type_change,
+            message,
+        });
+    }
+
+    fn push_safe(&mut self, type_change: Option<IncrementalBuildSchemaChange>, message: String) {
+        self.push(ChangeCriticality::Safe, type_change, message);
+    }
+
+    fn classify_fields(
+        &mut self,
+        type_change: IncrementalBuildSchemaChange,
+        type_name: StringKey,
+        added: &[TypeChange],
+        removed: &[TypeChange],
+        changed: &[ArgumentChange],
+    ) {
+        // A field with a new type is both removed and added.
+        let mut added_types = added
+            .iter()
+            .map(|field| (field.name, &field.type_))
+            .collect::<FnvHashMap<_, _>>();
+        for field in removed {
+            let description = format!("Field `{}.{}`", type_name, field.name);
+            match added_types.remove(&field.name) {
+                Some(new_type) => {
+                    let criticality = if is_safe_output_type_change(&field.type_, new_type) {
+                        ChangeCriticality::Safe
+                    } else {
+                        ChangeCriticality::Breaking
+                    };
+                    self.push(
+                        criticality,
+                        Some(type_change.clone()),
+                        format!(
+                            "{} changed type from `{}` to `{}`.",
+                            description, field.type_, new_type
+                        ),
+                    );
+                }
+                None => self.push(
+                    ChangeCriticality::Breaking,
+                    Some(type_change.clone()),
+                    format!("{} was removed.", description),
+                ),
+            }
+        }
+        for field in added {
+            if added_types.contains_key(&field.name) {
+                self.push_safe(
+                    Some(type_change.clone()),
+                    format!("Field `{}.{}` was added.", type_name, field.name),
+                );
+            }
+        }
+        for field in changed {
+            self.classify_input_values(
+                Some(type_change.clone()),
+                &field.added,
+                &field.removed,
+                |argument| {
+                    format!(
+                        "Argument `{}` of field `{}.{}`",
+                        argument, type_name, field.name
+                    )
+                },
+            );
+        }
+    }
+
+    /// Classifies the changes of arguments or input object fields.
+    fn classify_input_values(
+        &mut self,
+        type_change: Option<IncrementalBuildSchemaChange>,
+        added: &[TypeChange],
+        removed: &[TypeChange],
+        describe: impl Fn(StringKey) -> String,
+    ) {
+        let mut added_types = added
+            .iter()
+            .map(|value| (value.name, &value.type_))
+            .collect::<FnvHashMap<_, _>>();
+        for value in removed {
+            match added_types.remove(&value.name) {
+                Some(new_type) => {
+                    let criticality = if is_safe_input_type_change(&value.type_, new_type) {
+                        ChangeCriticality::Safe
+                    } else {
+                        ChangeCriticality::Breaking
+                    };
+                    self.push(
+                        criticality,
+                        type_change.clone(),
+                        format!(
+                            "{} changed type from `{}` to `{}`.",
+                            describe(value.name),
+                            value.type_,
+                            new_type
+                        ),
+                    );
+                }
+                None => self.push(
+                    ChangeCriticality::Breaking,
+                    type_change.clone(),
+                    format!("{} was removed.", describe(value.name)),
+                ),
+            }
+        }
+        for value in added {
+            if let Some(type_) = added_types.get(&value.name) {
+                // The default values are not compared, so required values are
+                // considered breaking even if they have a default value.
+                if let Type::NonNull(_) = type_ {
+                    self.push(
+                        ChangeCriticality::Breaking,
+                        type_change.clone(),
+                        format!(
+                            "Required {} was added.",
+                            lowercase_first(describe(value.name))
+                        ),
+                    );
+                } else {
+                    self.push(
+                        ChangeCriticality::Dangerous,
+                        type_change.clone(),
+                        format!(
+                            "Optional {} was added.",
+                            lowercase_first(describe(value.name))
+                        ),
+                    );
+                }
+            }
+        }
+    }
+}
+
+/// The response of a field can be more precise: a nullable type can become
+/// non-null.
+fn is_safe_output_type_change(old_type: &Type, new_type: &Type) -> bool {
+    match (old_type, new_type) {
+        (Type::Named(old_name), Type::Named(new_name)) => old_name == new_name,
+        (Type::List(old_type), Type::List(new_type))
+        | (Type::NonNull(old_type), Type::NonNull(new_type)) => {
+            is_safe_output_type_change(old_type, new_type)
+        }
+        (old_type, Type::NonNull(new_type)) => is_safe_output_type_change(old_type, new_type),
+        _ => false,
+    }
+}
+
+/// Input values can be less precise: a non-null type can become nullable.
+fn is_safe_input_type_change(old_type: &Type, new_type: &Type) -> bool {
+    match (old_type, new_type) {
+        (Type::Named(old_name), Type::Named(new_name)) => old_name == new_name,
+        (Type::List(old_type), Type::List(new_type))
+        | (Type::NonNull(old_type), Type::NonNull(new_type)) => {
+            is_safe_input_type_change(old_type, new_type)
+        }
+        (Type::NonNull(old_type), new_type) => is_safe_input_type_change(old_type, new_type),
+        _ => false,
+    }
+}
+
+fn lowercase_first(text: String) -> String {
+    let mut chars = text.chars();
+    match chars.next() {
+        Some(first) => first.to_lowercase().chain(chars).collect(),
+        None => text,
+    }
+}
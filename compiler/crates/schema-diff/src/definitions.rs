@@ -15,6 +15,8 @@ pub enum DefinitionChange {
     EnumAdded(StringKey),
     EnumChanged {
         name: StringKey,
+        added: Vec<StringKey>,
+        removed: Vec<StringKey>,
     },
     EnumRemoved(StringKey),
     UnionAdded(StringKey),
@@ -56,7 +58,15 @@ pub enum DefinitionChange {
 impl fmt::Debug for DefinitionChange {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         match self {
-            DefinitionChange::EnumChanged { name } => write!(f, "EnumChanged {:?}.", name,),
+            DefinitionChange::EnumChanged {
+                name,
+                added,
+                removed,
+            } => write!(
+                f,
+                "EnumChanged {:?}: added:{:?} removed:{:?}",
+                name, added, removed,
+            ),
             DefinitionChange::UnionChanged {
                 added,
                 removed,
@@ -151,6 +161,16 @@ impl From<TypeAnnotation> for Type {
     }
 }
 
+impl fmt::Display for Type {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Type::Named(name) => write!(f, "{}", name),
+            Type::List(type_) => write!(f, "[{}]", type_),
+            Type::NonNull(type_) => write!(f, "{}!", type_),
+        }
+    }
+}
+
 #[derive(Eq, PartialEq, PartialOrd, Ord)]
 pub struct TypeChange {
     pub name: StringKey,
@@ -10,6 +10,7 @@
 #![deny(clippy::all)]
 
 pub mod check;
+pub mod criticality;
 pub mod definitions;
 use common::SourceLocationKey;
 use fnv::FnvHashMap;
@@ -314,17 +315,33 @@ fn diff(current: Vec<TypeSystemDefinition>, previous: Vec<TypeSystemDefinition>)
                         })),
                         Some(previous_values),
                     ) => {
-                        if values.items.len() != previous_values.items.len() {
-                            changes.push(DefinitionChange::EnumChanged { name: name.value });
-                        } else {
-                            for i in 0..values.items.len() {
-                                if values.items[i].name.value != previous_values.items[i].name.value
-                                {
-                                    changes
-                                        .push(DefinitionChange::EnumChanged { name: name.value });
-                                    break;
-                                }
-                            }
+                        let is_changed = values.items.len() != previous_values.items.len()
+                            || values.items.iter().zip(previous_values.items.iter()).any(
+                                |(value, previous_value)| {
+                                    value.name.value != previous_value.name.value
+                                },
+                            );
+                        if is_changed {
+                            // The values might only be reordered, which changes the generated
+                            // types, but `added` and `removed` are empty then.
+                            let current_names = values
+                                .items
+                                .iter()
+                                .map(|value| value.name)
+                                .collect::<Vec<_>>();
+                            let (added, removed) = compare_string_keys(
+                                &current_names,
+                                previous_values
+                                    .items
+                                    .into_iter()
+                                    .map(|value| value.name)
+                                    .collect(),
+                            );
+                            changes.push(DefinitionChange::EnumChanged {
+                                name: name.value,
+                                added,
+                                removed,
+                            });
                         }
                     }
                     (None, _) => {
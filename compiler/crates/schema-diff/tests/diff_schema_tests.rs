@@ -6,6 +6,7 @@
  */
 
 use intern::string_key::Intern;
+use intern::Lookup;
 use rustc_hash::FxHashSet;
 use schema::build_schema;
 use schema_diff::check::IncrementalBuildSchemaChange;
@@ -89,7 +90,11 @@ fn test_add_enum_value() {
            }
          #",
         ),
-        SchemaChange::DefinitionChanges(vec![DefinitionChange::EnumChanged { name: "A".intern() }])
+        SchemaChange::DefinitionChanges(vec![DefinitionChange::EnumChanged {
+            name: "A".intern(),
+            added: vec!["MAYBE".intern()],
+            removed: vec![],
+        }])
     );
 }
 
@@ -112,7 +117,11 @@ fn test_sort_enum_value() {
            }
          #",
         ),
-        SchemaChange::DefinitionChanges(vec![DefinitionChange::EnumChanged { name: "A".intern() }])
+        SchemaChange::DefinitionChanges(vec![DefinitionChange::EnumChanged {
+            name: "A".intern(),
+            added: vec![],
+            removed: vec![],
+        }])
     );
 }
 
@@ -134,7 +143,11 @@ fn test_remove_enum_value() {
            }
          #",
         ),
-        SchemaChange::DefinitionChanges(vec![DefinitionChange::EnumChanged { name: "A".intern() }])
+        SchemaChange::DefinitionChanges(vec![DefinitionChange::EnumChanged {
+            name: "A".intern(),
+            added: vec![],
+            removed: vec!["MAYBE".intern()],
+        }])
     );
 }
 
@@ -159,7 +172,11 @@ fn test_add_remove_enum_value() {
            }
          #",
         ),
-        SchemaChange::DefinitionChanges(vec![DefinitionChange::EnumChanged { name: "A".intern() }])
+        SchemaChange::DefinitionChanges(vec![DefinitionChange::EnumChanged {
+            name: "A".intern(),
+            added: vec!["NOT_ZUCK".intern(), "ZUCK".intern()],
+            removed: vec!["MARK".intern(), "NOT_MARK".intern()],
+        }])
     );
 }
 
@@ -1445,6 +1462,14 @@ fn sort_change(change: &mut SchemaChange) {
         changes.sort();
         for c in changes {
             match c {
+                DefinitionChange::EnumChanged {
+                    ref mut added,
+                    ref mut removed,
+                    ..
+                } => {
+                    added.sort_by_key(|item| item.lookup());
+                    removed.sort_by_key(|item| item.lookup());
+                }
                 DefinitionChange::UnionChanged {
                     ref mut added,
                     ref mut removed,
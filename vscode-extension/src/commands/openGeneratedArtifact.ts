@@ -0,0 +1,13 @@
+/**
+ * Copyright (c) Meta Platforms, Inc. and affiliates.
+ *
+ * This source code is licensed under the MIT license found in the
+ * LICENSE file in the root directory of this source tree.
+ */
+
+import {Uri, window} from 'vscode';
+
+// Invoked by the generated artifact code lens of the language server.
+export function handleOpenGeneratedArtifactCommand(uri: string): void {
+  window.showTextDocument(Uri.parse(uri));
+}
@@ -7,7 +7,9 @@
 
 import {commands} from 'vscode';
 import {RelayExtensionContext} from '../context';
+import {handleOpenGeneratedArtifactCommand} from './openGeneratedArtifact';
 import {handleRestartLanguageServerCommand} from './restart';
+import {handleShowFragmentSpreadsCommand} from './showFragmentSpreads';
 import {handleShowOutputCommand} from './showOutput';
 import {handleStartCompilerCommand} from './startCompiler';
 import {handleStopCompilerCommand} from './stopCompiler';
@@ -30,5 +32,13 @@ export function registerCommands(context: RelayExtensionContext) {
       'relay.showOutput',
       handleShowOutputCommand.bind(null, context),
     ),
+    commands.registerCommand(
+      'relay.showFragmentSpreads',
+      handleShowFragmentSpreadsCommand.bind(null, context),
+    ),
+    commands.registerCommand(
+      'relay.openGeneratedArtifact',
+      handleOpenGeneratedArtifactCommand,
+    ),
   );
 }
@@ -0,0 +1,31 @@
+/**
+ * Copyright (c) Meta Platforms, Inc. and affiliates.
+ *
+ * This source code is licensed under the MIT license found in the
+ * LICENSE file in the root directory of this source tree.
+ */
+
+import {commands} from 'vscode';
+import {Location, Position} from 'vscode-languageclient';
+import {RelayExtensionContext} from '../context';
+
+// Invoked by the fragment spreads code lens of the language server, with
+// the protocol representation of its arguments.
+export function handleShowFragmentSpreadsCommand(
+  context: RelayExtensionContext,
+  uri: string,
+  position: Position,
+  locations: Location[],
+): void {
+  if (!context.client) {
+    return;
+  }
+
+  const converter = context.client.protocol2CodeConverter;
+  commands.executeCommand(
+    'editor.action.showReferences',
+    converter.asUri(uri),
+    converter.asPosition(position),
+    locations.map(location => converter.asLocation(location)),
+  );
+}